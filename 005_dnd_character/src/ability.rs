@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::race::{Race, Subrace};
+
+// abilities
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Ability {
+    Strength,
+    Dexterity,
+    Constitution,
+    Intelligence,
+    Wisdom,
+    Charisma,
+}
+
+impl Ability {
+    pub const ALL: [Ability; 6] = [
+        Ability::Strength,
+        Ability::Dexterity,
+        Ability::Constitution,
+        Ability::Intelligence,
+        Ability::Wisdom,
+        Ability::Charisma,
+    ];
+}
+
+// a single ability score, always between 1 and 30
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct AbilityScore(u8);
+
+impl AbilityScore {
+    pub const MIN: u8 = 1;
+    pub const MAX: u8 = 30;
+
+    // clamps into 1-30
+    pub fn new(score: u8) -> AbilityScore {
+        AbilityScore(score.clamp(Self::MIN, Self::MAX))
+    }
+
+    pub fn value(self) -> u8 {
+        self.0
+    }
+
+    // (score - 10) / 2 rounded down, so a 9 is -1 and a 1 is -5
+    pub fn modifier(self) -> Modifier {
+        Modifier((self.0 as i8 - 10).div_euclid(2))
+    }
+
+    // None if the bonus would take the score outside 1-30
+    pub fn checked_add(self, bonus: i8) -> Option<AbilityScore> {
+        let score = self.0.checked_add_signed(bonus)?;
+        (Self::MIN..=Self::MAX).contains(&score).then_some(AbilityScore(score))
+    }
+
+    // add a bonus or penalty, stopping at 1 and 30
+    pub fn saturating_add(self, bonus: i8) -> AbilityScore {
+        AbilityScore::new(self.0.saturating_add_signed(bonus))
+    }
+}
+
+impl TryFrom<u8> for AbilityScore {
+    type Error = String;
+
+    fn try_from(score: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&score) {
+            Ok(AbilityScore(score))
+        } else {
+            Err(format!("ability score {} is outside {}-{}", score, Self::MIN, Self::MAX))
+        }
+    }
+}
+
+impl From<AbilityScore> for u8 {
+    fn from(score: AbilityScore) -> u8 {
+        score.0
+    }
+}
+
+impl fmt::Display for AbilityScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// an ability modifier, from -5 for a 1 to +10 for a 30
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+pub struct Modifier(i8);
+
+impl Modifier {
+    pub fn value(self) -> i8 {
+        self.0
+    }
+
+    // hit points from one hit die roll, never less than 1 however low the modifier
+    pub fn hit_points(self, roll: u8) -> u16 {
+        (roll as i16 + self.0 as i16).max(1) as u16
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:+}", self.0)
+    }
+}
+
+// stats with modifiers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbilityScores {
+    pub strength: AbilityScore,
+    pub dexterity: AbilityScore,
+    pub constitution: AbilityScore,
+    pub intelligence: AbilityScore,
+    pub wisdom: AbilityScore,
+    pub charisma: AbilityScore,
+}
+
+impl AbilityScores {
+    pub fn score(&self, ability: Ability) -> AbilityScore {
+        match ability {
+            Ability::Strength => self.strength,
+            Ability::Dexterity => self.dexterity,
+            Ability::Constitution => self.constitution,
+            Ability::Intelligence => self.intelligence,
+            Ability::Wisdom => self.wisdom,
+            Ability::Charisma => self.charisma,
+        }
+    }
+
+    fn score_mut(&mut self, ability: Ability) -> &mut AbilityScore {
+        match ability {
+            Ability::Strength => &mut self.strength,
+            Ability::Dexterity => &mut self.dexterity,
+            Ability::Constitution => &mut self.constitution,
+            Ability::Intelligence => &mut self.intelligence,
+            Ability::Wisdom => &mut self.wisdom,
+            Ability::Charisma => &mut self.charisma,
+        }
+    }
+
+    pub fn set(&mut self, ability: Ability, score: AbilityScore) {
+        *self.score_mut(ability) = score;
+    }
+
+    // shorthand for score(ability).modifier() as a plain number
+    pub fn modifier(&self, ability: Ability) -> i8 {
+        self.score(ability).modifier().value()
+    }
+
+    pub fn apply_racial_bonuses(&mut self, race: Race) {
+        self.apply_bonuses(race.ability_bonuses());
+    }
+
+    pub fn apply_subrace_bonuses(&mut self, subrace: Subrace) {
+        self.apply_bonuses(subrace.ability_bonuses());
+    }
+
+    fn apply_bonuses(&mut self, bonuses: HashMap<Ability, i8>) {
+        for (ability, bonus) in bonuses {
+            let score = self.score_mut(ability);
+            *score = score.saturating_add(bonus);
+        }
+    }
+
+    // a floating +1 racial bonus like the Half-Elf's, applied once per chosen ability
+    pub fn apply_chosen_bonus(&mut self, ability: Ability) {
+        let score = self.score_mut(ability);
+        *score = score.saturating_add(1);
+    }
+}
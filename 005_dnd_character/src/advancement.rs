@@ -0,0 +1,118 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::class::Class;
+use crate::death::LifeState;
+use crate::dice::roll_die_with;
+
+pub const MAX_LEVEL: u8 = 20;
+
+// XP needed to reach each level, indexed by level - 1
+pub const XP_THRESHOLDS: [u32; 20] = [
+    0, 300, 900, 2700, 6500, 14000, 23000, 34000, 48000, 64000, 85000, 100000, 120000, 140000, 165000, 195000, 225000,
+    265000, 305000, 355000,
+];
+
+// whether the campaign levels by experience points or by milestones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AdvancementMode {
+    #[default]
+    Experience,
+    Milestone,
+}
+
+// how to work out the hit points gained on a new level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitPointIncrease {
+    Average,
+    Roll,
+}
+
+// what a level-up gave the character, and which choices still need to be made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelUpSummary {
+    pub level: u8,
+    pub hit_points_gained: u16,
+    pub ability_score_improvement: bool,
+    pub new_expertise: u8,
+}
+
+// the level a given amount of XP is worth
+pub fn level_for_xp(xp: u32) -> u8 {
+    XP_THRESHOLDS.iter().filter(|&&threshold| xp >= threshold).count() as u8
+}
+
+impl Class {
+    // levels that grant an Ability Score Improvement (or a feat)
+    pub fn asi_levels(&self) -> Vec<u8> {
+        self.def().asi_levels.clone()
+    }
+
+    // new expertise picks gained at a level
+    pub fn expertise_at(&self, level: u8) -> u8 {
+        self.def().expertise.iter().filter(|&&(at, _)| at == level).map(|&(_, picks)| picks).sum()
+    }
+}
+
+impl Character {
+    // XP needed for the next level, None at level 20
+    pub fn next_level_xp(&self) -> Option<u32> {
+        XP_THRESHOLDS.get(self.level as usize).copied()
+    }
+
+    // add XP, returns how many levels the character can now gain
+    pub fn award_xp(&mut self, amount: u32) -> u8 {
+        self.experience = self.experience.saturating_add(amount);
+        self.pending_level_ups()
+    }
+
+    // level-ups earned through XP but not yet taken, always 0 with milestone advancement
+    pub fn pending_level_ups(&self) -> u8 {
+        match self.advancement {
+            AdvancementMode::Experience => level_for_xp(self.experience).saturating_sub(self.level),
+            AdvancementMode::Milestone => 0,
+        }
+    }
+
+    // gain a level: hit points and a hit die, the summary says which choices the player gets
+    pub fn level_up<R: Rng + ?Sized>(&mut self, increase: HitPointIncrease, rng: &mut R) -> Option<LevelUpSummary> {
+        if self.level >= MAX_LEVEL {
+            return None;
+        }
+
+        self.level += 1;
+
+        let die = self.class.hit_die();
+        let base = match increase {
+            HitPointIncrease::Average => die / 2 + 1,
+            HitPointIncrease::Roll => roll_die_with(rng, die),
+        };
+        let con_modifier = self.abilities.score(Ability::Constitution).modifier();
+        let toughness = self.subrace.map_or(0, |s| s.hit_points_per_level());
+        let hit_points_gained = con_modifier.hit_points(base) + toughness;
+
+        // the new hit points only come with being awake, and exhaustion still halves the maximum
+        self.hit_points.max += hit_points_gained;
+        if self.life_state == LifeState::Conscious {
+            let raised = self.hit_points.current.saturating_add(hit_points_gained).min(self.hit_point_maximum());
+            self.hit_points.current = raised.max(self.hit_points.current);
+        }
+
+        Some(LevelUpSummary {
+            level: self.level,
+            hit_points_gained,
+            ability_score_improvement: self.class.asi_levels().contains(&self.level),
+            new_expertise: self.class.expertise_at(self.level),
+        })
+    }
+
+    // raise an ability score, capped at 20; returns the increase actually applied
+    pub fn improve_ability(&mut self, ability: Ability, amount: u8) -> u8 {
+        let score = self.abilities.score(ability);
+        let increase = amount.min(20u8.saturating_sub(score.value()));
+        self.abilities.set(ability, score.saturating_add(increase as i8));
+        increase
+    }
+}
@@ -0,0 +1,103 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::content::{content, content_handle, BackgroundDef};
+use crate::dice::roll_die_with;
+use crate::skill::Skill;
+
+// background, one of the definitions loaded from the content packs
+#[derive(Clone, Copy)]
+pub struct Background(&'static BackgroundDef);
+
+impl Background {
+    // every loaded background, in load order
+    pub fn all() -> Vec<Background> {
+        content().backgrounds.iter().map(Background).collect()
+    }
+
+    pub fn id(&self) -> &'static str {
+        &self.0.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        &self.0.name
+    }
+
+    pub fn skills(&self) -> Vec<Skill> {
+        self.0.skills.clone()
+    }
+
+    // a one-line summary shown when choosing a background
+    pub fn description(&self) -> &'static str {
+        &self.0.description
+    }
+
+    // the background feature's name and what it does
+    pub fn feature(&self) -> (&'static str, &'static str) {
+        (&self.0.feature.name, &self.0.feature.description)
+    }
+
+    pub fn tool_proficiencies(&self) -> Vec<&'static str> {
+        self.0.tool_proficiencies.iter().map(String::as_str).collect()
+    }
+
+    // languages of the player's choice
+    pub fn extra_languages(&self) -> usize {
+        self.0.extra_languages
+    }
+
+    // personality traits, rolled on a die with one side per entry (d8 in the SRD)
+    pub fn personality_traits(&self) -> Vec<&'static str> {
+        self.0.personality_traits.iter().map(String::as_str).collect()
+    }
+
+    // d6 tables in the SRD
+    pub fn ideals(&self) -> Vec<&'static str> {
+        self.0.ideals.iter().map(String::as_str).collect()
+    }
+
+    pub fn bonds(&self) -> Vec<&'static str> {
+        self.0.bonds.iter().map(String::as_str).collect()
+    }
+
+    pub fn flaws(&self) -> Vec<&'static str> {
+        self.0.flaws.iter().map(String::as_str).collect()
+    }
+}
+
+// personality rolled or picked from the background tables
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Personality {
+    pub traits: Vec<String>,
+    pub ideal: String,
+    pub bond: String,
+    pub flaw: String,
+}
+
+// roll a die with one side per entry, so homebrew tables don't have to be d6 or d8
+fn roll_on<R: Rng + ?Sized>(rng: &mut R, table: &[&str]) -> usize {
+    roll_die_with(rng, table.len().min(u8::MAX as usize) as u8) as usize - 1
+}
+
+impl Personality {
+    // two different personality traits and one of each of the rest
+    pub fn roll<R: Rng + ?Sized>(background: Background, rng: &mut R) -> Personality {
+        let traits = background.personality_traits();
+        let first = roll_on(rng, &traits);
+        let second = loop {
+            let roll = roll_on(rng, &traits);
+            if roll != first {
+                break roll;
+            }
+        };
+
+        Personality {
+            traits: vec![traits[first].to_string(), traits[second].to_string()],
+            ideal: background.ideals()[roll_on(rng, &background.ideals())].to_string(),
+            bond: background.bonds()[roll_on(rng, &background.bonds())].to_string(),
+            flaw: background.flaws()[roll_on(rng, &background.flaws())].to_string(),
+        }
+    }
+}
+
+content_handle!(Background, "background");
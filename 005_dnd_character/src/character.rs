@@ -0,0 +1,412 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::ability::{Ability, AbilityScores};
+use crate::advancement::AdvancementMode;
+use crate::background::{Background, Personality};
+use crate::class::Class;
+use crate::conditions::{ActiveCondition, Condition, ConditionDuration};
+use crate::death::{DeathSaves, LifeState};
+use crate::equipment::Item;
+use crate::feat::Feat;
+use crate::history::HistoryEntry;
+use crate::hit_points::{DamageType, HitPoints};
+use crate::language::Language;
+use crate::race::{Race, Subrace};
+use crate::resources::ClassResource;
+use crate::skill::Skill;
+
+// character structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Character {
+    pub name: String,
+    pub race: Race,
+    #[serde(default)]
+    pub subrace: Option<Subrace>,
+    pub class: Class,
+    pub background: Background,
+    pub level: u8,
+    #[serde(default)]
+    pub experience: u32,
+    #[serde(default)]
+    pub advancement: AdvancementMode,
+    pub abilities: AbilityScores,
+    pub hit_points: HitPoints,
+    pub resistances: HashSet<DamageType>,
+    pub vulnerabilities: HashSet<DamageType>,
+    pub skills: HashSet<Skill>,
+    pub expertise: HashSet<Skill>,
+    pub saving_throws: HashSet<Ability>,
+    #[serde(default)]
+    pub hit_dice_spent: u8,
+    #[serde(default)]
+    pub spell_slots_used: [u8; 9],
+    #[serde(default)]
+    pub resources_used: HashMap<ClassResource, u8>,
+    #[serde(default)]
+    pub life_state: LifeState,
+    #[serde(default)]
+    pub death_saves: DeathSaves,
+    #[serde(default)]
+    pub conditions: Vec<ActiveCondition>,
+    #[serde(default)]
+    pub feats: Vec<Feat>,
+    #[serde(default)]
+    pub languages: BTreeSet<Language>,
+    #[serde(default)]
+    pub tool_proficiencies: Vec<String>,
+    #[serde(default)]
+    pub personality: Personality,
+    #[serde(default)]
+    pub equipment: Vec<Item>,
+    // spells known or prepared, by name
+    #[serde(default)]
+    pub spells: Vec<String>,
+    // free-form player notes
+    #[serde(default)]
+    pub notes: String,
+    // every recorded change, oldest first; left out of the file until there is one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<HistoryEntry>,
+}
+
+impl Character {
+    // create a level 1 character, racial bonuses must already be applied to the abilities;
+    // extra languages and personality are filled in afterwards
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        race: Race,
+        subrace: Option<Subrace>,
+        class: Class,
+        background: Background,
+        abilities: AbilityScores,
+        skills: HashSet<Skill>,
+        expertise: HashSet<Skill>,
+    ) -> Character {
+        // max hit die plus Con modifier, but never less than 1
+        let con_modifier = abilities.score(Ability::Constitution).modifier();
+        let toughness = subrace.map_or(0, |s| s.hit_points_per_level());
+        let max_hit_points = con_modifier.hit_points(class.hit_die()) + toughness;
+
+        // get saving throw proficiencies
+        let (saving_throw1, saving_throw2) = class.saving_throws();
+        let mut saving_throws = HashSet::new();
+        saving_throws.insert(saving_throw1);
+        saving_throws.insert(saving_throw2);
+
+        Character {
+            name,
+            race,
+            subrace,
+            class,
+            background,
+            level: 1,
+            experience: 0,
+            advancement: AdvancementMode::Experience,
+            abilities,
+            hit_points: HitPoints::new(max_hit_points),
+            resistances: race
+                .damage_resistances()
+                .into_iter()
+                .chain(subrace.map_or(vec![], |s| s.damage_resistances()))
+                .collect(),
+            vulnerabilities: HashSet::new(),
+            skills,
+            expertise,
+            saving_throws,
+            hit_dice_spent: 0,
+            spell_slots_used: [0; 9],
+            resources_used: HashMap::new(),
+            life_state: LifeState::Conscious,
+            death_saves: DeathSaves::default(),
+            conditions: Vec::new(),
+            feats: Vec::new(),
+            languages: race.languages().into_iter().collect(),
+            tool_proficiencies: background.tool_proficiencies().into_iter().map(String::from).collect(),
+            personality: Personality::default(),
+            equipment: Vec::new(),
+            spells: Vec::new(),
+            notes: String::new(),
+            history: Vec::new(),
+        }
+    }
+
+    // display character
+    pub fn display(&self) {
+        println!("\n=== CHARACTER SHEET ===");
+        println!("Name: {}", self.name);
+        match self.subrace {
+            Some(subrace) => println!("Race: {} ({})", self.race, subrace),
+            None => println!("Race: {}", self.race),
+        }
+        println!("Class: {}", self.class);
+        println!("Background: {}", self.background);
+        match (self.advancement, self.next_level_xp()) {
+            (AdvancementMode::Experience, Some(next)) => {
+                println!("Level: {} (XP {}/{})", self.level, self.experience, next)
+            },
+            (AdvancementMode::Experience, None) => println!("Level: {} (XP {})", self.level, self.experience),
+            (AdvancementMode::Milestone, _) => println!("Level: {} (milestone)", self.level),
+        }
+
+        println!("\nAbility Scores:");
+        println!("Strength: {} ({:+})", self.abilities.strength, self.abilities.modifier(Ability::Strength));
+        println!("Dexterity: {} ({:+})", self.abilities.dexterity, self.abilities.modifier(Ability::Dexterity));
+        println!(
+            "Constitution: {} ({:+})",
+            self.abilities.constitution,
+            self.abilities.modifier(Ability::Constitution)
+        );
+        println!(
+            "Intelligence: {} ({:+})",
+            self.abilities.intelligence,
+            self.abilities.modifier(Ability::Intelligence)
+        );
+        println!("Wisdom: {} ({:+})", self.abilities.wisdom, self.abilities.modifier(Ability::Wisdom));
+        println!("Charisma: {} ({:+})", self.abilities.charisma, self.abilities.modifier(Ability::Charisma));
+
+        println!("\nArmor Class: {}", self.armor_class());
+        print!("Hit Points: {}/{}", self.hit_points.current, self.hit_point_maximum());
+        if self.hit_points.temp > 0 {
+            print!(" (+{} temporary)", self.hit_points.temp);
+        }
+        println!();
+
+        match self.life_state {
+            LifeState::Conscious => {},
+            LifeState::Dying => println!(
+                "DYING - Death Saves: {} successes, {} failures",
+                self.death_saves.successes, self.death_saves.failures
+            ),
+            LifeState::Stable => println!("Unconscious but stable"),
+            LifeState::Dead => println!("DEAD"),
+        }
+
+        if !self.resistances.is_empty() {
+            println!("Resistances: {:?}", self.resistances);
+        }
+        if !self.vulnerabilities.is_empty() {
+            println!("Vulnerabilities: {:?}", self.vulnerabilities);
+        }
+
+        if !self.conditions.is_empty() {
+            println!("\nConditions:");
+            for active in &self.conditions {
+                match active.duration {
+                    ConditionDuration::Rounds(rounds) => println!("{:?} ({} rounds left)", active.condition, rounds),
+                    ConditionDuration::UntilRemoved => println!("{:?}", active.condition),
+                }
+            }
+        }
+
+        let slots = self.class.spell_slots(self.level);
+        if slots.iter().any(|&count| count > 0) {
+            println!("\nSpell Slots:");
+            for (i, &count) in slots.iter().enumerate().filter(|(_, &count)| count > 0) {
+                println!("Level {}: {}/{}", i + 1, count.saturating_sub(self.spell_slots_used[i]), count);
+            }
+        }
+
+        let resources = self.class.resources();
+        let resources: Vec<_> = resources.iter().filter(|r| r.max_uses(self) > 0).collect();
+        if !resources.is_empty() {
+            println!("\nClass Resources:");
+            for resource in resources {
+                println!("{:?}: {}/{}", resource, self.resource_remaining(*resource), resource.max_uses(self));
+            }
+        }
+
+        // [P] proficient, [E] expertise
+        println!("\nSaving Throws:");
+        for ability in Ability::ALL {
+            let marker = if self.saving_throws.contains(&ability) { "[P]" } else { "[ ]" };
+            println!("{} {:?} ({:+})", marker, ability, self.saving_throw_modifier(ability));
+        }
+
+        println!("\nSkills:");
+        for (ability, skills) in Skill::grouped_by_ability() {
+            println!("{:?}", ability);
+            for skill in skills {
+                let marker = if self.expertise.contains(&skill) {
+                    "[E]"
+                } else if self.skills.contains(&skill) {
+                    "[P]"
+                } else {
+                    "[ ]"
+                };
+                println!("  {} {:?} ({:+})", marker, skill, self.skill_modifier(skill));
+            }
+        }
+
+        let stats = self.derived_stats();
+        println!("\nDerived Stats:");
+        println!("Proficiency Bonus: {:+}", stats.proficiency_bonus);
+        println!("Initiative: {:+}", stats.initiative);
+        println!("Passive Perception: {}", stats.passive_perception);
+        println!("Passive Investigation: {}", stats.passive_investigation);
+        println!("Passive Insight: {}", stats.passive_insight);
+        println!("Speed: {}", stats.speeds);
+        println!(
+            "Carrying Capacity: {} lb (push, drag or lift {} lb)",
+            stats.carrying.capacity, stats.carrying.push_drag_lift
+        );
+        println!(
+            "Encumbered over {} lb, heavily encumbered over {} lb",
+            stats.carrying.encumbered, stats.carrying.heavily_encumbered
+        );
+        println!("Hit Dice: {}/{} (d{})", stats.hit_dice.remaining, stats.hit_dice.total, stats.hit_dice.die);
+
+        if !self.feats.is_empty() {
+            println!("\nFeats: {:?}", self.feats);
+        }
+
+        let attacks = self.attacks();
+        if !attacks.is_empty() {
+            println!("\nAttacks:");
+            for attack in attacks {
+                println!("{}: {:+} to hit, {} {:?}", attack.name, attack.to_hit, attack.damage, attack.damage_type);
+            }
+        }
+
+        println!("\nEquipment:");
+        if self.equipment.is_empty() {
+            println!("None");
+        }
+        for item in &self.equipment {
+            if item.quantity > 1 {
+                println!("{} x{}", item.name, item.quantity);
+            } else {
+                println!("{}", item.name);
+            }
+        }
+        println!("Carrying {} lb", self.carried_weight());
+
+        let (feature, description) = self.background.feature();
+        println!("\nBackground Feature: {}", feature);
+        println!("  {}", description);
+        if !self.personality.traits.is_empty() {
+            println!("Personality Traits:");
+            for personality_trait in &self.personality.traits {
+                println!("  {}", personality_trait);
+            }
+        }
+        if !self.personality.ideal.is_empty() {
+            println!("Ideal: {}", self.personality.ideal);
+        }
+        if !self.personality.bond.is_empty() {
+            println!("Bond: {}", self.personality.bond);
+        }
+        if !self.personality.flaw.is_empty() {
+            println!("Flaw: {}", self.personality.flaw);
+        }
+
+        let languages: Vec<String> = self.languages.iter().map(|l| format!("{:?}", l)).collect();
+        println!("\nLanguages: {}", if languages.is_empty() { "None".to_string() } else { languages.join(", ") });
+        println!(
+            "Tool Proficiencies: {}",
+            if self.tool_proficiencies.is_empty() { "None".to_string() } else { self.tool_proficiencies.join(", ") }
+        );
+    }
+
+    // racial walking speed before conditions
+    pub fn base_speed(&self) -> u8 {
+        self.race.speed() + self.subrace.map_or(0, |s| s.speed_bonus())
+    }
+
+    // racial swimming and climbing speeds, whichever of race and subrace is faster
+    pub fn base_swim_speed(&self) -> u8 {
+        self.race.swim_speed().max(self.subrace.map_or(0, |s| s.swim_speed()))
+    }
+
+    pub fn base_climb_speed(&self) -> u8 {
+        self.race.climb_speed().max(self.subrace.map_or(0, |s| s.climb_speed()))
+    }
+
+    // calculate skill modifier
+    pub fn skill_modifier(&self, skill: Skill) -> i8 {
+        let base = self.abilities.modifier(skill.associated_ability());
+
+        base + self.skill_proficiency(skill)
+    }
+
+    // expend a spell slot of the given level (1-9), returns false if none are left
+    pub fn expend_spell_slot(&mut self, slot_level: u8) -> bool {
+        if !(1..=9).contains(&slot_level) {
+            return false;
+        }
+
+        let index = slot_level as usize - 1;
+        if self.spell_slots_used[index] >= self.class.spell_slots(self.level)[index] {
+            return false;
+        }
+
+        self.spell_slots_used[index] += 1;
+        true
+    }
+
+    pub fn resource_remaining(&self, resource: ClassResource) -> u8 {
+        let used = self.resources_used.get(&resource).copied().unwrap_or(0);
+        resource.max_uses(self).saturating_sub(used)
+    }
+
+    // use up some of a class resource, returns false if there isn't enough left
+    pub fn use_resource(&mut self, resource: ClassResource, amount: u8) -> bool {
+        if !self.class.resources().contains(&resource) || self.resource_remaining(resource) < amount {
+            return false;
+        }
+
+        *self.resources_used.entry(resource).or_insert(0) += amount;
+        true
+    }
+
+    // apply resistances and vulnerabilities, then take the damage, returns the damage actually dealt
+    pub fn take_damage(&mut self, amount: u16, damage_type: DamageType) -> u16 {
+        // petrified creatures resist everything and are immune to poison
+        let petrified = self.has_condition(Condition::Petrified);
+        if petrified && damage_type == DamageType::Poison {
+            return 0;
+        }
+
+        let resistant = petrified || self.resistances.contains(&damage_type);
+        let vulnerable = self.vulnerabilities.contains(&damage_type);
+
+        // resistance and vulnerability to the same type cancel out
+        let damage = match (resistant, vulnerable) {
+            (true, false) => amount / 2,
+            (false, true) => amount.saturating_mul(2),
+            _ => amount,
+        };
+
+        if self.life_state == LifeState::Dead {
+            return 0;
+        }
+
+        let was_at_zero = self.hit_points.current == 0;
+        let excess = self.hit_points.lose(damage);
+        self.check_dying(was_at_zero, excess);
+
+        damage
+    }
+
+    // regain hit points up to the (possibly reduced) maximum, returns the amount regained
+    pub(crate) fn restore_hit_points(&mut self, amount: u16) -> u16 {
+        let missing = self.hit_point_maximum().saturating_sub(self.hit_points.current);
+        self.hit_points.heal(amount.min(missing))
+    }
+
+    // regain hit points, capped at the maximum; any healing brings a dying character back
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        if self.life_state == LifeState::Dead {
+            return 0;
+        }
+
+        let healed = self.restore_hit_points(amount);
+        if self.hit_points.current > 0 {
+            self.life_state = LifeState::Conscious;
+            self.death_saves = DeathSaves::default();
+        }
+
+        healed
+    }
+}
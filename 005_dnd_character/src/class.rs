@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use crate::ability::Ability;
+use crate::background::Background;
+use crate::content::{content, content_handle, ClassDef};
+use crate::race::Race;
+use crate::skill::Skill;
+
+// class, one of the definitions loaded from the content packs
+#[derive(Clone, Copy)]
+pub struct Class(&'static ClassDef);
+
+impl Class {
+    // every loaded class, in load order
+    pub fn all() -> Vec<Class> {
+        content().classes.iter().map(Class).collect()
+    }
+
+    pub fn id(&self) -> &'static str {
+        &self.0.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        &self.0.name
+    }
+
+    // the rest of the rules data, for the modules that build on it
+    pub(crate) fn def(&self) -> &'static ClassDef {
+        self.0
+    }
+
+    pub fn hit_die(&self) -> u8 {
+        self.0.hit_die
+    }
+
+    // the ability the class relies on most, used when improving scores automatically
+    pub fn primary_ability(&self) -> Ability {
+        self.0.primary_ability
+    }
+
+    pub fn saving_throws(&self) -> (Ability, Ability) {
+        self.0.saving_throws
+    }
+
+    // a one-line summary shown when choosing a class
+    pub fn description(&self) -> &'static str {
+        &self.0.description
+    }
+}
+
+// get class skills based on the selected class
+pub fn get_class_skills(class: &Class) -> Vec<Skill> {
+    class.0.skill_choices.clone()
+}
+
+// get the number of skill proficiencies based on class
+pub fn get_skill_proficiency_count(class: &Class) -> usize {
+    class.0.skill_count
+}
+
+// skills the character gets from background and race without choosing
+pub fn granted_skills(background: Background, race: Race) -> HashSet<Skill> {
+    let mut skills: HashSet<Skill> = background.skills().into_iter().collect();
+    skills.extend(race.racial_skills());
+    skills
+}
+
+// class skills that can still be chosen, and how many more to choose
+pub fn class_skill_choices(class: &Class, granted: &HashSet<Skill>) -> (Vec<Skill>, usize) {
+    let mut available: Vec<Skill> = Vec::new();
+    for skill in get_class_skills(class) {
+        if !granted.contains(&skill) && !available.contains(&skill) {
+            available.push(skill);
+        }
+    }
+
+    // background and racial skills count towards the class total, and there
+    // can't be more picks than skills left to pick from
+    let remaining = get_skill_proficiency_count(class).saturating_sub(granted.len()).min(available.len());
+
+    (available, remaining)
+}
+
+// number of expertise picks at 1st level
+pub fn get_expertise_count(class: &Class) -> usize {
+    class.expertise_at(1) as usize
+}
+
+content_handle!(Class, "class");
@@ -0,0 +1,268 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::death::LifeState;
+use crate::roll::RollMode;
+
+// rounds in a minute and an hour, for condition durations
+pub const ROUNDS_PER_MINUTE: u32 = 10;
+pub const ROUNDS_PER_HOUR: u32 = 600;
+
+// standard conditions, exhaustion carries its level (1-6)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Condition {
+    Blinded,
+    Charmed,
+    Frightened,
+    Grappled,
+    Incapacitated,
+    Invisible,
+    Paralyzed,
+    Petrified,
+    Poisoned,
+    Prone,
+    Restrained,
+    Stunned,
+    Unconscious,
+    Exhaustion(u8),
+}
+
+impl Condition {
+    pub const STANDARD: [Condition; 13] = [
+        Condition::Blinded,
+        Condition::Charmed,
+        Condition::Frightened,
+        Condition::Grappled,
+        Condition::Incapacitated,
+        Condition::Invisible,
+        Condition::Paralyzed,
+        Condition::Petrified,
+        Condition::Poisoned,
+        Condition::Prone,
+        Condition::Restrained,
+        Condition::Stunned,
+        Condition::Unconscious,
+    ];
+
+    // conditions that bring others along with them
+    fn implied(&self) -> Vec<Condition> {
+        match self {
+            Condition::Paralyzed | Condition::Petrified | Condition::Stunned => vec![Condition::Incapacitated],
+            Condition::Unconscious => vec![Condition::Incapacitated, Condition::Prone],
+            _ => vec![],
+        }
+    }
+
+    // same condition, ignoring the exhaustion level
+    fn same_kind(&self, other: &Condition) -> bool {
+        matches!((self, other), (Condition::Exhaustion(_), Condition::Exhaustion(_))) || self == other
+    }
+}
+
+// how long a condition lasts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionDuration {
+    Rounds(u32),
+    UntilRemoved,
+}
+
+impl ConditionDuration {
+    pub fn minutes(minutes: u32) -> ConditionDuration {
+        ConditionDuration::Rounds(minutes * ROUNDS_PER_MINUTE)
+    }
+
+    pub fn hours(hours: u32) -> ConditionDuration {
+        ConditionDuration::Rounds(hours * ROUNDS_PER_HOUR)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveCondition {
+    pub condition: Condition,
+    pub duration: ConditionDuration,
+}
+
+// what a d20 roll is for, so conditions can tell which rolls they affect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollKind {
+    AbilityCheck,
+    SavingThrow(Ability),
+    Attack,
+}
+
+impl Character {
+    // apply a condition, replacing any existing one of the same kind (and exhaustion level)
+    pub fn apply_condition(&mut self, condition: Condition, duration: ConditionDuration) {
+        let condition = match condition {
+            Condition::Exhaustion(level) => Condition::Exhaustion(level.clamp(1, 6)),
+            other => other,
+        };
+
+        self.conditions.retain(|active| !active.condition.same_kind(&condition));
+        self.conditions.push(ActiveCondition { condition, duration });
+
+        match self.exhaustion_level() {
+            6 => self.life_state = LifeState::Dead,
+            // the hit point maximum halves at 4th level of exhaustion
+            4..=5 => self.hit_points.current = self.hit_points.current.min(self.hit_point_maximum()),
+            _ => {},
+        }
+    }
+
+    // remove a condition, any exhaustion level is removed by Exhaustion(_)
+    pub fn remove_condition(&mut self, condition: Condition) -> bool {
+        let before = self.conditions.len();
+        self.conditions.retain(|active| !active.condition.same_kind(&condition));
+        self.conditions.len() != before
+    }
+
+    // the character's conditions, including implied ones and unconsciousness at 0 HP
+    pub fn active_conditions(&self) -> Vec<Condition> {
+        let mut conditions: Vec<Condition> = Vec::new();
+
+        let mut add = |condition: Condition| {
+            for c in std::iter::once(condition).chain(condition.implied()) {
+                if !conditions.contains(&c) {
+                    conditions.push(c);
+                }
+            }
+        };
+
+        for active in &self.conditions {
+            add(active.condition);
+        }
+        if let LifeState::Dying | LifeState::Stable = self.life_state {
+            add(Condition::Unconscious);
+        }
+
+        conditions
+    }
+
+    pub fn has_condition(&self, condition: Condition) -> bool {
+        self.active_conditions().iter().any(|c| c.same_kind(&condition))
+    }
+
+    pub fn exhaustion_level(&self) -> u8 {
+        self.conditions
+            .iter()
+            .filter_map(|active| match active.condition {
+                Condition::Exhaustion(level) => Some(level),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    // let time pass, returns the conditions that wore off
+    pub fn advance_rounds(&mut self, rounds: u32) -> Vec<Condition> {
+        let mut expired = Vec::new();
+
+        self.conditions.retain_mut(|active| match &mut active.duration {
+            ConditionDuration::Rounds(left) if *left <= rounds => {
+                expired.push(active.condition);
+                false
+            },
+            ConditionDuration::Rounds(left) => {
+                *left -= rounds;
+                true
+            },
+            ConditionDuration::UntilRemoved => true,
+        });
+
+        expired
+    }
+
+    // hit point maximum after exhaustion
+    pub fn hit_point_maximum(&self) -> u16 {
+        if self.exhaustion_level() >= 4 {
+            self.hit_points.max / 2
+        } else {
+            self.hit_points.max
+        }
+    }
+
+    // advantage or disadvantage imposed by conditions on a roll
+    pub fn condition_roll_mode(&self, kind: RollKind) -> RollMode {
+        let (advantage, disadvantage) = self.condition_roll_sources(kind);
+        RollMode::resolve(advantage, disadvantage)
+    }
+
+    // whether any condition grants advantage and whether any imposes disadvantage
+    pub(crate) fn condition_roll_sources(&self, kind: RollKind) -> (bool, bool) {
+        let conditions = self.active_conditions();
+        let exhaustion = self.exhaustion_level();
+        let has = |condition: Condition| conditions.contains(&condition);
+
+        let mut advantage = false;
+        let mut disadvantage = false;
+
+        match kind {
+            RollKind::AbilityCheck => {
+                disadvantage |= has(Condition::Frightened) || has(Condition::Poisoned) || exhaustion >= 1;
+            },
+            RollKind::SavingThrow(ability) => {
+                disadvantage |= exhaustion >= 3;
+                disadvantage |= ability == Ability::Dexterity && has(Condition::Restrained);
+            },
+            RollKind::Attack => {
+                advantage |= has(Condition::Invisible);
+                disadvantage |= has(Condition::Blinded)
+                    || has(Condition::Frightened)
+                    || has(Condition::Poisoned)
+                    || has(Condition::Prone)
+                    || has(Condition::Restrained)
+                    || exhaustion >= 3;
+            },
+        }
+
+        (advantage, disadvantage)
+    }
+
+    // the condition that makes a roll fail automatically, if any
+    pub fn condition_auto_fail(&self, kind: RollKind) -> Option<Condition> {
+        match kind {
+            RollKind::SavingThrow(Ability::Strength | Ability::Dexterity) => {
+                self.active_conditions().into_iter().find(|c| {
+                    matches!(
+                        c,
+                        Condition::Paralyzed | Condition::Petrified | Condition::Stunned | Condition::Unconscious
+                    )
+                })
+            },
+            _ => None,
+        }
+    }
+
+    // walking speed after conditions
+    pub fn speed(&self) -> u8 {
+        self.movement(self.base_speed())
+    }
+
+    // any speed after conditions and exhaustion
+    pub(crate) fn movement(&self, base: u8) -> u8 {
+        let conditions = self.active_conditions();
+        let exhaustion = self.exhaustion_level();
+
+        let immobile = exhaustion >= 5
+            || conditions.iter().any(|c| {
+                matches!(
+                    c,
+                    Condition::Grappled
+                        | Condition::Restrained
+                        | Condition::Paralyzed
+                        | Condition::Petrified
+                        | Condition::Stunned
+                        | Condition::Unconscious
+                )
+            });
+
+        if immobile {
+            0
+        } else if exhaustion >= 2 {
+            base / 2
+        } else {
+            base
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::ability::Ability;
+use crate::equipment::WeaponCategory;
+use crate::hit_points::DamageType;
+use crate::language::Language;
+use crate::names::NameStyle;
+use crate::resources::ClassResource;
+use crate::skill::Skill;
+use crate::spellcasting::Spellcasting;
+
+// the built-in SRD pack, always loaded first
+pub(crate) const SRD: [(&str, &str); 3] = [
+    ("srd/races.toml", include_str!("../data/content/srd/races.toml")),
+    ("srd/classes.toml", include_str!("../data/content/srd/classes.toml")),
+    ("srd/backgrounds.toml", include_str!("../data/content/srd/backgrounds.toml")),
+];
+
+static CONTENT: OnceLock<Content> = OnceLock::new();
+
+// one content file; a pack is a directory of these, and any file can hold
+// any mix of races, classes and backgrounds
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContentFile {
+    #[serde(default)]
+    pub race: Vec<RaceDef>,
+    #[serde(default)]
+    pub class: Vec<ClassDef>,
+    #[serde(default)]
+    pub background: Vec<BackgroundDef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RaceDef {
+    // stable key written to save files
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub ability_bonuses: HashMap<Ability, i8>,
+    // +1 to this many abilities without a racial bonus, chosen by the player
+    #[serde(default)]
+    pub ability_choices: usize,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub languages: Vec<Language>,
+    #[serde(default)]
+    pub extra_languages: usize,
+    #[serde(default = "default_speed")]
+    pub speed: u8,
+    // 0 for races that swim and climb at the cost of extra movement
+    #[serde(default)]
+    pub swim_speed: u8,
+    #[serde(default)]
+    pub climb_speed: u8,
+    #[serde(default)]
+    pub resistances: Vec<DamageType>,
+    #[serde(default = "default_name_style")]
+    pub name_style: NameStyle,
+    #[serde(default, rename = "subrace")]
+    pub subraces: Vec<SubraceDef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubraceDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub ability_bonuses: HashMap<Ability, i8>,
+    #[serde(default)]
+    pub resistances: Vec<DamageType>,
+    #[serde(default)]
+    pub hit_points_per_level: u16,
+    #[serde(default)]
+    pub speed_bonus: u8,
+    #[serde(default)]
+    pub swim_speed: u8,
+    #[serde(default)]
+    pub climb_speed: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClassDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub hit_die: u8,
+    pub primary_ability: Ability,
+    pub saving_throws: (Ability, Ability),
+    #[serde(default)]
+    pub skill_choices: Vec<Skill>,
+    #[serde(default)]
+    pub skill_count: usize,
+    // (level, picks) pairs
+    #[serde(default)]
+    pub expertise: Vec<(u8, u8)>,
+    #[serde(default = "default_asi_levels")]
+    pub asi_levels: Vec<u8>,
+    #[serde(default)]
+    pub spellcasting: Spellcasting,
+    #[serde(default)]
+    pub resources: Vec<ClassResource>,
+    #[serde(default)]
+    pub weapon_categories: Vec<WeaponCategory>,
+    #[serde(default)]
+    pub weapons: Vec<String>,
+    #[serde(default)]
+    pub unarmored_defense: Option<UnarmoredDefense>,
+    // level the feature starts at
+    #[serde(default)]
+    pub jack_of_all_trades: Option<u8>,
+    #[serde(default)]
+    pub reliable_talent: Option<u8>,
+    // each choice is a list of options, each option a list of items like "Javelin x4"
+    #[serde(default)]
+    pub starting_equipment: Vec<Vec<Vec<String>>>,
+}
+
+// 10 + Dexterity + another ability while wearing no armor
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UnarmoredDefense {
+    pub ability: Ability,
+    // whether a shield can be used with it
+    pub shield: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackgroundDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub tool_proficiencies: Vec<String>,
+    #[serde(default)]
+    pub extra_languages: usize,
+    pub feature: FeatureDef,
+    pub personality_traits: Vec<String>,
+    pub ideals: Vec<String>,
+    pub bonds: Vec<String>,
+    pub flaws: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureDef {
+    pub name: String,
+    pub description: String,
+}
+
+fn default_speed() -> u8 {
+    30
+}
+
+fn default_name_style() -> NameStyle {
+    NameStyle::Human
+}
+
+fn default_asi_levels() -> Vec<u8> {
+    vec![4, 8, 12, 16, 19]
+}
+
+// everything loaded from the built-in pack and any extra packs, in load order
+#[derive(Debug, Default)]
+pub struct Content {
+    pub races: Vec<RaceDef>,
+    pub classes: Vec<ClassDef>,
+    pub backgrounds: Vec<BackgroundDef>,
+}
+
+impl Content {
+    // the SRD pack on its own
+    pub fn builtin() -> Content {
+        let mut content = Content::default();
+        for (name, text) in SRD {
+            let file = parse(text).unwrap_or_else(|e| panic!("built-in content {} is invalid: {}", name, e));
+            content.add(file).unwrap_or_else(|e| panic!("built-in content {} is invalid: {}", name, e));
+        }
+        content
+    }
+
+    // the SRD pack plus every .toml file in the given directories
+    pub fn with_packs(dirs: &[PathBuf]) -> io::Result<Content> {
+        let mut content = Content::builtin();
+        for dir in dirs {
+            let files = pack_files(dir).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", dir.display(), e)))?;
+            for path in files {
+                let text = fs::read_to_string(&path)
+                    .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+                let invalid =
+                    |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e));
+                content.add(parse(&text).map_err(invalid)?).map_err(invalid)?;
+            }
+        }
+        Ok(content)
+    }
+
+    // add a file's definitions, refusing ids that are already taken
+    fn add(&mut self, file: ContentFile) -> Result<(), String> {
+        let mut races: HashSet<&str> = self.races.iter().map(|r| r.id.as_str()).collect();
+        let mut subraces: HashSet<&str> = self.races.iter().flat_map(|r| &r.subraces).map(|s| s.id.as_str()).collect();
+        let mut classes: HashSet<&str> = self.classes.iter().map(|c| c.id.as_str()).collect();
+        let mut backgrounds: HashSet<&str> = self.backgrounds.iter().map(|b| b.id.as_str()).collect();
+
+        for race in &file.race {
+            if !races.insert(&race.id) {
+                return Err(format!("duplicate race id '{}'", race.id));
+            }
+            check_ability_choices(&race.id, race.ability_choices, race.ability_bonuses.len())?;
+            for subrace in &race.subraces {
+                if !subraces.insert(&subrace.id) {
+                    return Err(format!("duplicate subrace id '{}'", subrace.id));
+                }
+            }
+        }
+        for class in &file.class {
+            if !classes.insert(&class.id) {
+                return Err(format!("duplicate class id '{}'", class.id));
+            }
+            check_hit_die(&class.id, class.hit_die as i64)?;
+            let choices: HashSet<&Skill> = class.skill_choices.iter().collect();
+            check_skill_count(&class.id, class.skill_count, choices.len())?;
+            for &(level, picks) in &class.expertise {
+                check_expertise(&class.id, level, picks as usize, class.skill_count)?;
+            }
+        }
+        for background in &file.background {
+            if !backgrounds.insert(&background.id) {
+                return Err(format!("duplicate background id '{}'", background.id));
+            }
+            // the personality tables are rolled on, two different traits included
+            if background.personality_traits.len() < 2
+                || [&background.ideals, &background.bonds, &background.flaws].iter().any(|t| t.is_empty())
+            {
+                return Err(format!(
+                    "background '{}' needs two personality traits and an ideal, bond and flaw",
+                    background.id
+                ));
+            }
+        }
+
+        self.races.extend(file.race);
+        self.classes.extend(file.class);
+        self.backgrounds.extend(file.background);
+        Ok(())
+    }
+}
+
+// hit dice a class can use
+const HIT_DICE: [i64; 4] = [6, 8, 10, 12];
+
+// class and race rules the loader enforces and validate-content reports, kept here so the two agree
+pub(crate) fn check_hit_die(id: &str, die: i64) -> Result<(), String> {
+    if HIT_DICE.contains(&die) {
+        Ok(())
+    } else {
+        Err(format!("class '{}' has an invalid hit die d{}, use 6, 8, 10 or 12", id, die))
+    }
+}
+
+// the class has to be able to offer as many skills as it asks for
+pub(crate) fn check_skill_count(id: &str, count: usize, distinct_choices: usize) -> Result<(), String> {
+    if count > distinct_choices {
+        Err(format!("class '{}' asks for {} skills but only offers {}", id, count, distinct_choices))
+    } else {
+        Ok(())
+    }
+}
+
+// expertise goes into skills the character is proficient in, and the class only
+// promises its own skill count, background and racial skills included
+pub(crate) fn check_expertise(id: &str, level: u8, picks: usize, skill_count: usize) -> Result<(), String> {
+    if picks > skill_count {
+        Err(format!(
+            "class '{}' gives {} expertise picks at level {} but only {} skills",
+            id, picks, level, skill_count
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// the chosen +1 bonuses go into abilities without a racial bonus, each one different
+pub(crate) fn check_ability_choices(id: &str, choices: usize, bonuses: usize) -> Result<(), String> {
+    let open = Ability::ALL.len().saturating_sub(bonuses);
+    if choices > open {
+        Err(format!("race '{}' has {} ability choices but only {} abilities without a racial bonus", id, choices, open))
+    } else {
+        Ok(())
+    }
+}
+
+fn parse(text: &str) -> Result<ContentFile, String> {
+    toml::from_str(text).map_err(|e| e.message().to_string())
+}
+
+// .toml files directly inside a pack directory, sorted so load order is stable
+pub(crate) fn pack_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|e| e == "toml") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// races, classes and backgrounds are handles to loaded definitions; they compare and
+// hash by id, display as their name and are saved as their id
+macro_rules! content_handle {
+    ($handle:ident, $kind:literal) => {
+        impl std::cmp::PartialEq for $handle {
+            fn eq(&self, other: &$handle) -> bool {
+                self.id() == other.id()
+            }
+        }
+
+        impl std::cmp::Eq for $handle {}
+
+        impl std::hash::Hash for $handle {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::hash::Hash::hash(self.id(), state);
+            }
+        }
+
+        impl std::fmt::Debug for $handle {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}({})", stringify!($handle), self.id())
+            }
+        }
+
+        impl std::fmt::Display for $handle {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl std::str::FromStr for $handle {
+            type Err = String;
+
+            // accepts the id or the name in any case, with or without spaces and dashes
+            fn from_str(s: &str) -> Result<$handle, String> {
+                $handle::all()
+                    .into_iter()
+                    .find(|v| crate::matches_name(s, v.id()) || crate::matches_name(s, v.name()))
+                    .ok_or_else(|| format!(concat!("unknown ", $kind, ": {}"), s))
+            }
+        }
+
+        // saved by id; older saves used the variant name, which still matches
+        impl serde::Serialize for $handle {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.id())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $handle {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<$handle, D::Error> {
+                let id = <String as serde::Deserialize>::deserialize(deserializer)?;
+                id.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+pub(crate) use content_handle;
+
+// load the SRD pack plus extra packs; has to happen before any rules are looked up
+pub fn load_content(dirs: &[PathBuf]) -> io::Result<()> {
+    let content = Content::with_packs(dirs)?;
+    CONTENT.set(content).map_err(|_| io::Error::other("content has already been loaded"))
+}
+
+// the loaded content, falling back to the SRD pack if nothing was loaded
+pub fn content() -> &'static Content {
+    CONTENT.get_or_init(Content::builtin)
+}
@@ -0,0 +1,538 @@
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+use crate::ability::Ability;
+use crate::content::{
+    check_ability_choices, check_expertise, check_hit_die, check_skill_count, pack_files, ContentFile, SRD,
+};
+use crate::equipment::{find_item, parse_item, ItemKind, WeaponCategory};
+use crate::hit_points::DamageType;
+use crate::language::Language;
+use crate::names::NameStyle;
+use crate::resources::ClassResource;
+use crate::skill::Skill;
+use crate::spellcasting::Spellcasting;
+
+const RACE_FIELDS: [&str; 14] = [
+    "id",
+    "name",
+    "description",
+    "ability_bonuses",
+    "ability_choices",
+    "skills",
+    "languages",
+    "extra_languages",
+    "speed",
+    "swim_speed",
+    "climb_speed",
+    "resistances",
+    "name_style",
+    "subrace",
+];
+const SUBRACE_FIELDS: [&str; 9] = [
+    "id",
+    "name",
+    "description",
+    "ability_bonuses",
+    "resistances",
+    "hit_points_per_level",
+    "speed_bonus",
+    "swim_speed",
+    "climb_speed",
+];
+const CLASS_FIELDS: [&str; 18] = [
+    "id",
+    "name",
+    "description",
+    "hit_die",
+    "primary_ability",
+    "saving_throws",
+    "skill_choices",
+    "skill_count",
+    "expertise",
+    "asi_levels",
+    "spellcasting",
+    "resources",
+    "weapon_categories",
+    "weapons",
+    "unarmored_defense",
+    "jack_of_all_trades",
+    "reliable_talent",
+    "starting_equipment",
+];
+const BACKGROUND_FIELDS: [&str; 11] = [
+    "id",
+    "name",
+    "description",
+    "skills",
+    "tool_proficiencies",
+    "extra_languages",
+    "feature",
+    "personality_traits",
+    "ideals",
+    "bonds",
+    "flaws",
+];
+
+// one problem found in a content file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentProblem {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ContentProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+// walks content files, collecting every problem instead of stopping at the first
+struct Checker {
+    problems: Vec<ContentProblem>,
+    // (kind, id) -> where it was first defined
+    ids: HashMap<(&'static str, String), (String, usize)>,
+    file: String,
+    text: String,
+    // line of the entry being checked, for values without a position of their own
+    entry_line: usize,
+}
+
+impl Checker {
+    fn line(&self, span: Option<Range<usize>>) -> usize {
+        match span {
+            Some(span) => self.text[..span.start.min(self.text.len())].matches('\n').count() + 1,
+            None => self.entry_line,
+        }
+    }
+
+    fn report(&mut self, span: Option<Range<usize>>, message: String) {
+        let line = self.line(span);
+        self.problems.push(ContentProblem { file: self.file.clone(), line, message });
+    }
+
+    fn check_file(&mut self, file: String, text: String) {
+        self.file = file;
+        self.text = text;
+        self.entry_line = 1;
+        let found = self.problems.len();
+
+        let document = match ImDocument::parse(self.text.clone()) {
+            Ok(document) => document,
+            Err(e) => {
+                self.report(e.span(), format!("invalid TOML: {}", e.message().trim().replace('\n', "; ")));
+                return;
+            },
+        };
+
+        for (key, item) in document.iter() {
+            let checks: fn(&mut Checker, &dyn TableLike) = match key {
+                "race" => Checker::check_race,
+                "class" => Checker::check_class,
+                "background" => Checker::check_background,
+                _ => {
+                    let span = document.key(key).and_then(|k| k.span());
+                    self.report(
+                        span,
+                        format!("unknown section '{}', expected [[race]], [[class]] or [[background]]", key),
+                    );
+                    continue;
+                },
+            };
+            for (entry, span) in entries(item) {
+                self.entry_line = self.line(span);
+                checks(self, entry);
+            }
+        }
+
+        // anything the loader would still refuse, in case the checks above missed it
+        if self.problems.len() == found {
+            if let Err(e) = toml::from_str::<ContentFile>(&self.text) {
+                self.report(e.span(), e.message().to_string());
+            }
+        }
+        self.problems[found..].sort_by_key(|p| p.line);
+    }
+
+    fn check_race(&mut self, race: &dyn TableLike) {
+        self.check_fields(race, "race", &RACE_FIELDS);
+        let id = self.check_id(race, "race");
+        let bonuses = self.check_ability_bonuses(race);
+        self.check_count(race, "ability_choices", 0, 6);
+        if let Some(choices) = race.get("ability_choices").and_then(Item::as_integer).filter(|&n| n >= 0) {
+            if let Err(e) = check_ability_choices(&id, choices as usize, bonuses.len()) {
+                self.report(span_of(race, "ability_choices"), e);
+            }
+        }
+        self.check_names::<Skill>(race, "skills", "skill");
+        self.check_names::<Language>(race, "languages", "language");
+        self.check_count(race, "extra_languages", 0, Language::ALL.len() as i64);
+        for speed in ["speed", "swim_speed", "climb_speed"] {
+            self.check_count(race, speed, 0, u8::MAX as i64);
+        }
+        self.check_names::<DamageType>(race, "resistances", "damage type");
+        self.check_name::<NameStyle>(race, "name_style", "name style");
+
+        if let Some(item) = race.get("subrace") {
+            for (subrace, span) in entries(item) {
+                let race_line = self.entry_line;
+                self.entry_line = self.line(span);
+                self.check_fields(subrace, "subrace", &SUBRACE_FIELDS);
+                self.check_id(subrace, "subrace");
+                self.check_ability_bonuses(subrace);
+                self.check_names::<DamageType>(subrace, "resistances", "damage type");
+                self.check_count(subrace, "hit_points_per_level", 0, u16::MAX as i64);
+                for speed in ["speed_bonus", "swim_speed", "climb_speed"] {
+                    self.check_count(subrace, speed, 0, u8::MAX as i64);
+                }
+                self.entry_line = race_line;
+            }
+        }
+    }
+
+    fn check_class(&mut self, class: &dyn TableLike) {
+        self.check_fields(class, "class", &CLASS_FIELDS);
+        let id = self.check_id(class, "class");
+
+        match class.get("hit_die") {
+            None => self.report(None, format!("class '{}' is missing its hit_die", id)),
+            Some(item) => match item.as_integer() {
+                Some(die) => {
+                    if let Err(e) = check_hit_die(&id, die) {
+                        self.report(item.span(), e);
+                    }
+                },
+                None => self.report(item.span(), format!("class '{}' has a hit_die that isn't a number", id)),
+            },
+        }
+
+        if class.get("primary_ability").is_none() {
+            self.report(None, format!("class '{}' is missing its primary_ability", id));
+        }
+        self.check_name::<Ability>(class, "primary_ability", "ability");
+
+        match class.get("saving_throws") {
+            None => self.report(None, format!("class '{}' is missing its saving_throws", id)),
+            Some(item) => {
+                let saves = self.check_names::<Ability>(class, "saving_throws", "ability");
+                let distinct: HashSet<&String> = saves.iter().collect();
+                if saves.len() != 2 || distinct.len() != 2 {
+                    self.report(item.span(), format!("class '{}' needs exactly two different saving throws", id));
+                }
+            },
+        }
+
+        let choices = self.check_names::<Skill>(class, "skill_choices", "skill");
+        let distinct: HashSet<&String> = choices.iter().collect();
+        if distinct.len() < choices.len() {
+            self.report(span_of(class, "skill_choices"), format!("class '{}' lists a skill choice more than once", id));
+        }
+        self.check_count(class, "skill_count", 0, Skill::ALL.len() as i64);
+        let skill_count = class.get("skill_count").and_then(Item::as_integer).filter(|&n| n >= 0).unwrap_or(0) as usize;
+        if let Err(e) = check_skill_count(&id, skill_count, distinct.len()) {
+            self.report(span_of(class, "skill_count"), e);
+        }
+
+        if let Some(item) = class.get("expertise") {
+            for value in values(item) {
+                match value.as_array().map(|pair| pair.iter().map(Value::as_integer).collect::<Vec<_>>()).as_deref() {
+                    Some([Some(level), Some(picks)]) if (1..=20).contains(level) && *picks >= 0 => {
+                        if let Err(e) = check_expertise(&id, *level as u8, *picks as usize, skill_count) {
+                            self.report(value.span(), e);
+                        }
+                    },
+                    _ => self.report(
+                        value.span(),
+                        format!(
+                            "class '{}' has an expertise entry that isn't [level, picks] with a level from 1 to 20",
+                            id
+                        ),
+                    ),
+                }
+            }
+        }
+        if let Some(item) = class.get("asi_levels") {
+            for value in values(item) {
+                if !value.as_integer().is_some_and(|level| (1..=20).contains(&level)) {
+                    self.report(
+                        value.span(),
+                        format!("class '{}' has an ability score improvement level that isn't from 1 to 20", id),
+                    );
+                }
+            }
+        }
+        for feature in ["jack_of_all_trades", "reliable_talent"] {
+            self.check_count(class, feature, 1, 20);
+        }
+
+        self.check_name::<Spellcasting>(class, "spellcasting", "spellcasting progression");
+        self.check_names::<ClassResource>(class, "resources", "class resource");
+        self.check_names::<WeaponCategory>(class, "weapon_categories", "weapon category");
+        if let Some(item) = class.get("weapons") {
+            for value in values(item) {
+                match value.as_str() {
+                    Some(name) if find_item(name, 1).is_some_and(|i| matches!(i.kind, ItemKind::Weapon(_))) => {},
+                    Some(name) => self.report(
+                        value.span(),
+                        format!("class '{}' is proficient with '{}', which isn't a known weapon", id, name),
+                    ),
+                    None => self.report(value.span(), format!("class '{}' has a weapon that isn't a string", id)),
+                }
+            }
+        }
+
+        if let Some(item) = class.get("unarmored_defense") {
+            match item.as_table_like() {
+                Some(defense) => {
+                    self.check_fields(defense, "unarmored_defense", &["ability", "shield"]);
+                    if defense.get("ability").is_none() || defense.get("shield").is_none_or(|s| s.as_bool().is_none()) {
+                        self.report(
+                            item.span(),
+                            format!("class '{}' needs an ability and shield = true or false for unarmored_defense", id),
+                        );
+                    }
+                    self.check_name::<Ability>(defense, "ability", "ability");
+                },
+                None => self.report(item.span(), format!("class '{}' has an unarmored_defense that isn't a table", id)),
+            }
+        }
+
+        // every starting item has to exist in the equipment catalog
+        if let Some(item) = class.get("starting_equipment") {
+            for choice in values(item) {
+                for option in choice.as_array().into_iter().flatten() {
+                    for entry in option.as_array().into_iter().flatten() {
+                        match entry.as_str() {
+                            Some(text) if find_item(&parse_item(text).0, 1).is_some() => {},
+                            Some(text) => self.report(
+                                entry.span(),
+                                format!(
+                                    "class '{}' starts with '{}', which isn't a known item",
+                                    id,
+                                    parse_item(text).0
+                                ),
+                            ),
+                            None => self.report(
+                                entry.span(),
+                                format!("class '{}' has a starting item that isn't a string", id),
+                            ),
+                        }
+                    }
+                    if !option.is_array() {
+                        self.report(
+                            option.span(),
+                            format!("class '{}' has a starting equipment option that isn't a list of items", id),
+                        );
+                    }
+                }
+                if !choice.is_array() {
+                    self.report(
+                        choice.span(),
+                        format!("class '{}' has a starting equipment choice that isn't a list of options", id),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_background(&mut self, background: &dyn TableLike) {
+        self.check_fields(background, "background", &BACKGROUND_FIELDS);
+        let id = self.check_id(background, "background");
+        self.check_names::<Skill>(background, "skills", "skill");
+        self.check_count(background, "extra_languages", 0, Language::ALL.len() as i64);
+
+        match background.get("feature").map(|item| (item, item.as_table_like())) {
+            None => self.report(None, format!("background '{}' is missing its feature", id)),
+            Some((item, None)) => {
+                self.report(item.span(), format!("background '{}' has a feature that isn't a table", id))
+            },
+            Some((item, Some(feature))) => {
+                self.check_fields(feature, "feature", &["name", "description"]);
+                if !["name", "description"].iter().all(|key| feature.get(key).is_some_and(|v| v.is_str())) {
+                    self.report(item.span(), format!("background '{}' needs a feature name and description", id));
+                }
+            },
+        }
+
+        // tables are rolled on with one die side per entry
+        for (table, least) in [("personality_traits", 2), ("ideals", 1), ("bonds", 1), ("flaws", 1)] {
+            let entries = background.get(table).map(values).unwrap_or_default();
+            let span = span_of(background, table);
+            if entries.len() < least || entries.len() > u8::MAX as usize {
+                self.report(
+                    span,
+                    format!(
+                        "background '{}' needs from {} to 255 {}, it has {}",
+                        id,
+                        least,
+                        table.replace('_', " "),
+                        entries.len()
+                    ),
+                );
+            }
+        }
+    }
+
+    // id and name are required, and ids can't repeat across any of the packs
+    fn check_id(&mut self, entry: &dyn TableLike, kind: &'static str) -> String {
+        if !entry.get("name").is_some_and(Item::is_str) {
+            self.report(None, format!("{} is missing its name", kind));
+        }
+
+        let (id, span) = match entry.get("id").map(|item| (item.as_str(), item.span())) {
+            Some((Some(id), span)) if !id.trim().is_empty() => (id.to_string(), span),
+            Some((_, span)) => {
+                self.report(span, format!("{} id has to be a non-empty string", kind));
+                return String::new();
+            },
+            None => {
+                self.report(None, format!("{} is missing its id", kind));
+                return String::new();
+            },
+        };
+
+        let line = self.line(span.clone());
+        match self.ids.get(&(kind, id.clone())) {
+            Some((file, first)) => {
+                let message = format!("duplicate {} id '{}', already defined at {}:{}", kind, id, file, first);
+                self.report(span, message);
+            },
+            None => {
+                self.ids.insert((kind, id.clone()), (self.file.clone(), line));
+            },
+        }
+        id
+    }
+
+    fn check_fields(&mut self, entry: &dyn TableLike, kind: &str, fields: &[&str]) {
+        for (key, _) in entry.iter() {
+            if !fields.contains(&key) {
+                let span = entry.key(key).and_then(|k| k.span());
+                self.report(span, format!("unknown {} field '{}'", kind, key));
+            }
+        }
+    }
+
+    // a table of ability names to bonuses, returning the abilities it names
+    fn check_ability_bonuses(&mut self, entry: &dyn TableLike) -> Vec<String> {
+        let Some(item) = entry.get("ability_bonuses") else { return Vec::new() };
+        let Some(bonuses) = item.as_table_like() else {
+            self.report(item.span(), "ability_bonuses has to be a table like { Strength = 2 }".to_string());
+            return Vec::new();
+        };
+
+        let mut names = Vec::new();
+        for (name, bonus) in bonuses.iter() {
+            let span = bonuses.key(name).and_then(|k| k.span());
+            if let Err(e) = parse_name::<Ability>(name) {
+                self.report(span.clone(), unknown("ability", e));
+            }
+            if !bonus.as_integer().is_some_and(|b| (i8::MIN as i64..=i8::MAX as i64).contains(&b)) {
+                self.report(bonus.span().or(span), format!("the bonus to {} has to be a whole number", name));
+            }
+            names.push(name.to_string());
+        }
+        names
+    }
+
+    // a single enum value given by name
+    fn check_name<T: DeserializeOwned>(&mut self, entry: &dyn TableLike, key: &str, kind: &str) {
+        if let Some(item) = entry.get(key) {
+            match item.as_str() {
+                Some(name) => {
+                    if let Err(e) = parse_name::<T>(name) {
+                        self.report(item.span(), unknown(kind, e));
+                    }
+                },
+                None => self.report(item.span(), format!("{} has to be a string", key)),
+            }
+        }
+    }
+
+    // a list of enum values given by name, returning the names
+    fn check_names<T: DeserializeOwned>(&mut self, entry: &dyn TableLike, key: &str, kind: &str) -> Vec<String> {
+        let Some(item) = entry.get(key) else { return Vec::new() };
+        if item.as_array().is_none() {
+            self.report(item.span(), format!("{} has to be a list", key));
+            return Vec::new();
+        }
+
+        let mut names = Vec::new();
+        for value in values(item) {
+            match value.as_str() {
+                Some(name) => {
+                    if let Err(e) = parse_name::<T>(name) {
+                        self.report(value.span(), unknown(kind, e));
+                    }
+                    names.push(name.to_string());
+                },
+                None => self.report(value.span(), format!("{} has to hold strings", key)),
+            }
+        }
+        names
+    }
+
+    fn check_count(&mut self, entry: &dyn TableLike, key: &str, min: i64, max: i64) {
+        if let Some(item) = entry.get(key) {
+            if !item.as_integer().is_some_and(|n| (min..=max).contains(&n)) {
+                self.report(item.span(), format!("{} has to be a whole number from {} to {}", key, min, max));
+            }
+        }
+    }
+}
+
+// the tables of a [[section]], or of an inline array of tables
+fn entries(item: &Item) -> Vec<(&dyn TableLike, Option<Range<usize>>)> {
+    match item {
+        Item::ArrayOfTables(tables) => tables.iter().map(|t| (t as &dyn TableLike, t.span())).collect(),
+        Item::Value(Value::Array(array)) => {
+            array.iter().filter_map(|v| v.as_inline_table().map(|t| (t as &dyn TableLike, v.span()))).collect()
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn values(item: &Item) -> Vec<&Value> {
+    item.as_array().map(|array| array.iter().collect()).unwrap_or_default()
+}
+
+fn span_of(entry: &dyn TableLike, key: &str) -> Option<Range<usize>> {
+    entry.get(key).and_then(Item::span)
+}
+
+fn parse_name<T: DeserializeOwned>(name: &str) -> Result<T, serde::de::value::Error> {
+    T::deserialize(name.into_deserializer())
+}
+
+// serde says "unknown variant `X`, expected one of ..."; name the kind instead
+fn unknown(kind: &str, error: serde::de::value::Error) -> String {
+    error.to_string().replacen("variant", kind, 1)
+}
+
+// check the built-in pack and every .toml file in the directories, in load order;
+// ids are checked against everything loaded before them
+pub fn check_packs(dirs: &[PathBuf]) -> io::Result<Vec<ContentProblem>> {
+    let mut checker =
+        Checker { problems: Vec::new(), ids: HashMap::new(), file: String::new(), text: String::new(), entry_line: 1 };
+
+    for (name, text) in SRD {
+        checker.check_file(name.to_string(), text.to_string());
+    }
+    for dir in dirs {
+        let files = pack_files(dir).map_err(|e| io::Error::new(e.kind(), format!("{}: {}", dir.display(), e)))?;
+        for path in files {
+            let text = fs::read_to_string(&path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            checker.check_file(path.display().to_string(), text);
+        }
+    }
+
+    Ok(checker.problems)
+}
@@ -0,0 +1,702 @@
+use std::collections::HashSet;
+
+use rand::RngCore;
+
+use crate::ability::{Ability, AbilityScores};
+use crate::advancement::AdvancementMode;
+use crate::background::{Background, Personality};
+use crate::character::Character;
+use crate::class::{class_skill_choices, get_expertise_count, granted_skills, Class};
+use crate::dice::{generate_ability_scores_with, roll_die_with};
+use crate::equipment::describe_option;
+use crate::input::Input;
+use crate::language::Language;
+use crate::names::random_name;
+use crate::race::{Race, Subrace};
+use crate::skill::Skill;
+
+// the character creator's steps, in order, shared by the prompts and the full-screen builder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Race,
+    Subrace,
+    Name,
+    Class,
+    Background,
+    AbilityScores,
+    AbilityChoices,
+    Skills,
+    Expertise,
+    Languages,
+    Personality,
+    Equipment,
+    Advancement,
+    Review,
+}
+
+impl Step {
+    pub const ALL: [Step; 14] = [
+        Step::Race,
+        Step::Subrace,
+        Step::Name,
+        Step::Class,
+        Step::Background,
+        Step::AbilityScores,
+        Step::AbilityChoices,
+        Step::Skills,
+        Step::Expertise,
+        Step::Languages,
+        Step::Personality,
+        Step::Equipment,
+        Step::Advancement,
+        Step::Review,
+    ];
+}
+
+// commands that leave the current step, accepted at every prompt
+enum Nav {
+    Back,
+    Restart,
+    // the input ran out before the character was finished
+    Quit,
+}
+
+// the choices made so far; nothing is applied to a character until it's built
+#[derive(Debug, Clone, Default)]
+pub struct Draft {
+    pub name: Option<String>,
+    pub race: Option<Race>,
+    pub subrace: Option<Subrace>,
+    pub class: Option<Class>,
+    pub background: Option<Background>,
+    // rolled once per character so stepping back can't be used to re-roll
+    pub rolls: Option<AbilityScores>,
+    pub ability_choices: Vec<Ability>,
+    // class skills chosen on top of the background and racial ones
+    pub skills: HashSet<Skill>,
+    pub expertise: HashSet<Skill>,
+    pub languages: Vec<Language>,
+    pub personality: Option<Personality>,
+    // index of the option picked for each of the class's starting equipment choices
+    pub equipment: Vec<usize>,
+    pub advancement: AdvancementMode,
+}
+
+impl Draft {
+    // whether the step has anything to ask given the earlier choices
+    pub fn applies(&self, step: Step) -> bool {
+        match step {
+            Step::Subrace => self.race.is_some_and(|r| !r.subraces().is_empty()),
+            Step::AbilityChoices => self.race.is_some_and(|r| r.ability_choices() > 0),
+            Step::Skills => self.skill_choices().1 > 0,
+            Step::Expertise => self.class.is_some_and(|c| get_expertise_count(&c) > 0),
+            Step::Languages => self.extra_languages() > 0,
+            _ => true,
+        }
+    }
+
+    // whether the step's choices are all made
+    pub fn is_complete(&self, step: Step) -> bool {
+        match step {
+            Step::Race => self.race.is_some(),
+            Step::Subrace => match (self.race, self.subrace) {
+                (Some(race), Some(subrace)) => race.subraces().contains(&subrace),
+                _ => false,
+            },
+            Step::Name => self.name.as_deref().is_some_and(|name| !name.trim().is_empty()),
+            Step::Class => self.class.is_some(),
+            Step::Background => self.background.is_some(),
+            Step::AbilityScores => self.rolls.is_some(),
+            Step::AbilityChoices => self.ability_choices.len() == self.ability_choice_count(),
+            Step::Skills => {
+                let (available, remaining) = self.skill_choices();
+                self.skills.len() == remaining.min(available.len())
+            },
+            Step::Expertise => self.class.is_some_and(|c| self.expertise.len() == get_expertise_count(&c)),
+            Step::Languages => self.languages.len() == self.extra_languages().min(self.language_choices().len()),
+            Step::Personality => self.personality.as_ref().is_some_and(|p| {
+                p.traits.len() == 2 && !p.ideal.is_empty() && !p.bond.is_empty() && !p.flaw.is_empty()
+            }),
+            Step::Equipment => self.class.is_some_and(|c| self.equipment.len() == c.starting_equipment().len()),
+            Step::Advancement | Step::Review => true,
+        }
+    }
+
+    // a new race takes the subrace, racial bonuses and everything picked around its skills and languages with it
+    pub fn set_race(&mut self, race: Race) {
+        if self.race != Some(race) {
+            self.race = Some(race);
+            self.subrace = None;
+            self.ability_choices.clear();
+            self.clear_skills();
+            self.languages.clear();
+        }
+    }
+
+    // a new class has its own skill list and starting equipment, the first option of each is picked
+    pub fn set_class(&mut self, class: Class) {
+        if self.class != Some(class) {
+            self.class = Some(class);
+            self.clear_skills();
+            self.equipment = vec![0; class.starting_equipment().len()];
+        }
+    }
+
+    // a new background grants other skills and languages and has its own personality tables
+    pub fn set_background(&mut self, background: Background) {
+        if self.background != Some(background) {
+            self.background = Some(background);
+            self.clear_skills();
+            self.languages.clear();
+            self.personality = None;
+        }
+    }
+
+    fn clear_skills(&mut self) {
+        self.skills.clear();
+        self.expertise.clear();
+    }
+
+    // skills that come with the race and background
+    pub fn granted_skills(&self) -> HashSet<Skill> {
+        match (self.race, self.background) {
+            (Some(race), Some(background)) => granted_skills(background, race),
+            _ => HashSet::new(),
+        }
+    }
+
+    // class skills that can still be chosen and how many to choose
+    pub fn skill_choices(&self) -> (Vec<Skill>, usize) {
+        match self.class {
+            Some(class) => class_skill_choices(&class, &self.granted_skills()),
+            None => (Vec::new(), 0),
+        }
+    }
+
+    // every proficient skill, the ones expertise can be picked from
+    pub fn expertise_choices(&self) -> Vec<Skill> {
+        let granted = self.granted_skills();
+        Skill::ALL.into_iter().filter(|s| granted.contains(s) || self.skills.contains(s)).collect()
+    }
+
+    // abilities without a racial bonus, the ones the race's floating +1s can go into
+    pub fn ability_choice_options(&self) -> Vec<Ability> {
+        self.race.map(|r| r.ability_choice_options()).unwrap_or_default()
+    }
+
+    // how many floating +1s to place, never more than there are abilities to take them
+    pub fn ability_choice_count(&self) -> usize {
+        self.race.map_or(0, |r| r.ability_choices()).min(self.ability_choice_options().len())
+    }
+
+    pub fn extra_languages(&self) -> usize {
+        match (self.race, self.background) {
+            (Some(race), Some(background)) => race.extra_languages() + background.extra_languages(),
+            _ => 0,
+        }
+    }
+
+    // languages the race doesn't already speak
+    pub fn language_choices(&self) -> Vec<Language> {
+        let known = self.race.map(|r| r.languages()).unwrap_or_default();
+        Language::ALL.into_iter().filter(|l| !known.contains(l)).collect()
+    }
+
+    // rolled scores with every racial bonus chosen so far
+    pub fn abilities(&self) -> Option<AbilityScores> {
+        let mut abilities = self.rolls.clone()?;
+        if let Some(race) = self.race {
+            abilities.apply_racial_bonuses(race);
+        }
+        if let Some(subrace) = self.subrace {
+            abilities.apply_subrace_bonuses(subrace);
+        }
+        for &ability in &self.ability_choices {
+            abilities.apply_chosen_bonus(ability);
+        }
+        Some(abilities)
+    }
+
+    // the finished character, None until every step that applies is complete
+    pub fn build(&self) -> Option<Character> {
+        if Step::ALL.iter().any(|&step| self.applies(step) && !self.is_complete(step)) {
+            return None;
+        }
+        self.preview()
+    }
+
+    // the character as it stands, once race, class, background and scores are known
+    pub fn preview(&self) -> Option<Character> {
+        let (race, class, background) = (self.race?, self.class?, self.background?);
+
+        let mut skills = self.granted_skills();
+        skills.extend(self.skills.iter().copied());
+
+        let name = self.name.as_deref().map(str::trim).filter(|name| !name.is_empty()).unwrap_or("(unnamed)");
+        let mut character = Character::new(
+            name.to_string(),
+            race,
+            self.subrace,
+            class,
+            background,
+            self.abilities()?,
+            skills,
+            self.expertise.clone(),
+        );
+        character.languages.extend(self.languages.iter().copied());
+        character.personality = self.personality.clone().unwrap_or_default();
+        character.advancement = self.advancement;
+
+        for (choice, &index) in class.starting_equipment().iter().zip(&self.equipment) {
+            if let Some(option) = choice.get(index) {
+                character.add_equipment_option(option);
+            }
+        }
+
+        Some(character)
+    }
+
+    // print the choices made so far
+    pub fn print_summary(&self) {
+        fn or_dash<T: std::fmt::Display>(value: Option<T>) -> String {
+            value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+        }
+
+        println!("\n--- Choices so far ---");
+        println!("Name: {}", self.name.as_deref().unwrap_or("-"));
+        match self.subrace {
+            Some(subrace) => println!("Race: {} ({})", or_dash(self.race), subrace),
+            None => println!("Race: {}", or_dash(self.race)),
+        }
+        println!("Class: {}", or_dash(self.class));
+        println!("Background: {}", or_dash(self.background));
+
+        if let Some(abilities) = self.abilities() {
+            let scores: Vec<String> =
+                Ability::ALL.iter().map(|&a| format!("{} {}", &format!("{:?}", a)[..3], abilities.score(a))).collect();
+            println!("Ability Scores: {}", scores.join(", "));
+        }
+
+        let mut skills: Vec<Skill> = self.granted_skills().into_iter().chain(self.skills.iter().copied()).collect();
+        skills.sort_by_key(|s| format!("{:?}", s));
+        if !skills.is_empty() {
+            println!("Skills: {:?}", skills);
+        }
+        if !self.expertise.is_empty() {
+            println!("Expertise: {:?}", self.expertise);
+        }
+        if !self.languages.is_empty() {
+            println!("Extra Languages: {:?}", self.languages);
+        }
+        if let Some(personality) = &self.personality {
+            println!("Personality: {}", personality.traits.join(" "));
+        }
+        println!("------------------------");
+    }
+}
+
+// asks the creator's questions, reading answers from any input source
+struct Creator<'a> {
+    input: &'a mut dyn Input,
+    rng: &'a mut dyn RngCore,
+    draft: Draft,
+}
+
+impl Creator<'_> {
+    // read a line, handling summary here and turning back/restart into Nav
+    fn read_input(&mut self) -> Result<String, Nav> {
+        loop {
+            let input = self.input.read_line().ok_or(Nav::Quit)?;
+            let input = input.trim();
+
+            match input.to_lowercase().as_str() {
+                "back" => return Err(Nav::Back),
+                "restart" => return Err(Nav::Restart),
+                "summary" => {
+                    self.draft.print_summary();
+                    println!("\nContinue with your choice:");
+                },
+                _ => return Ok(input.to_string()),
+            }
+        }
+    }
+
+    // read a menu number from 1 to count, returned as an index
+    fn read_choice(&mut self, count: usize) -> Result<usize, Nav> {
+        loop {
+            match self.read_input()?.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= count => return Ok(n - 1),
+                _ => println!("Invalid selection. Please choose 1-{}.", count),
+            }
+        }
+    }
+
+    // select a race
+    fn select_race(&mut self) -> Result<Race, Nav> {
+        let races = Race::all();
+
+        println!("\nSelect a race:");
+        for (i, race) in races.iter().enumerate() {
+            println!("{}. {}", i + 1, race);
+        }
+
+        Ok(races[self.read_choice(races.len())?])
+    }
+
+    // select a subrace, only asked for races that have them
+    fn select_subrace(&mut self, race: Race) -> Result<Subrace, Nav> {
+        let subraces = race.subraces();
+
+        println!("\nSelect a subrace:");
+        for (i, subrace) in subraces.iter().enumerate() {
+            println!("{}. {}", i + 1, subrace);
+        }
+
+        Ok(subraces[self.read_choice(subraces.len())?])
+    }
+
+    // read a name, suggesting generated ones in the race's style when left blank
+    fn select_name(&mut self, race: Race) -> Result<String, Nav> {
+        println!("\nEnter your character's name (leave blank for a suggestion):");
+        let input = self.read_input()?;
+        if !input.is_empty() {
+            return Ok(input);
+        }
+
+        loop {
+            let suggestion = random_name(race, self.rng);
+            println!("\nHow about \"{}\"? Press Enter to accept, type 'n' for another, or enter a name:", suggestion);
+
+            match self.read_input()?.as_str() {
+                "" => return Ok(suggestion),
+                "n" | "N" => continue,
+                name => return Ok(name.to_string()),
+            }
+        }
+    }
+
+    // select a class
+    fn select_class(&mut self) -> Result<Class, Nav> {
+        let classes = Class::all();
+
+        println!("\nSelect a class:");
+        for (i, class) in classes.iter().enumerate() {
+            println!("{}. {}", i + 1, class);
+        }
+
+        Ok(classes[self.read_choice(classes.len())?])
+    }
+
+    // select a background
+    fn select_background(&mut self) -> Result<Background, Nav> {
+        let backgrounds = Background::all();
+
+        println!("\nSelect a background:");
+        for (i, background) in backgrounds.iter().enumerate() {
+            println!("{}. {}", i + 1, background);
+        }
+
+        Ok(backgrounds[self.read_choice(backgrounds.len())?])
+    }
+
+    // show the rolled scores with racial bonuses applied
+    fn show_ability_scores(&mut self) -> Result<(), Nav> {
+        let (rolls, abilities) = match (&self.draft.rolls, self.draft.abilities()) {
+            (Some(rolls), Some(abilities)) => (rolls, abilities),
+            _ => return Ok(()),
+        };
+
+        println!("\nAbility scores (4d6 drop lowest, with racial bonuses):");
+        for ability in Ability::ALL {
+            println!(
+                "{:?}: {} -> {} ({:+})",
+                ability,
+                rolls.score(ability),
+                abilities.score(ability),
+                abilities.modifier(ability)
+            );
+        }
+
+        println!("\nPress Enter to continue.");
+        self.read_input()?;
+        Ok(())
+    }
+
+    // handle floating +1 bonuses like the Half-Elf's, each to a different ability
+    fn select_ability_choices(&mut self, race: Race) -> Result<Vec<Ability>, Nav> {
+        let choices = self.draft.ability_choice_options();
+        let count = self.draft.ability_choice_count();
+        let mut bonuses = Vec::new();
+
+        println!(
+            "\n{} characters get +1 to {} abilities of your choice (excluding ones with a racial bonus)",
+            race, count
+        );
+        while bonuses.len() < count {
+            println!("\nChoose ability {} to receive +1:", bonuses.len() + 1);
+            for (i, ability) in choices.iter().enumerate() {
+                println!("{}. {:?}", i + 1, ability);
+            }
+
+            let ability = choices[self.read_choice(choices.len())?];
+            if bonuses.contains(&ability) {
+                println!("That ability already has the bonus, choose another.");
+                continue;
+            }
+            bonuses.push(ability);
+        }
+
+        Ok(bonuses)
+    }
+
+    // select skills from the available class skills
+    fn select_skills(&mut self) -> Result<HashSet<Skill>, Nav> {
+        // background and racial skills come for free
+        let (available_skills, remaining) = self.draft.skill_choices();
+        let mut selected_skills = HashSet::new();
+
+        println!("\nSelect {} additional skill proficiencies from:", remaining);
+
+        // display available skills with numbers
+        for (i, skill) in available_skills.iter().enumerate() {
+            println!("{}. {:?}", i + 1, skill);
+        }
+
+        while selected_skills.len() < remaining {
+            println!("\nChoose skill {} (1-{}):", selected_skills.len() + 1, available_skills.len());
+
+            let skill = available_skills[self.read_choice(available_skills.len())?];
+            if selected_skills.insert(skill) {
+                println!("Skill added.");
+            } else {
+                println!("You already chose that skill.");
+            }
+        }
+
+        Ok(selected_skills)
+    }
+
+    // select expertise skills (for Rogues and Bards)
+    fn select_expertise(&mut self, class: Class) -> Result<HashSet<Skill>, Nav> {
+        let expertise_count = get_expertise_count(&class);
+        let mut expertise = HashSet::new();
+
+        println!("\nSelect {} skill(s) for Expertise:", expertise_count);
+
+        // display available skills with numbers
+        let available_skills = self.draft.expertise_choices();
+        for (i, skill) in available_skills.iter().enumerate() {
+            println!("{}. {:?}", i + 1, skill);
+        }
+
+        while expertise.len() < expertise_count {
+            println!("\nChoose expertise skill {} (1-{}):", expertise.len() + 1, available_skills.len());
+
+            let skill = available_skills[self.read_choice(available_skills.len())?];
+            if expertise.insert(skill) {
+                println!("Expertise added.");
+            } else {
+                println!("You already chose that skill.");
+            }
+        }
+
+        Ok(expertise)
+    }
+
+    // choose extra languages the character doesn't already know
+    fn select_languages(&mut self) -> Result<Vec<Language>, Nav> {
+        let count = self.draft.extra_languages();
+        let mut chosen = Vec::new();
+
+        println!("\nSelect {} additional language(s):", count);
+        while chosen.len() < count {
+            let available: Vec<Language> =
+                self.draft.language_choices().into_iter().filter(|l| !chosen.contains(l)).collect();
+            if available.is_empty() {
+                break;
+            }
+
+            for (i, language) in available.iter().enumerate() {
+                println!("{}. {:?}", i + 1, language);
+            }
+            println!("\nChoose language {} (1-{}):", chosen.len() + 1, available.len());
+
+            chosen.push(available[self.read_choice(available.len())?]);
+            println!("Language added.");
+        }
+
+        Ok(chosen)
+    }
+
+    // pick an entry from a background table by number, or roll for it
+    fn select_from_table(&mut self, title: &str, entries: &[&'static str], exclude: &[String]) -> Result<String, Nav> {
+        println!("\n{} (enter a number, or r to roll d{}):", title, entries.len());
+        for (i, entry) in entries.iter().enumerate() {
+            println!("{}. {}", i + 1, entry);
+        }
+
+        loop {
+            let input = self.read_input()?;
+
+            let index = if input.eq_ignore_ascii_case("r") {
+                let roll = roll_die_with(self.rng, entries.len() as u8) as usize;
+                println!("Rolled {}.", roll);
+                roll - 1
+            } else {
+                match input.parse::<usize>() {
+                    Ok(n) if n >= 1 && n <= entries.len() => n - 1,
+                    _ => {
+                        println!("Invalid selection. Please choose 1-{} or r.", entries.len());
+                        continue;
+                    },
+                }
+            };
+
+            if exclude.iter().any(|e| e == entries[index]) {
+                println!("You already have that one, choose again.");
+                continue;
+            }
+
+            println!("{}", entries[index]);
+            return Ok(entries[index].to_string());
+        }
+    }
+
+    fn select_personality(&mut self, background: Background) -> Result<Personality, Nav> {
+        let traits = background.personality_traits();
+        let first = self.select_from_table("Personality trait 1", &traits, &[])?;
+        let second = self.select_from_table("Personality trait 2", &traits, std::slice::from_ref(&first))?;
+
+        Ok(Personality {
+            traits: vec![first, second],
+            ideal: self.select_from_table("Ideal", &background.ideals(), &[])?,
+            bond: self.select_from_table("Bond", &background.bonds(), &[])?,
+            flaw: self.select_from_table("Flaw", &background.flaws(), &[])?,
+        })
+    }
+
+    // choose between the class's starting equipment options
+    fn select_starting_equipment(&mut self, class: Class) -> Result<Vec<usize>, Nav> {
+        let mut picks = Vec::new();
+
+        for choice in class.starting_equipment() {
+            if choice.len() == 1 {
+                println!("\nYou receive: {}", describe_option(&choice[0]));
+                picks.push(0);
+                continue;
+            }
+
+            println!("\nChoose your starting equipment:");
+            for (i, option) in choice.iter().enumerate() {
+                println!("{}. {}", i + 1, describe_option(option));
+            }
+            picks.push(self.read_choice(choice.len())?);
+        }
+
+        Ok(picks)
+    }
+
+    fn select_advancement_mode(&mut self) -> Result<AdvancementMode, Nav> {
+        println!("\nHow does your campaign level up?");
+        println!("1. Experience points");
+        println!("2. Milestones");
+
+        Ok([AdvancementMode::Experience, AdvancementMode::Milestone][self.read_choice(2)?])
+    }
+
+    // show the finished sheet, Ok once the player accepts it
+    fn review(&mut self) -> Result<(), Nav> {
+        if let Some(character) = self.draft.build() {
+            character.display();
+        }
+
+        println!("\nPress Enter to create this character, or type back, restart or summary.");
+        self.read_input()?;
+        Ok(())
+    }
+
+    // ask the step's question and record the answer in the draft
+    fn run_step(&mut self, step: Step) -> Result<(), Nav> {
+        // earlier steps always fill these in before the steps that use them
+        let race = self.draft.race.unwrap_or_else(|| Race::all()[0]);
+        let class = self.draft.class.unwrap_or_else(|| Class::all()[0]);
+        let background = self.draft.background.unwrap_or_else(|| Background::all()[0]);
+
+        match step {
+            Step::Race => {
+                let race = self.select_race()?;
+                self.draft.set_race(race);
+            },
+            Step::Subrace => self.draft.subrace = Some(self.select_subrace(race)?),
+            Step::Name => self.draft.name = Some(self.select_name(race)?),
+            Step::Class => {
+                let class = self.select_class()?;
+                self.draft.set_class(class);
+            },
+            Step::Background => {
+                let background = self.select_background()?;
+                self.draft.set_background(background);
+            },
+            Step::AbilityScores => {
+                if self.draft.rolls.is_none() {
+                    self.draft.rolls = Some(generate_ability_scores_with(self.rng));
+                }
+                self.show_ability_scores()?;
+            },
+            Step::AbilityChoices => self.draft.ability_choices = self.select_ability_choices(race)?,
+            Step::Skills => self.draft.skills = self.select_skills()?,
+            Step::Expertise => self.draft.expertise = self.select_expertise(class)?,
+            Step::Languages => self.draft.languages = self.select_languages()?,
+            Step::Personality => self.draft.personality = Some(self.select_personality(background)?),
+            Step::Equipment => self.draft.equipment = self.select_starting_equipment(class)?,
+            Step::Advancement => self.draft.advancement = self.select_advancement_mode()?,
+            Step::Review => self.review()?,
+        }
+
+        Ok(())
+    }
+
+    fn run(&mut self) -> Option<Character> {
+        println!("\nAt any prompt, type 'back' to return to the previous step, 'restart' to start over,");
+        println!("or 'summary' to see your choices so far.");
+
+        let mut index = 0;
+        loop {
+            let step = Step::ALL[index];
+            if !self.draft.applies(step) {
+                index += 1;
+                continue;
+            }
+
+            match self.run_step(step) {
+                Ok(()) if step == Step::Review => match self.draft.build() {
+                    Some(character) => return Some(character),
+                    // every step is asked before the review, but go back to anything left unfinished
+                    None => {
+                        index = Step::ALL
+                            .iter()
+                            .position(|&s| self.draft.applies(s) && !self.draft.is_complete(s))
+                            .unwrap_or(0)
+                    },
+                },
+                Ok(()) => index += 1,
+                Err(Nav::Back) => match (0..index).rev().find(|&i| self.draft.applies(Step::ALL[i])) {
+                    Some(previous) => index = previous,
+                    None => println!("\nThis is the first step."),
+                },
+                Err(Nav::Restart) => {
+                    println!("\nStarting over.");
+                    self.draft = Draft::default();
+                    index = 0;
+                },
+                Err(Nav::Quit) => return None,
+            }
+        }
+    }
+}
+
+// walk through character creation with answers from the input, None if it runs out first;
+// back, restart and summary work at every prompt
+pub fn create_character(input: &mut dyn Input, rng: &mut dyn RngCore) -> Option<Character> {
+    Creator { input, rng, draft: Draft::default() }.run()
+}
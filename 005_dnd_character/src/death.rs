@@ -0,0 +1,116 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::character::Character;
+use crate::dice::roll_die_with;
+
+// whether the character is up, dying at 0 HP, stable at 0 HP or dead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LifeState {
+    #[default]
+    Conscious,
+    Dying,
+    Stable,
+    Dead,
+}
+
+// death saving throw tally, reset whenever the character stops dying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DeathSaves {
+    pub successes: u8,
+    pub failures: u8,
+}
+
+// outcome of a single death saving throw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathSaveResult {
+    Success,
+    Failure,
+    // natural 1, counts as two failures
+    CriticalFailure,
+    // natural 20, back on your feet with 1 HP
+    Revived,
+    Stabilized,
+    Died,
+    // not dying, nothing to roll
+    NotDying,
+}
+
+impl Character {
+    pub fn is_conscious(&self) -> bool {
+        self.life_state == LifeState::Conscious
+    }
+
+    // roll a death saving throw, only does anything while dying
+    pub fn roll_death_save<R: Rng + ?Sized>(&mut self, rng: &mut R) -> (u8, DeathSaveResult) {
+        if self.life_state != LifeState::Dying {
+            return (0, DeathSaveResult::NotDying);
+        }
+
+        let roll = roll_die_with(rng, 20);
+        let result = match roll {
+            20 => {
+                self.hit_points.current = 1;
+                self.life_state = LifeState::Conscious;
+                self.death_saves = DeathSaves::default();
+                DeathSaveResult::Revived
+            },
+            1 => self.add_death_save_failures(2),
+            10..=19 => {
+                self.death_saves.successes += 1;
+                if self.death_saves.successes >= 3 {
+                    self.stabilize();
+                    DeathSaveResult::Stabilized
+                } else {
+                    DeathSaveResult::Success
+                }
+            },
+            _ => self.add_death_save_failures(1),
+        };
+
+        (roll, result)
+    }
+
+    // stop dying without regaining hit points (three successes, Medicine check, spare the dying)
+    pub fn stabilize(&mut self) {
+        if self.life_state == LifeState::Dying {
+            self.life_state = LifeState::Stable;
+            self.death_saves = DeathSaves::default();
+        }
+    }
+
+    // three failures and the character is dead
+    fn add_death_save_failures(&mut self, count: u8) -> DeathSaveResult {
+        self.death_saves.failures = (self.death_saves.failures + count).min(3);
+
+        if self.death_saves.failures >= 3 {
+            self.life_state = LifeState::Dead;
+            DeathSaveResult::Died
+        } else if count > 1 {
+            DeathSaveResult::CriticalFailure
+        } else {
+            DeathSaveResult::Failure
+        }
+    }
+
+    // update the dying state after losing hit points, `excess` is damage beyond the hit points left
+    pub(crate) fn check_dying(&mut self, was_at_zero: bool, excess: u16) {
+        if self.life_state == LifeState::Dead {
+            return;
+        }
+
+        // damage that leaves the character at 0 with at least max HP left over kills outright
+        if self.hit_points.current == 0 && excess >= self.hit_points.max {
+            self.life_state = LifeState::Dead;
+        } else if was_at_zero {
+            // any damage while at 0 HP is a failed death save
+            if excess > 0 {
+                self.life_state = LifeState::Dying;
+                self.add_death_save_failures(1);
+            }
+        } else if self.hit_points.current == 0 {
+            self.life_state = LifeState::Dying;
+            self.death_saves = DeathSaves::default();
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use std::fmt;
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::conditions::RollKind;
+use crate::feat::Feat;
+use crate::roll::RollMode;
+use crate::skill::Skill;
+
+// movement speeds in feet; swim and climb are 0 without a racial speed,
+// the character can still swim and climb but each foot costs an extra foot of walking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Speeds {
+    pub walk: u8,
+    pub swim: u8,
+    pub climb: u8,
+}
+
+// "30 ft, swim 30 ft", leaving out speeds the character doesn't have
+impl fmt::Display for Speeds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ft", self.walk)?;
+        for (name, speed) in [("swim", self.swim), ("climb", self.climb)] {
+            if speed > 0 {
+                write!(f, ", {} {} ft", name, speed)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// carrying limits in pounds, including the variant encumbrance thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CarryingCapacity {
+    pub capacity: u16,
+    pub push_drag_lift: u16,
+    // over this the character is encumbered (-10 ft speed)
+    pub encumbered: u16,
+    // over this the character is heavily encumbered (-20 ft speed, disadvantage on Str/Dex/Con rolls)
+    pub heavily_encumbered: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HitDiceTotals {
+    pub die: u8,
+    pub total: u8,
+    pub remaining: u8,
+}
+
+// everything on the sheet that is computed rather than chosen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DerivedStats {
+    pub proficiency_bonus: i8,
+    pub initiative: i8,
+    pub passive_perception: i8,
+    pub passive_investigation: i8,
+    pub passive_insight: i8,
+    pub speeds: Speeds,
+    pub carrying: CarryingCapacity,
+    pub hit_dice: HitDiceTotals,
+}
+
+impl Character {
+    // Dex modifier plus anything that adds to initiative
+    pub fn initiative(&self) -> i8 {
+        let mut initiative = self.abilities.modifier(Ability::Dexterity) + self.jack_of_all_trades();
+
+        if self.feats.contains(&Feat::Alert) {
+            initiative += 5;
+        }
+
+        initiative
+    }
+
+    // 10 + skill modifier, +5 with advantage and -5 with disadvantage
+    pub fn passive_score(&self, skill: Skill, mode: RollMode) -> i8 {
+        let (advantage, disadvantage) = self.condition_roll_sources(RollKind::AbilityCheck);
+        let mode =
+            RollMode::resolve(advantage || mode == RollMode::Advantage, disadvantage || mode == RollMode::Disadvantage);
+
+        let mut score = 10 + self.skill_modifier(skill);
+        match mode {
+            RollMode::Normal => {},
+            RollMode::Advantage => score += 5,
+            RollMode::Disadvantage => score -= 5,
+        }
+
+        if self.feats.contains(&Feat::Observant) && matches!(skill, Skill::Perception | Skill::Investigation) {
+            score += 5;
+        }
+
+        score
+    }
+
+    // walking speed with feats and racial swimming and climbing speeds, all after
+    // conditions and slowed by variant encumbrance
+    pub fn speeds(&self) -> Speeds {
+        let mut walk = self.speed();
+        if walk > 0 && self.feats.contains(&Feat::Mobile) {
+            walk += 10;
+        }
+
+        let penalty = self.encumbrance_penalty();
+        Speeds {
+            walk: walk.saturating_sub(penalty),
+            swim: self.movement(self.base_swim_speed()).saturating_sub(penalty),
+            climb: self.movement(self.base_climb_speed()).saturating_sub(penalty),
+        }
+    }
+
+    // feet of speed lost to variant encumbrance for the equipment carried
+    pub fn encumbrance_penalty(&self) -> u8 {
+        let carrying = self.carrying_capacity();
+        let weight = self.carried_weight();
+        if weight > carrying.heavily_encumbered as f32 {
+            20
+        } else if weight > carrying.encumbered as f32 {
+            10
+        } else {
+            0
+        }
+    }
+
+    pub fn carrying_capacity(&self) -> CarryingCapacity {
+        let strength = self.abilities.strength.value() as u16;
+
+        CarryingCapacity {
+            capacity: strength * 15,
+            push_drag_lift: strength * 30,
+            encumbered: strength * 5,
+            heavily_encumbered: strength * 10,
+        }
+    }
+
+    pub fn derived_stats(&self) -> DerivedStats {
+        DerivedStats {
+            proficiency_bonus: self.proficiency_bonus(),
+            initiative: self.initiative(),
+            passive_perception: self.passive_score(Skill::Perception, RollMode::Normal),
+            passive_investigation: self.passive_score(Skill::Investigation, RollMode::Normal),
+            passive_insight: self.passive_score(Skill::Insight, RollMode::Normal),
+            speeds: self.speeds(),
+            carrying: self.carrying_capacity(),
+            hit_dice: HitDiceTotals {
+                die: self.class.hit_die(),
+                total: self.level,
+                remaining: self.hit_dice_remaining(),
+            },
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::ability::{AbilityScore, AbilityScores};
+
+// roll a die
+pub fn roll_die(sides: u8) -> u8 {
+    roll_die_with(&mut rand::thread_rng(), sides)
+}
+
+// roll a die with a given random number generator
+pub fn roll_die_with<R: Rng + ?Sized>(rng: &mut R, sides: u8) -> u8 {
+    rng.gen_range(1..=sides)
+}
+
+// roll 4d6 and drop the lowest
+pub fn roll_ability_score() -> u8 {
+    roll_ability_score_with(&mut rand::thread_rng())
+}
+
+pub fn roll_ability_score_with<R: Rng + ?Sized>(rng: &mut R) -> u8 {
+    let mut rolls = [roll_die_with(rng, 6), roll_die_with(rng, 6), roll_die_with(rng, 6), roll_die_with(rng, 6)];
+
+    // sort and drop the lowest
+    rolls.sort();
+    rolls[1..].iter().sum()
+}
+
+// ability scores
+pub fn generate_ability_scores() -> AbilityScores {
+    generate_ability_scores_with(&mut rand::thread_rng())
+}
+
+pub fn generate_ability_scores_with<R: Rng + ?Sized>(rng: &mut R) -> AbilityScores {
+    AbilityScores {
+        strength: AbilityScore::new(roll_ability_score_with(rng)),
+        dexterity: AbilityScore::new(roll_ability_score_with(rng)),
+        constitution: AbilityScore::new(roll_ability_score_with(rng)),
+        intelligence: AbilityScore::new(roll_ability_score_with(rng)),
+        wisdom: AbilityScore::new(roll_ability_score_with(rng)),
+        charisma: AbilityScore::new(roll_ability_score_with(rng)),
+    }
+}
+
+// which dice of a group count towards the total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    All,
+    Highest(u8),
+    Lowest(u8),
+}
+
+// one part of a dice expression, e.g. "4d6kh3" or "5"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceTerm {
+    Dice { count: u8, sides: u8, keep: Keep },
+    Constant(i32),
+}
+
+// a sum of dice and constants such as "2d6+1d4-1", with "kh"/"kl" to keep the highest or lowest dice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceExpression {
+    terms: Vec<(i32, DiceTerm)>,
+}
+
+// the dice rolled for one term, with the ones that were dropped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermRoll {
+    pub sign: i32,
+    pub term: DiceTerm,
+    pub kept: Vec<u8>,
+    pub dropped: Vec<u8>,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceRoll {
+    pub terms: Vec<TermRoll>,
+    pub total: i32,
+}
+
+// limits that keep an expression sensible to roll and print
+const MAX_DICE: u8 = 100;
+const MAX_CONSTANT: i32 = 10_000;
+
+impl DiceExpression {
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> DiceRoll {
+        let mut terms = Vec::new();
+        let mut total = 0;
+
+        for &(sign, term) in &self.terms {
+            let (kept, dropped, value) = match term {
+                DiceTerm::Constant(n) => (Vec::new(), Vec::new(), n),
+                DiceTerm::Dice { count, sides, keep } => {
+                    let rolls: Vec<u8> = (0..count).map(|_| roll_die_with(rng, sides)).collect();
+
+                    // sort the indices so the kept dice can be shown in the order they were rolled
+                    let mut order: Vec<usize> = (0..rolls.len()).collect();
+                    order.sort_by_key(|&i| rolls[i]);
+                    let dropped_indices: Vec<usize> = match keep {
+                        Keep::All => Vec::new(),
+                        Keep::Highest(n) => order[..rolls.len() - n as usize].to_vec(),
+                        Keep::Lowest(n) => order[n as usize..].to_vec(),
+                    };
+
+                    let mut kept = Vec::new();
+                    let mut dropped = Vec::new();
+                    for (i, &roll) in rolls.iter().enumerate() {
+                        if dropped_indices.contains(&i) {
+                            dropped.push(roll)
+                        } else {
+                            kept.push(roll)
+                        }
+                    }
+                    let value = kept.iter().map(|&r| r as i32).sum();
+                    (kept, dropped, value)
+                },
+            };
+
+            total += sign * value;
+            terms.push(TermRoll { sign, term, kept, dropped, value });
+        }
+
+        DiceRoll { terms, total }
+    }
+}
+
+impl DiceRoll {
+    // e.g. "4d6kh3 [5, 3, 6, (1)] + 2 = 16"
+    pub fn breakdown(&self) -> String {
+        let mut text = String::new();
+
+        for (i, roll) in self.terms.iter().enumerate() {
+            match (i, roll.sign) {
+                (0, -1) => text.push('-'),
+                (0, _) => {},
+                (_, -1) => text.push_str(" - "),
+                _ => text.push_str(" + "),
+            }
+
+            match roll.term {
+                DiceTerm::Constant(n) => text.push_str(&n.to_string()),
+                DiceTerm::Dice { .. } => {
+                    let mut dice: Vec<String> = roll.kept.iter().map(|r| r.to_string()).collect();
+                    dice.extend(roll.dropped.iter().map(|r| format!("({})", r)));
+                    text.push_str(&format!("{} [{}]", roll.term, dice.join(", ")));
+                },
+            }
+        }
+
+        format!("{} = {}", text, self.total)
+    }
+}
+
+impl fmt::Display for DiceTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiceTerm::Constant(n) => write!(f, "{}", n),
+            DiceTerm::Dice { count, sides, keep } => {
+                write!(f, "{}d{}", count, sides)?;
+                match keep {
+                    Keep::All => Ok(()),
+                    Keep::Highest(n) => write!(f, "kh{}", n),
+                    Keep::Lowest(n) => write!(f, "kl{}", n),
+                }
+            },
+        }
+    }
+}
+
+impl fmt::Display for DiceExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (sign, term)) in self.terms.iter().enumerate() {
+            match (i, sign) {
+                (0, -1) => write!(f, "-")?,
+                (0, _) => {},
+                (_, -1) => write!(f, " - ")?,
+                _ => write!(f, " + ")?,
+            }
+            write!(f, "{}", term)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_number(text: &str, what: &str) -> Result<u32, String> {
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("expected a number for the {}, found '{}'", what, text));
+    }
+    text.parse().map_err(|_| format!("{} is too large: {}", what, text))
+}
+
+fn parse_term(text: &str) -> Result<DiceTerm, String> {
+    let (count, rest) = match text.split_once('d') {
+        Some(parts) => parts,
+        None => {
+            let n = parse_number(text, "constant")? as i32;
+            if n > MAX_CONSTANT {
+                return Err(format!("constant is too large: {} (at most {})", n, MAX_CONSTANT));
+            }
+            return Ok(DiceTerm::Constant(n));
+        },
+    };
+
+    // "d20" means one die
+    let count = if count.is_empty() { 1 } else { parse_number(count, "number of dice")? };
+    if count == 0 || count > MAX_DICE as u32 {
+        return Err(format!("number of dice must be 1-{}, found {}", MAX_DICE, count));
+    }
+
+    let (sides, keep) = match rest.find('k') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, ""),
+    };
+    let sides = if sides == "%" { 100 } else { parse_number(sides, "die size")? };
+    if sides == 0 || sides > u8::MAX as u32 {
+        return Err(format!("die size must be 1-{}, found {}", u8::MAX, sides));
+    }
+
+    let keep = match keep {
+        "" => Keep::All,
+        _ => {
+            // "k3" is short for "kh3"
+            let (highest, n) = match keep.strip_prefix("kl") {
+                Some(n) => (false, n),
+                None => (true, keep.strip_prefix("kh").unwrap_or(&keep[1..])),
+            };
+            let n = parse_number(n, "number of dice to keep")?;
+            if n == 0 || n > count {
+                return Err(format!("can only keep 1-{} of {} dice, found {}", count, count, n));
+            }
+            if highest {
+                Keep::Highest(n as u8)
+            } else {
+                Keep::Lowest(n as u8)
+            }
+        },
+    };
+
+    Ok(DiceTerm::Dice { count: count as u8, sides: sides as u8, keep })
+}
+
+impl FromStr for DiceExpression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<DiceExpression, String> {
+        let text: String = s.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+        if text.is_empty() {
+            return Err("empty dice expression".to_string());
+        }
+
+        let mut terms = Vec::new();
+        let mut sign = 1;
+        let mut start = 0;
+
+        // split on + and -, a leading sign belongs to the first term
+        for (i, c) in text.char_indices() {
+            if c == '+' || c == '-' {
+                if i > start {
+                    terms.push((sign, parse_term(&text[start..i])?));
+                } else if i > 0 {
+                    return Err(format!("missing term before '{}' in '{}'", c, s.trim()));
+                }
+                sign = if c == '-' { -1 } else { 1 };
+                start = i + 1;
+            }
+        }
+        if start == text.len() {
+            return Err(format!("missing term at the end of '{}'", s.trim()));
+        }
+        terms.push((sign, parse_term(&text[start..])?));
+
+        Ok(DiceExpression { terms })
+    }
+}
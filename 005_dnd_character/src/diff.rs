@@ -0,0 +1,191 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::resources::ClassResource;
+use crate::sorted_names;
+
+// how one field differs between two versions of a character
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FieldChange {
+    // a value replaced by another, such as the level or one ability score
+    Changed { field: String, before: Value, after: Value },
+    // entries added to or taken out of a list, such as skills or equipment
+    Entries { field: String, added: Vec<String>, removed: Vec<String> },
+}
+
+impl FieldChange {
+    // where the field is in the save file, e.g. "abilities.dexterity"
+    pub fn field(&self) -> &str {
+        match self {
+            FieldChange::Changed { field, .. } | FieldChange::Entries { field, .. } => field,
+        }
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldChange::Changed { field, before, after } => {
+                write!(f, "{}: {} → {}", field, readable(before), readable(after))?;
+                if let (Some(before), Some(after)) = (before.as_i64(), after.as_i64()) {
+                    write!(f, " ({:+})", after - before)?;
+                }
+                Ok(())
+            },
+            FieldChange::Entries { field, added, removed } => {
+                let entries: Vec<String> =
+                    added.iter().map(|e| format!("+{}", e)).chain(removed.iter().map(|e| format!("-{}", e))).collect();
+                write!(f, "{}: {}", field, entries.join(", "))
+            },
+        }
+    }
+}
+
+// strings without quotes and a missing value as "none"
+fn readable(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "none".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// everything that differs between an earlier and a later version of a character
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CharacterDiff {
+    pub before: String,
+    pub after: String,
+    pub changes: Vec<FieldChange>,
+}
+
+impl CharacterDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // the change to one field, if it changed
+    pub fn get(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.field() == field)
+    }
+
+    // print one line per changed field
+    pub fn display(&self) {
+        if self.before == self.after {
+            println!("\n=== DIFF: {} ===", self.after);
+        } else {
+            println!("\n=== DIFF: {} → {} ===", self.before, self.after);
+        }
+        if self.changes.is_empty() {
+            println!("No differences.");
+        }
+        for change in &self.changes {
+            println!("{}", change);
+        }
+    }
+
+    fn value<T: Serialize + PartialEq>(&mut self, field: &str, before: &T, after: &T) {
+        if before != after {
+            let to_value = |value: &T| serde_json::to_value(value).expect("character fields serialize to JSON");
+            self.changes.push(FieldChange::Changed {
+                field: field.to_string(),
+                before: to_value(before),
+                after: to_value(after),
+            });
+        }
+    }
+
+    // compares as lists rather than sets, so a second dagger shows up as added
+    fn entries(&mut self, field: &str, before: Vec<String>, after: Vec<String>) {
+        let mut removed = before;
+        let mut added = Vec::new();
+        for entry in after {
+            match removed.iter().position(|e| *e == entry) {
+                Some(i) => {
+                    removed.remove(i);
+                },
+                None => added.push(entry),
+            }
+        }
+
+        if !added.is_empty() || !removed.is_empty() {
+            added.sort();
+            removed.sort();
+            self.changes.push(FieldChange::Entries { field: field.to_string(), added, removed });
+        }
+    }
+}
+
+impl Character {
+    // compare field by field with a later version of the character, in save file order;
+    // the change history itself isn't compared
+    pub fn diff(&self, after: &Character) -> CharacterDiff {
+        let mut diff = CharacterDiff { before: self.name.clone(), after: after.name.clone(), changes: Vec::new() };
+
+        diff.value("name", &self.name, &after.name);
+        diff.value("race", &self.race, &after.race);
+        diff.value("subrace", &self.subrace, &after.subrace);
+        diff.value("class", &self.class, &after.class);
+        diff.value("background", &self.background, &after.background);
+        diff.value("level", &self.level, &after.level);
+        diff.value("experience", &self.experience, &after.experience);
+        diff.value("advancement", &self.advancement, &after.advancement);
+        for ability in Ability::ALL {
+            let field = format!("abilities.{:?}", ability).to_lowercase();
+            diff.value(&field, &self.abilities.score(ability), &after.abilities.score(ability));
+        }
+        diff.value("hit_points.max", &self.hit_points.max, &after.hit_points.max);
+        diff.value("hit_points.current", &self.hit_points.current, &after.hit_points.current);
+        diff.value("hit_points.temp", &self.hit_points.temp, &after.hit_points.temp);
+        diff.entries("resistances", sorted_names(&self.resistances), sorted_names(&after.resistances));
+        diff.entries("vulnerabilities", sorted_names(&self.vulnerabilities), sorted_names(&after.vulnerabilities));
+        diff.entries("skills", sorted_names(&self.skills), sorted_names(&after.skills));
+        diff.entries("expertise", sorted_names(&self.expertise), sorted_names(&after.expertise));
+        diff.entries("saving_throws", sorted_names(&self.saving_throws), sorted_names(&after.saving_throws));
+        diff.value("hit_dice_spent", &self.hit_dice_spent, &after.hit_dice_spent);
+        diff.value("spell_slots_used", &self.spell_slots_used, &after.spell_slots_used);
+        let mut resources: Vec<ClassResource> =
+            self.resources_used.keys().chain(after.resources_used.keys()).copied().collect();
+        resources.sort_by_key(|resource| format!("{:?}", resource));
+        resources.dedup();
+        for resource in resources {
+            let used = |character: &Character| character.resources_used.get(&resource).copied().unwrap_or(0);
+            diff.value(&format!("resources_used.{:?}", resource), &used(self), &used(after));
+        }
+        diff.value("life_state", &self.life_state, &after.life_state);
+        diff.value("death_saves", &self.death_saves, &after.death_saves);
+        let conditions = |character: &Character| {
+            character.conditions.iter().map(|c| format!("{:?} ({:?})", c.condition, c.duration)).collect()
+        };
+        diff.entries("conditions", conditions(self), conditions(after));
+        diff.entries("feats", sorted_names(&self.feats), sorted_names(&after.feats));
+        diff.entries("languages", sorted_names(&self.languages), sorted_names(&after.languages));
+        diff.entries("tool_proficiencies", self.tool_proficiencies.clone(), after.tool_proficiencies.clone());
+        diff.entries("personality.traits", self.personality.traits.clone(), after.personality.traits.clone());
+        diff.value("personality.ideal", &self.personality.ideal, &after.personality.ideal);
+        diff.value("personality.bond", &self.personality.bond, &after.personality.bond);
+        diff.value("personality.flaw", &self.personality.flaw, &after.personality.flaw);
+        let equipment =
+            |character: &Character| {
+                character
+                    .equipment
+                    .iter()
+                    .map(|item| {
+                        if item.quantity > 1 {
+                            format!("{} x{}", item.name, item.quantity)
+                        } else {
+                            item.name.clone()
+                        }
+                    })
+                    .collect()
+            };
+        diff.entries("equipment", equipment(self), equipment(after));
+        diff.entries("spells", self.spells.clone(), after.spells.clone());
+        diff.value("notes", &self.notes, &after.notes);
+
+        diff
+    }
+}
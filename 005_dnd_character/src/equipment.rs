@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::class::Class;
+use crate::hit_points::DamageType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeaponCategory {
+    Simple,
+    Martial,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Weapon {
+    pub category: WeaponCategory,
+    pub damage: String,
+    pub damage_type: DamageType,
+    pub finesse: bool,
+    pub ranged: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArmorCategory {
+    Light,
+    Medium,
+    Heavy,
+    Shield,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Armor {
+    pub category: ArmorCategory,
+    // base AC, or the bonus for a shield
+    pub base_ac: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemKind {
+    Gear,
+    Weapon(Weapon),
+    Armor(Armor),
+}
+
+// an item in the character's inventory, weight is per item in pounds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub name: String,
+    pub quantity: u16,
+    pub weight: f32,
+    pub kind: ItemKind,
+}
+
+// one option of a starting equipment choice: item names and quantities
+pub type EquipmentOption = Vec<(String, u16)>;
+
+fn gear(name: &str, weight: f32) -> Item {
+    Item { name: name.to_string(), quantity: 1, weight, kind: ItemKind::Gear }
+}
+
+fn weapon(
+    name: &str,
+    weight: f32,
+    category: WeaponCategory,
+    damage: &str,
+    damage_type: DamageType,
+    finesse: bool,
+    ranged: bool,
+) -> Item {
+    Item {
+        name: name.to_string(),
+        quantity: 1,
+        weight,
+        kind: ItemKind::Weapon(Weapon { category, damage: damage.to_string(), damage_type, finesse, ranged }),
+    }
+}
+
+fn armor(name: &str, weight: f32, category: ArmorCategory, base_ac: u8) -> Item {
+    Item { name: name.to_string(), quantity: 1, weight, kind: ItemKind::Armor(Armor { category, base_ac }) }
+}
+
+// every item the starting equipment can hand out
+pub fn catalog() -> Vec<Item> {
+    use DamageType::*;
+    use WeaponCategory::*;
+
+    vec![
+        // simple melee weapons
+        weapon("Club", 2.0, Simple, "1d4", Bludgeoning, false, false),
+        weapon("Dagger", 1.0, Simple, "1d4", Piercing, true, false),
+        weapon("Greatclub", 10.0, Simple, "1d8", Bludgeoning, false, false),
+        weapon("Handaxe", 2.0, Simple, "1d6", Slashing, false, false),
+        weapon("Javelin", 2.0, Simple, "1d6", Piercing, false, false),
+        weapon("Light hammer", 2.0, Simple, "1d4", Bludgeoning, false, false),
+        weapon("Mace", 4.0, Simple, "1d6", Bludgeoning, false, false),
+        weapon("Quarterstaff", 4.0, Simple, "1d6", Bludgeoning, false, false),
+        weapon("Sickle", 2.0, Simple, "1d4", Slashing, false, false),
+        weapon("Spear", 3.0, Simple, "1d6", Piercing, false, false),
+        // simple ranged weapons
+        weapon("Light crossbow", 5.0, Simple, "1d8", Piercing, false, true),
+        weapon("Dart", 0.25, Simple, "1d4", Piercing, true, true),
+        weapon("Shortbow", 2.0, Simple, "1d6", Piercing, false, true),
+        weapon("Sling", 0.0, Simple, "1d4", Bludgeoning, false, true),
+        // martial melee weapons
+        weapon("Battleaxe", 4.0, Martial, "1d8", Slashing, false, false),
+        weapon("Greataxe", 7.0, Martial, "1d12", Slashing, false, false),
+        weapon("Greatsword", 6.0, Martial, "2d6", Slashing, false, false),
+        weapon("Longsword", 3.0, Martial, "1d8", Slashing, false, false),
+        weapon("Maul", 10.0, Martial, "2d6", Bludgeoning, false, false),
+        weapon("Morningstar", 4.0, Martial, "1d8", Piercing, false, false),
+        weapon("Rapier", 2.0, Martial, "1d8", Piercing, true, false),
+        weapon("Scimitar", 3.0, Martial, "1d6", Slashing, true, false),
+        weapon("Shortsword", 2.0, Martial, "1d6", Piercing, true, false),
+        weapon("Warhammer", 2.0, Martial, "1d8", Bludgeoning, false, false),
+        // martial ranged weapons
+        weapon("Hand crossbow", 3.0, Martial, "1d6", Piercing, false, true),
+        weapon("Longbow", 2.0, Martial, "1d8", Piercing, false, true),
+        // armor
+        armor("Leather armor", 10.0, ArmorCategory::Light, 11),
+        armor("Scale mail", 45.0, ArmorCategory::Medium, 14),
+        armor("Chain mail", 55.0, ArmorCategory::Heavy, 16),
+        armor("Shield", 6.0, ArmorCategory::Shield, 2),
+        // gear
+        gear("Arrows", 0.05),
+        gear("Crossbow bolts", 0.075),
+        gear("Burglar's pack", 44.5),
+        gear("Diplomat's pack", 39.0),
+        gear("Dungeoneer's pack", 61.5),
+        gear("Entertainer's pack", 38.0),
+        gear("Explorer's pack", 59.0),
+        gear("Priest's pack", 24.0),
+        gear("Scholar's pack", 10.0),
+        gear("Component pouch", 2.0),
+        gear("Arcane focus", 1.0),
+        gear("Druidic focus", 0.0),
+        gear("Holy symbol", 1.0),
+        gear("Spellbook", 3.0),
+        gear("Thieves' tools", 1.0),
+        gear("Lute", 2.0),
+    ]
+}
+
+// look up an item by name, with the given quantity
+pub fn find_item(name: &str, quantity: u16) -> Option<Item> {
+    catalog().into_iter().find(|item| item.name.eq_ignore_ascii_case(name)).map(|item| Item { quantity, ..item })
+}
+
+// describe a starting equipment option, e.g. "Longsword, Shield" or "Handaxe x2"
+pub fn describe_option(option: &EquipmentOption) -> String {
+    let items: Vec<String> = option
+        .iter()
+        .map(|(name, quantity)| if *quantity > 1 { format!("{} x{}", name, quantity) } else { name.clone() })
+        .collect();
+    items.join(", ")
+}
+
+// parse an item from a content file, e.g. "Javelin x4"
+pub(crate) fn parse_item(text: &str) -> (String, u16) {
+    match text.rsplit_once(" x").and_then(|(name, quantity)| Some((name, quantity.parse().ok()?))) {
+        Some((name, quantity)) => (name.to_string(), quantity),
+        None => (text.to_string(), 1),
+    }
+}
+
+impl Class {
+    // starting equipment as a list of choices, each choice a list of options;
+    // a choice with a single option is given automatically
+    pub fn starting_equipment(&self) -> Vec<Vec<EquipmentOption>> {
+        self.def()
+            .starting_equipment
+            .iter()
+            .map(|choice| choice.iter().map(|option| option.iter().map(|item| parse_item(item)).collect()).collect())
+            .collect()
+    }
+
+    pub fn proficient_with(&self, name: &str, weapon: &Weapon) -> bool {
+        let def = self.def();
+        def.weapon_categories.contains(&weapon.category) || def.weapons.iter().any(|w| w == name)
+    }
+}
+
+// a weapon attack as it appears on the sheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Attack {
+    pub name: String,
+    pub ability: Ability,
+    pub to_hit: i8,
+    pub damage: String,
+    pub damage_type: DamageType,
+}
+
+impl Character {
+    // add items to the inventory, stacking with any of the same name
+    pub fn add_item(&mut self, item: Item) {
+        match self.equipment.iter_mut().find(|i| i.name == item.name) {
+            Some(existing) => existing.quantity += item.quantity,
+            None => self.equipment.push(item),
+        }
+    }
+
+    // take one option of a starting equipment choice
+    pub fn add_equipment_option(&mut self, option: &EquipmentOption) {
+        for (name, quantity) in option {
+            if let Some(item) = find_item(name, *quantity) {
+                self.add_item(item);
+            }
+        }
+    }
+
+    pub fn carried_weight(&self) -> f32 {
+        self.equipment.iter().map(|item| item.weight * item.quantity as f32).sum()
+    }
+
+    // AC from worn armor and shield, or unarmored defense
+    pub fn armor_class(&self) -> u8 {
+        let dexterity = self.abilities.modifier(Ability::Dexterity);
+
+        let mut body = None;
+        let mut shield = 0;
+        for item in &self.equipment {
+            if let ItemKind::Armor(armor) = &item.kind {
+                match armor.category {
+                    ArmorCategory::Shield => shield = armor.base_ac as i8,
+                    _ => body = Some(*armor),
+                }
+            }
+        }
+
+        let base = match body {
+            Some(Armor { category: ArmorCategory::Light, base_ac }) => base_ac as i8 + dexterity,
+            Some(Armor { category: ArmorCategory::Medium, base_ac }) => base_ac as i8 + dexterity.min(2),
+            Some(Armor { base_ac, .. }) => base_ac as i8,
+            // unarmored defense
+            None => match self.class.def().unarmored_defense {
+                Some(defense) if defense.shield || shield == 0 => {
+                    10 + dexterity + self.abilities.modifier(defense.ability)
+                },
+                _ => 10 + dexterity,
+            },
+        };
+
+        (base + shield).max(0) as u8
+    }
+
+    // attacks for every weapon carried
+    pub fn attacks(&self) -> Vec<Attack> {
+        self.equipment
+            .iter()
+            .filter_map(|item| match &item.kind {
+                ItemKind::Weapon(weapon) => Some((item.name.as_str(), weapon)),
+                _ => None,
+            })
+            .map(|(name, weapon)| {
+                let strength = self.abilities.modifier(Ability::Strength);
+                let dexterity = self.abilities.modifier(Ability::Dexterity);
+                let ability = if weapon.ranged || (weapon.finesse && dexterity > strength) {
+                    Ability::Dexterity
+                } else {
+                    Ability::Strength
+                };
+
+                let modifier = self.abilities.modifier(ability);
+                let proficiency = if self.class.proficient_with(name, weapon) { self.proficiency_bonus() } else { 0 };
+
+                Attack {
+                    name: name.to_string(),
+                    ability,
+                    to_hit: modifier + proficiency,
+                    damage: format!("{}{:+}", weapon.damage, modifier),
+                    damage_type: weapon.damage_type,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,447 @@
+use crate::ability::Ability;
+use crate::advancement::AdvancementMode;
+use crate::character::Character;
+use crate::conditions::ConditionDuration;
+use crate::skill::Skill;
+
+// one piece of a sheet section
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    // label: value pairs
+    Fields(Vec<(String, String)>),
+    Table { headers: Vec<String>, rows: Vec<Vec<String>> },
+    List(Vec<String>),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+// the character sheet laid out independent of any output format
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sheet {
+    pub title: String,
+    pub sections: Vec<Section>,
+}
+
+// renders a sheet; implement the block methods for a new format, override render to change the layout
+pub trait Template {
+    fn document(&self, title: &str, body: &str) -> String;
+    fn section(&self, title: &str, body: &str) -> String;
+    fn fields(&self, fields: &[(String, String)]) -> String;
+    fn table(&self, headers: &[String], rows: &[Vec<String>]) -> String;
+    fn list(&self, items: &[String]) -> String;
+    fn text(&self, text: &str) -> String;
+
+    fn block(&self, block: &Block) -> String {
+        match block {
+            Block::Fields(fields) => self.fields(fields),
+            Block::Table { headers, rows } => self.table(headers, rows),
+            Block::List(items) => self.list(items),
+            Block::Text(text) => self.text(text),
+        }
+    }
+
+    fn render(&self, sheet: &Sheet) -> String {
+        let body: String = sheet
+            .sections
+            .iter()
+            .map(|section| {
+                let blocks: String = section.blocks.iter().map(|block| self.block(block)).collect();
+                self.section(&section.title, &blocks)
+            })
+            .collect();
+        self.document(&sheet.title, &body)
+    }
+}
+
+// the sheet as Markdown, for wikis
+pub struct Markdown;
+
+// escape the characters that would break a table cell or start formatting
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            '\n' => escaped.push_str("<br>"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Template for Markdown {
+    fn document(&self, title: &str, body: &str) -> String {
+        format!("# {}\n{}", markdown_escape(title), body)
+    }
+
+    fn section(&self, title: &str, body: &str) -> String {
+        format!("\n## {}\n{}", markdown_escape(title), body)
+    }
+
+    fn fields(&self, fields: &[(String, String)]) -> String {
+        let lines: String = fields
+            .iter()
+            .map(|(label, value)| format!("- **{}:** {}\n", markdown_escape(label), markdown_escape(value)))
+            .collect();
+        format!("\n{}", lines)
+    }
+
+    fn table(&self, headers: &[String], rows: &[Vec<String>]) -> String {
+        let row = |cells: &[String]| {
+            format!("| {} |\n", cells.iter().map(|c| markdown_escape(c)).collect::<Vec<_>>().join(" | "))
+        };
+        let mut table = format!("\n{}", row(headers));
+        table.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+        for cells in rows {
+            table.push_str(&row(cells));
+        }
+        table
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        let lines: String = items.iter().map(|item| format!("- {}\n", markdown_escape(item))).collect();
+        format!("\n{}", lines)
+    }
+
+    fn text(&self, text: &str) -> String {
+        // keep the player's own line breaks as paragraphs
+        let paragraphs: Vec<String> = text.split("\n\n").map(markdown_escape).collect();
+        format!("\n{}\n", paragraphs.join("\n\n"))
+    }
+}
+
+// a self-contained HTML page that prints on a couple of sheets of paper
+pub struct Html;
+
+const HTML_STYLE: &str = "
+body { font-family: Georgia, serif; max-width: 60em; margin: 2em auto; padding: 0 1em; color: #222; }
+h1 { border-bottom: 3px double #7a200d; color: #7a200d; }
+h2 { border-bottom: 1px solid #7a200d; color: #7a200d; font-size: 1.2em; margin-top: 1.5em; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #bbb; padding: 0.2em 0.6em; text-align: left; }
+th { background: #f3ead8; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.2em 1em; }
+dt { font-weight: bold; }
+dd { margin: 0; }
+@media print {
+  body { margin: 0; max-width: none; font-size: 10pt; }
+  section { break-inside: avoid; }
+  h2 { margin-top: 0.8em; }
+}
+";
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Template for Html {
+    fn document(&self, title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+             <style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n{}</body>\n</html>\n",
+            html_escape(title),
+            HTML_STYLE,
+            html_escape(title),
+            body
+        )
+    }
+
+    fn section(&self, title: &str, body: &str) -> String {
+        format!("<section>\n<h2>{}</h2>\n{}</section>\n", html_escape(title), body)
+    }
+
+    fn fields(&self, fields: &[(String, String)]) -> String {
+        let items: String = fields
+            .iter()
+            .map(|(label, value)| format!("<dt>{}</dt><dd>{}</dd>\n", html_escape(label), html_escape(value)))
+            .collect();
+        format!("<dl>\n{}</dl>\n", items)
+    }
+
+    fn table(&self, headers: &[String], rows: &[Vec<String>]) -> String {
+        let cells = |tag: &str, cells: &[String]| -> String {
+            cells.iter().map(|c| format!("<{}>{}</{}>", tag, html_escape(c), tag)).collect()
+        };
+        let mut table = format!("<table>\n<tr>{}</tr>\n", cells("th", headers));
+        for row in rows {
+            table.push_str(&format!("<tr>{}</tr>\n", cells("td", row)));
+        }
+        table.push_str("</table>\n");
+        table
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        let items: String = items.iter().map(|item| format!("<li>{}</li>\n", html_escape(item))).collect();
+        format!("<ul>\n{}</ul>\n", items)
+    }
+
+    fn text(&self, text: &str) -> String {
+        text.split("\n\n")
+            .map(|paragraph| format!("<p>{}</p>\n", html_escape(paragraph).replace('\n', "<br>")))
+            .collect()
+    }
+}
+
+fn field(label: &str, value: impl ToString) -> (String, String) {
+    (label.to_string(), value.to_string())
+}
+
+fn headers(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn or_none(items: Vec<String>) -> String {
+    if items.is_empty() {
+        "None".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+impl Character {
+    // everything on the printed sheet, ready for a template
+    pub fn sheet(&self) -> Sheet {
+        let stats = self.derived_stats();
+        let mut sections = Vec::new();
+
+        let race = match self.subrace {
+            Some(subrace) => format!("{} ({})", self.race, subrace),
+            None => self.race.to_string(),
+        };
+        let level = match (self.advancement, self.next_level_xp()) {
+            (AdvancementMode::Experience, Some(next)) => format!("{} (XP {}/{})", self.level, self.experience, next),
+            (AdvancementMode::Experience, None) => format!("{} (XP {})", self.level, self.experience),
+            (AdvancementMode::Milestone, _) => format!("{} (milestone)", self.level),
+        };
+        sections.push(Section {
+            title: "Character".to_string(),
+            blocks: vec![Block::Fields(vec![
+                field("Race", race),
+                field("Class", self.class),
+                field("Background", self.background),
+                field("Level", level),
+            ])],
+        });
+
+        let abilities = Ability::ALL
+            .iter()
+            .map(|&ability| {
+                let score = self.abilities.score(ability);
+                vec![format!("{:?}", ability), score.to_string(), score.modifier().to_string()]
+            })
+            .collect();
+        sections.push(Section {
+            title: "Abilities".to_string(),
+            blocks: vec![Block::Table { headers: headers(&["Ability", "Score", "Modifier"]), rows: abilities }],
+        });
+
+        let mut combat = vec![
+            field("Armor Class", self.armor_class()),
+            field("Hit Points", format!("{}/{}", self.hit_points.current, self.hit_point_maximum())),
+        ];
+        if self.hit_points.temp > 0 {
+            combat.push(field("Temporary Hit Points", self.hit_points.temp));
+        }
+        combat.extend([
+            field(
+                "Hit Dice",
+                format!("{}/{} (d{})", stats.hit_dice.remaining, stats.hit_dice.total, stats.hit_dice.die),
+            ),
+            field("Initiative", format!("{:+}", stats.initiative)),
+            field("Proficiency Bonus", format!("{:+}", stats.proficiency_bonus)),
+            field("Speed", stats.speeds.to_string()),
+            field("Passive Perception", stats.passive_perception),
+            field("Passive Investigation", stats.passive_investigation),
+            field("Passive Insight", stats.passive_insight),
+        ]);
+        if !self.resistances.is_empty() {
+            let mut resistances: Vec<String> = self.resistances.iter().map(|r| format!("{:?}", r)).collect();
+            resistances.sort();
+            combat.push(field("Resistances", resistances.join(", ")));
+        }
+        if !self.vulnerabilities.is_empty() {
+            let mut vulnerabilities: Vec<String> = self.vulnerabilities.iter().map(|v| format!("{:?}", v)).collect();
+            vulnerabilities.sort();
+            combat.push(field("Vulnerabilities", vulnerabilities.join(", ")));
+        }
+        if !self.conditions.is_empty() {
+            let conditions = self.conditions.iter().map(|active| match active.duration {
+                ConditionDuration::Rounds(rounds) => format!("{:?} ({} rounds)", active.condition, rounds),
+                ConditionDuration::UntilRemoved => format!("{:?}", active.condition),
+            });
+            combat.push(field("Conditions", conditions.collect::<Vec<_>>().join(", ")));
+        }
+        sections.push(Section { title: "Combat".to_string(), blocks: vec![Block::Fields(combat)] });
+
+        let saves = Ability::ALL
+            .iter()
+            .map(|&ability| {
+                let proficient = if self.saving_throws.contains(&ability) { "yes" } else { "" };
+                vec![
+                    format!("{:?}", ability),
+                    proficient.to_string(),
+                    format!("{:+}", self.saving_throw_modifier(ability)),
+                ]
+            })
+            .collect();
+        sections.push(Section {
+            title: "Saving Throws".to_string(),
+            blocks: vec![Block::Table { headers: headers(&["Save", "Proficient", "Bonus"]), rows: saves }],
+        });
+
+        let skills = Skill::grouped_by_ability()
+            .into_iter()
+            .flat_map(|(ability, skills)| skills.into_iter().map(move |skill| (ability, skill)))
+            .map(|(ability, skill)| {
+                let proficiency = if self.expertise.contains(&skill) {
+                    "expertise"
+                } else if self.skills.contains(&skill) {
+                    "proficient"
+                } else {
+                    ""
+                };
+                vec![
+                    format!("{:?}", skill),
+                    format!("{:?}", ability)[..3].to_string(),
+                    proficiency.to_string(),
+                    format!("{:+}", self.skill_modifier(skill)),
+                ]
+            })
+            .collect();
+        sections.push(Section {
+            title: "Skills".to_string(),
+            blocks: vec![Block::Table {
+                headers: headers(&["Skill", "Ability", "Proficiency", "Bonus"]),
+                rows: skills,
+            }],
+        });
+
+        let attacks: Vec<Vec<String>> = self
+            .attacks()
+            .into_iter()
+            .map(|attack| {
+                vec![attack.name, format!("{:+}", attack.to_hit), attack.damage, format!("{:?}", attack.damage_type)]
+            })
+            .collect();
+        let attacks = if attacks.is_empty() {
+            Block::Text("No weapons carried.".to_string())
+        } else {
+            Block::Table { headers: headers(&["Weapon", "To Hit", "Damage", "Type"]), rows: attacks }
+        };
+        sections.push(Section { title: "Attacks".to_string(), blocks: vec![attacks] });
+
+        let slots = self.class.spell_slots(self.level);
+        if slots.iter().any(|&count| count > 0) || !self.spells.is_empty() {
+            let mut blocks = Vec::new();
+            let rows: Vec<Vec<String>> = slots
+                .iter()
+                .enumerate()
+                .filter(|(_, &count)| count > 0)
+                .map(|(i, &count)| {
+                    vec![
+                        (i + 1).to_string(),
+                        count.saturating_sub(self.spell_slots_used[i]).to_string(),
+                        count.to_string(),
+                    ]
+                })
+                .collect();
+            if !rows.is_empty() {
+                blocks.push(Block::Table { headers: headers(&["Slot Level", "Remaining", "Total"]), rows });
+            }
+            if self.spells.is_empty() {
+                blocks.push(Block::Text("No spells recorded.".to_string()));
+            } else {
+                blocks.push(Block::List(self.spells.clone()));
+            }
+            sections.push(Section { title: "Spells".to_string(), blocks });
+        }
+
+        let (feature, description) = self.background.feature();
+        let mut features = vec![field(feature, description)];
+        for resource in self.class.resources().into_iter().filter(|r| r.max_uses(self) > 0) {
+            features.push(field(
+                &format!("{:?}", resource),
+                format!("{}/{} uses", self.resource_remaining(resource), resource.max_uses(self)),
+            ));
+        }
+        let mut blocks = vec![Block::Fields(features)];
+        if !self.feats.is_empty() {
+            blocks.push(Block::List(self.feats.iter().map(|feat| format!("Feat: {:?}", feat)).collect()));
+        }
+        sections.push(Section { title: "Features".to_string(), blocks });
+
+        sections.push(Section {
+            title: "Proficiencies".to_string(),
+            blocks: vec![Block::Fields(vec![
+                field("Languages", or_none(self.languages.iter().map(|l| format!("{:?}", l)).collect())),
+                field("Tools", or_none(self.tool_proficiencies.clone())),
+            ])],
+        });
+
+        let items: Vec<Vec<String>> = self
+            .equipment
+            .iter()
+            .map(|item| {
+                vec![item.name.clone(), item.quantity.to_string(), format!("{} lb", item.weight * item.quantity as f32)]
+            })
+            .collect();
+        let equipment = if items.is_empty() {
+            vec![Block::Text("None".to_string())]
+        } else {
+            vec![
+                Block::Table { headers: headers(&["Item", "Quantity", "Weight"]), rows: items },
+                Block::Fields(vec![
+                    field("Carrying", format!("{} lb", self.carried_weight())),
+                    field("Capacity", format!("{} lb", stats.carrying.capacity)),
+                ]),
+            ]
+        };
+        sections.push(Section { title: "Equipment".to_string(), blocks: equipment });
+
+        let mut personality = Vec::new();
+        if !self.personality.traits.is_empty() {
+            personality.push(field("Traits", self.personality.traits.join(" ")));
+        }
+        for (label, value) in
+            [("Ideal", &self.personality.ideal), ("Bond", &self.personality.bond), ("Flaw", &self.personality.flaw)]
+        {
+            if !value.is_empty() {
+                personality.push(field(label, value));
+            }
+        }
+        if !personality.is_empty() {
+            sections.push(Section { title: "Personality".to_string(), blocks: vec![Block::Fields(personality)] });
+        }
+
+        if !self.notes.trim().is_empty() {
+            sections
+                .push(Section { title: "Notes".to_string(), blocks: vec![Block::Text(self.notes.trim().to_string())] });
+        }
+
+        Sheet { title: self.name.clone(), sections }
+    }
+
+    // the sheet rendered with a template, e.g. character.export(&Markdown)
+    pub fn export(&self, template: &dyn Template) -> String {
+        template.render(&self.sheet())
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+// feats that change the numbers on the sheet
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Feat {
+    // +5 to initiative
+    Alert,
+    // +10 ft walking speed
+    Mobile,
+    // +5 to passive Perception and Investigation
+    Observant,
+}
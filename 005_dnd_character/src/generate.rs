@@ -0,0 +1,110 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::ability::Ability;
+use crate::advancement::{HitPointIncrease, XP_THRESHOLDS};
+use crate::background::{Background, Personality};
+use crate::character::Character;
+use crate::class::{class_skill_choices, get_expertise_count, granted_skills, Class};
+use crate::dice::generate_ability_scores_with;
+use crate::language::Language;
+use crate::names::random_name;
+use crate::race::{Race, Subrace};
+use crate::skill::Skill;
+
+// optional constraints for a random character, anything left as None is picked at random
+#[derive(Debug, Clone, Default)]
+pub struct RandomOptions {
+    pub name: Option<String>,
+    pub race: Option<Race>,
+    // a subrace also decides the race, overriding `race`
+    pub subrace: Option<Subrace>,
+    pub class: Option<Class>,
+    pub background: Option<Background>,
+    pub level: Option<u8>,
+}
+
+fn pick<T: Copy, R: Rng + ?Sized>(items: &[T], rng: &mut R) -> T {
+    *items.choose(rng).expect("nothing to choose from")
+}
+
+// pick `count` different items
+fn pick_many<T: Copy, R: Rng + ?Sized>(items: &[T], count: usize, rng: &mut R) -> Vec<T> {
+    items.choose_multiple(rng, count).copied().collect()
+}
+
+// build a whole character without prompts, using the same rules as the interactive creator
+pub fn random_character<R: Rng + ?Sized>(options: &RandomOptions, rng: &mut R) -> Character {
+    let race = match options.subrace {
+        Some(subrace) => subrace.race(),
+        None => options.race.unwrap_or_else(|| pick(&Race::all(), rng)),
+    };
+    let subrace = options.subrace.or_else(|| race.subraces().choose(rng).copied());
+    let class = options.class.unwrap_or_else(|| pick(&Class::all(), rng));
+    let background = options.background.unwrap_or_else(|| pick(&Background::all(), rng));
+
+    // 4d6 drop lowest, then racial bonuses
+    let mut abilities = generate_ability_scores_with(rng);
+    abilities.apply_racial_bonuses(race);
+    if let Some(subrace) = subrace {
+        abilities.apply_subrace_bonuses(subrace);
+    }
+    if race.ability_choices() > 0 {
+        for ability in pick_many(&race.ability_choice_options(), race.ability_choices(), rng) {
+            abilities.apply_chosen_bonus(ability);
+        }
+    }
+
+    // background and racial skills, then class skills to make up the count
+    let mut skills = granted_skills(background, race);
+    let (available, remaining) = class_skill_choices(&class, &skills);
+    skills.extend(pick_many(&available, remaining, rng));
+
+    let proficient: Vec<Skill> = Skill::ALL.into_iter().filter(|s| skills.contains(s)).collect();
+    let expertise = pick_many(&proficient, get_expertise_count(&class), rng).into_iter().collect();
+
+    let name = options.name.clone().unwrap_or_else(|| random_name(race, rng));
+    let mut character = Character::new(name, race, subrace, class, background, abilities, skills, expertise);
+
+    // extra languages and personality
+    let unknown: Vec<Language> = Language::ALL.into_iter().filter(|l| !character.languages.contains(l)).collect();
+    let extra = race.extra_languages() + background.extra_languages();
+    character.languages.extend(pick_many(&unknown, extra, rng));
+    character.personality = Personality::roll(background, rng);
+
+    // one option from every starting equipment choice
+    for choice in class.starting_equipment() {
+        if let Some(option) = choice.choose(rng) {
+            character.add_equipment_option(option);
+        }
+    }
+
+    // level up to the requested level, rolling hit points and improving the primary ability
+    let level = options.level.unwrap_or(1).clamp(1, XP_THRESHOLDS.len() as u8);
+    while character.level < level {
+        let summary = match character.level_up(HitPointIncrease::Roll, rng) {
+            Some(summary) => summary,
+            None => break,
+        };
+
+        if summary.ability_score_improvement {
+            let primary = class.primary_ability();
+            let increase = character.improve_ability(primary, 2);
+            if increase < 2 {
+                let others: Vec<Ability> = Ability::ALL.into_iter().filter(|&a| a != primary).collect();
+                character.improve_ability(pick(&others, rng), 2 - increase);
+            }
+        }
+
+        if summary.new_expertise > 0 {
+            let candidates: Vec<Skill> = Skill::ALL
+                .into_iter()
+                .filter(|s| character.skills.contains(s) && !character.expertise.contains(s))
+                .collect();
+            character.expertise.extend(pick_many(&candidates, summary.new_expertise as usize, rng));
+        }
+    }
+    character.experience = XP_THRESHOLDS[level as usize - 1];
+
+    character
+}
@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::character::Character;
+use crate::diff::FieldChange;
+
+// one change to a character, with the whole character as it was afterwards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // seconds since the Unix epoch
+    pub timestamp: u64,
+    // what changed, e.g. "L3 → L4: +2 Dex"
+    pub change: String,
+    // why it changed, e.g. "ASI" or "DM ruling"
+    pub reason: String,
+    // snapshots never have a history of their own
+    pub snapshot: Character,
+}
+
+impl HistoryEntry {
+    // "2026-10-19 14:03 UTC"
+    pub fn date(&self) -> String {
+        let days = (self.timestamp / 86_400) as i64;
+        let minutes = self.timestamp % 86_400 / 60;
+
+        // days since 1970-01-01 to a calendar date, after Howard Hinnant's civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes / 60, minutes % 60)
+    }
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.reason.is_empty() {
+            write!(f, "{}", self.change)
+        } else {
+            write!(f, "{} ({})", self.change, self.reason)
+        }
+    }
+}
+
+impl Character {
+    // the character without its history, as kept in each entry
+    fn snapshot(&self) -> Character {
+        Character { history: Vec::new(), ..self.clone() }
+    }
+
+    // add an entry for the character as it is now; entries are only ever added, never edited
+    pub fn record(&mut self, change: impl Into<String>, reason: impl Into<String>) {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        let entry = HistoryEntry { timestamp, change: change.into(), reason: reason.into(), snapshot: self.snapshot() };
+        self.history.push(entry);
+    }
+
+    // the first entry of a new or imported character
+    pub fn record_created(&mut self, reason: impl Into<String>) {
+        let change = format!("Created level {} {} {}", self.level, self.race, self.class);
+        self.record(change, reason);
+    }
+
+    // record what changed since `before`, returns false if nothing did; a character
+    // with no history yet gets `before` as its first version so it can be reverted to
+    pub fn record_changes(&mut self, before: &Character, reason: impl Into<String>) -> bool {
+        let Some(change) = changes_between(before, self) else {
+            return false;
+        };
+
+        if self.history.is_empty() {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+            let reason = "existing save".to_string();
+            self.history.push(HistoryEntry {
+                timestamp,
+                change: untracked(before),
+                reason,
+                snapshot: before.snapshot(),
+            });
+        }
+        self.record(change, reason);
+        true
+    }
+
+    // changes since the last recorded version that were never recorded, such as hand edits to the save file;
+    // only the build is compared, so damage, rests and spent slots don't count
+    pub fn unrecorded_changes(&self) -> Option<String> {
+        changes_between(&self.history.last()?.snapshot, self)
+    }
+
+    // record whatever changed since the last version, such as hand edits; a character with no
+    // history gets its first version. Returns false if there was nothing to record
+    pub fn record_edits(&mut self, reason: impl Into<String>) -> bool {
+        let change = if self.history.is_empty() {
+            untracked(self)
+        } else {
+            match self.unrecorded_changes() {
+                Some(change) => change,
+                None => return false,
+            }
+        };
+        self.record(change, reason);
+        true
+    }
+
+    // the character as recorded in a version, counting from 1
+    pub fn version(&self, version: usize) -> Option<&Character> {
+        self.history.get(version.checked_sub(1)?).map(|entry| &entry.snapshot)
+    }
+
+    // go back to an earlier version, everything after it stays in the history;
+    // unrecorded changes are recorded first so they aren't lost
+    pub fn revert(&mut self, version: usize, reason: impl Into<String>) -> Result<(), String> {
+        let mut reverted =
+            self.version(version).cloned().ok_or_else(|| format!("There is no version {} in the history.", version))?;
+
+        self.record_edits("not recorded before a revert");
+
+        let change = match changes_between(self, &reverted) {
+            Some(changes) => format!("Reverted to version {}: {}", version, changes),
+            None => format!("Reverted to version {}", version),
+        };
+        reverted.history = std::mem::take(&mut self.history);
+        *self = reverted;
+        self.record(change, reason);
+        Ok(())
+    }
+
+    // print every version, oldest first
+    pub fn display_history(&self) {
+        println!("\n=== HISTORY: {} ===", self.name);
+        if self.history.is_empty() {
+            println!("No changes recorded yet.");
+            return;
+        }
+
+        for (i, entry) in self.history.iter().enumerate() {
+            println!("{:>3}. {}  {}", i + 1, entry.date(), entry);
+        }
+        if let Some(changes) = self.unrecorded_changes() {
+            println!("\nNot recorded since version {}: {}", self.history.len(), changes);
+        }
+    }
+}
+
+// the first version of a character saved before it had a history
+fn untracked(character: &Character) -> String {
+    format!("Level {} {} {} before the history was kept", character.level, character.race, character.class)
+}
+
+// a one-line summary of how the build differs, or None if it doesn't; made from the
+// field-by-field diff, leaving out damage, rests, XP, equipment and notes
+fn changes_between(before: &Character, after: &Character) -> Option<String> {
+    let mut level = None;
+    let mut changes = Vec::new();
+
+    for change in before.diff(after).changes {
+        match change {
+            FieldChange::Changed { ref field, before: ref from, after: ref to } => {
+                let difference = to.as_i64().zip(from.as_i64()).map(|(to, from)| to - from);
+                match (field.as_str(), difference) {
+                    ("level", _) => level = Some(format!("L{} → L{}", from, to)),
+                    ("hit_points.max", Some(difference)) => changes.push(format!("{:+} max HP", difference)),
+                    (field, Some(difference)) if field.starts_with("abilities.") => {
+                        let ability = &field["abilities.".len()..];
+                        changes.push(format!("{:+} {}{}", difference, ability[..1].to_uppercase(), &ability[1..3]));
+                    },
+                    ("name" | "race" | "subrace" | "class" | "background" | "advancement", _) => {
+                        changes.push(change.to_string())
+                    },
+                    _ => {},
+                }
+            },
+            FieldChange::Entries { field, added, removed } => {
+                // "+Stealth proficiency" and "-Elvish"
+                let label = match field.as_str() {
+                    "saving_throws" => " save",
+                    "skills" | "tool_proficiencies" => " proficiency",
+                    "expertise" => " expertise",
+                    "feats" => " feat",
+                    "languages" => "",
+                    "resistances" => " resistance",
+                    "vulnerabilities" => " vulnerability",
+                    "spells" => " spell",
+                    _ => continue,
+                };
+                changes.extend(added.iter().map(|entry| format!("+{}{}", entry, label)));
+                changes.extend(removed.iter().map(|entry| format!("-{}{}", entry, label)));
+            },
+        }
+    }
+
+    let changes = changes.join(", ");
+    match (level, changes.is_empty()) {
+        (None, true) => None,
+        (None, false) => Some(changes),
+        (Some(level), true) => Some(level),
+        (Some(level), false) => Some(format!("{}: {}", level, changes)),
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+// damage types
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum DamageType {
+    Acid,
+    Bludgeoning,
+    Cold,
+    Fire,
+    Force,
+    Lightning,
+    Necrotic,
+    Piercing,
+    Poison,
+    Psychic,
+    Radiant,
+    Slashing,
+    Thunder,
+}
+
+// current, maximum and temporary hit points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HitPoints {
+    pub max: u16,
+    pub current: u16,
+    pub temp: u16,
+}
+
+impl HitPoints {
+    pub fn new(max: u16) -> HitPoints {
+        HitPoints { max, current: max, temp: 0 }
+    }
+
+    // temporary hit points absorb damage first, the rest comes off current hit points,
+    // returns the damage left over once current hit points reach 0
+    pub fn lose(&mut self, amount: u16) -> u16 {
+        let absorbed = amount.min(self.temp);
+        self.temp -= absorbed;
+
+        let remaining = amount - absorbed;
+        let lost = remaining.min(self.current);
+        self.current -= lost;
+        remaining - lost
+    }
+
+    // heal up to the maximum, returns the hit points actually regained
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        // a hand-edited save or a lowered maximum can leave current above max
+        let healed = amount.min(self.max.saturating_sub(self.current));
+        self.current += healed;
+        healed
+    }
+
+    // temporary hit points don't stack, keep whichever is higher
+    pub fn grant_temp(&mut self, amount: u16) {
+        self.temp = self.temp.max(amount);
+    }
+}
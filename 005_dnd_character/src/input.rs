@@ -0,0 +1,76 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+// where the interactive creator gets its answers from
+pub trait Input {
+    // the next line of input without its line ending, None once the input has run out
+    fn read_line(&mut self) -> Option<String>;
+}
+
+// answers typed at the terminal
+#[derive(Debug, Default)]
+pub struct StdinInput;
+
+impl Input for StdinInput {
+    fn read_line(&mut self) -> Option<String> {
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+            Err(e) => panic!("Failed to read line: {}", e),
+        }
+    }
+}
+
+// a fixed list of answers, given in order
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput {
+    answers: VecDeque<String>,
+}
+
+impl ScriptedInput {
+    pub fn new<I, S>(answers: I) -> ScriptedInput
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ScriptedInput { answers: answers.into_iter().map(Into::into).collect() }
+    }
+
+    // answers that haven't been read yet
+    pub fn remaining(&self) -> usize {
+        self.answers.len()
+    }
+}
+
+impl Input for ScriptedInput {
+    fn read_line(&mut self) -> Option<String> {
+        self.answers.pop_front()
+    }
+}
+
+// answers read from a JSON file holding an array of strings, e.g. ["2", "1", "Aria"]
+#[derive(Debug, Clone)]
+pub struct JsonInput {
+    answers: ScriptedInput,
+}
+
+impl JsonInput {
+    pub fn from_json(json: &str) -> io::Result<JsonInput> {
+        let answers: Vec<String> =
+            serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(JsonInput { answers: ScriptedInput::new(answers) })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<JsonInput> {
+        JsonInput::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+impl Input for JsonInput {
+    fn read_line(&mut self) -> Option<String> {
+        self.answers.read_line()
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+// standard and exotic languages
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Serialize, Deserialize)]
+pub enum Language {
+    Common,
+    Dwarvish,
+    Elvish,
+    Giant,
+    Gnomish,
+    Goblin,
+    Halfling,
+    Orc,
+    Abyssal,
+    Celestial,
+    Draconic,
+    DeepSpeech,
+    Infernal,
+    Primordial,
+    Sylvan,
+    Undercommon,
+}
+
+impl Language {
+    pub const ALL: [Language; 16] = [
+        Language::Common,
+        Language::Dwarvish,
+        Language::Elvish,
+        Language::Giant,
+        Language::Gnomish,
+        Language::Goblin,
+        Language::Halfling,
+        Language::Orc,
+        Language::Abyssal,
+        Language::Celestial,
+        Language::Draconic,
+        Language::DeepSpeech,
+        Language::Infernal,
+        Language::Primordial,
+        Language::Sylvan,
+        Language::Undercommon,
+    ];
+}
@@ -0,0 +1,83 @@
+pub mod ability;
+pub mod advancement;
+pub mod background;
+pub mod character;
+pub mod class;
+pub mod conditions;
+pub mod content;
+pub mod content_check;
+pub mod creator;
+pub mod death;
+pub mod derived;
+pub mod dice;
+pub mod diff;
+pub mod equipment;
+pub mod export;
+pub mod feat;
+pub mod generate;
+pub mod history;
+pub mod hit_points;
+pub mod input;
+pub mod language;
+pub mod names;
+pub mod party;
+pub mod pdf;
+pub mod race;
+pub mod resources;
+pub mod rest;
+pub mod roll;
+pub mod save;
+pub mod skill;
+pub mod spellcasting;
+pub mod validate;
+pub mod vtt;
+
+pub use ability::{Ability, AbilityScore, AbilityScores, Modifier};
+pub use advancement::{AdvancementMode, HitPointIncrease, LevelUpSummary};
+pub use background::{Background, Personality};
+pub use character::Character;
+pub use class::Class;
+pub use conditions::{ActiveCondition, Condition, ConditionDuration, RollKind};
+pub use content::{load_content, Content};
+pub use content_check::{check_packs, ContentProblem};
+pub use death::{DeathSaveResult, DeathSaves, LifeState};
+pub use derived::{CarryingCapacity, DerivedStats, HitDiceTotals, Speeds};
+pub use dice::{DiceExpression, DiceRoll};
+pub use diff::{CharacterDiff, FieldChange};
+pub use equipment::{Attack, Item};
+pub use export::{Html, Markdown, Sheet, Template};
+pub use feat::Feat;
+pub use generate::{random_character, RandomOptions};
+pub use history::HistoryEntry;
+pub use hit_points::{DamageType, HitPoints};
+pub use input::{Input, JsonInput, ScriptedInput, StdinInput};
+pub use language::Language;
+pub use names::{generate_name, random_name, NameStyle};
+pub use party::{Party, PartySummary};
+pub use race::{Race, Subrace};
+pub use resources::{ClassResource, Recharge};
+pub use rest::RestSummary;
+pub use roll::{D20Roll, RollMode};
+pub use skill::Skill;
+pub use validate::{Severity, Violation};
+pub use vtt::{Conversion, VttFormat};
+
+// an enum value's name as saved, e.g. "SleightOfHand" or "Fire"
+pub(crate) fn saved_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+// saved names sorted, so hash set order doesn't matter
+pub(crate) fn sorted_names<'a, T: serde::Serialize + 'a>(values: impl IntoIterator<Item = &'a T>) -> Vec<String> {
+    let mut names: Vec<String> = values.into_iter().map(saved_name).collect();
+    names.sort();
+    names
+}
+
+// compare user input with an enum variant name or content id, ignoring case, spaces, dashes and underscores
+pub(crate) fn matches_name(input: &str, name: &str) -> bool {
+    let normalize =
+        |s: &str| -> String { s.chars().filter(|c| c.is_alphanumeric()).flat_map(|c| c.to_lowercase()).collect() };
+
+    normalize(input) == normalize(name)
+}
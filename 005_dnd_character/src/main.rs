@@ -1,780 +1,1254 @@
-use rand::Rng;
-use std::io;
-use std::collections::{HashSet, HashMap};
-
-// race
-#[derive(Debug, Clone, Copy)]
-enum Race {
-    Human,
-    Elf,
-    Dwarf,
-    Halfling,
-    Dragonborn,
-    Gnome,
-    HalfElf,
-    HalfOrc,
-    Tiefling,
-}
-
-impl Race {
-    fn ability_bonuses(&self) -> HashMap<Ability, i8> {
-        let mut bonuses = HashMap::new();
-
-        match self {
-            Race::Human => {
-                bonuses.insert(Ability::Strength, 1);
-                bonuses.insert(Ability::Dexterity, 1);
-                bonuses.insert(Ability::Constitution, 1);
-                bonuses.insert(Ability::Intelligence, 1);
-                bonuses.insert(Ability::Wisdom, 1);
-                bonuses.insert(Ability::Charisma, 1);
-            },
-            Race::Elf => {
-                bonuses.insert(Ability::Dexterity, 2);
-            },
-            Race::Dwarf => {
-                bonuses.insert(Ability::Constitution, 2);
-            },
-            Race::Halfling => {
-                bonuses.insert(Ability::Dexterity, 2);
-            },
-            Race::Dragonborn => {
-                bonuses.insert(Ability::Strength, 2);
-                bonuses.insert(Ability::Charisma, 1);
-            },
-            Race::Gnome => {
-                bonuses.insert(Ability::Intelligence, 2);
-            },
-            Race::HalfElf => {
-                bonuses.insert(Ability::Charisma, 2);
-                // Half-Elf gets +1 to two other abilities
-            },
-            Race::HalfOrc => {
-                bonuses.insert(Ability::Strength, 2);
-                bonuses.insert(Ability::Constitution, 1);
-            },
-            Race::Tiefling => {
-                bonuses.insert(Ability::Intelligence, 1);
-                bonuses.insert(Ability::Charisma, 2);
-            },
-        }
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+
+use dnd_character::ability::Ability;
+use dnd_character::advancement::MAX_LEVEL;
+use dnd_character::creator;
+use dnd_character::save::{load_character, save_character};
+use dnd_character::{
+    check_packs, load_content, random_character, AdvancementMode, Character, Condition, ConditionDuration, DamageType,
+    DeathSaveResult, DiceExpression, Feat, HitPointIncrease, Html, JsonInput, LifeState, Markdown, Party, PartySummary,
+    RandomOptions, RestSummary, RollMode, Severity, Skill, StdinInput, VttFormat,
+};
+
+mod tui;
+
+// ask for a file name and save the character there, returns the path used
+fn prompt_save(character: &Character) -> Option<String> {
+    println!("\nEnter a file name to save your character (leave blank to skip):");
+    let mut path = String::new();
+    io::stdin().read_line(&mut path).expect("Failed to read line");
+    let path = path.trim();
+
+    if path.is_empty() {
+        return None;
+    }
 
-        bonuses
+    match save_character(character, path) {
+        Ok(()) => {
+            println!("Character saved to {}", path);
+            Some(path.to_string())
+        },
+        Err(e) => {
+            println!("Failed to save character: {}", e);
+            None
+        },
     }
+}
 
-    fn racial_skills(&self) -> Vec<Skill> {
-        match self {
-            Race::Elf => vec![Skill::Perception],
-            Race::Dwarf => vec![Skill::History], // stonecunning
-            Race::Halfling => vec![Skill::Acrobatics], // naturally Nimble
-            Race::HalfOrc => vec![Skill::Intimidation],
-            _ => vec![],
-        }
+// load a character from a save file
+fn prompt_load() -> Option<(Character, String)> {
+    println!("\nEnter the file name of the saved character:");
+    let mut path = String::new();
+    io::stdin().read_line(&mut path).expect("Failed to read line");
+    let path = path.trim().to_string();
+
+    match load_character(&path) {
+        Ok(character) => {
+            for violation in character.validate() {
+                println!("{}", violation);
+            }
+            Some((character, path))
+        },
+        Err(e) => {
+            println!("Failed to load character: {}", e);
+            None
+        },
     }
 }
 
-// classes
-#[derive(Debug)]
-enum Class {
-    Barbarian,
-    Bard,
-    Cleric,
-    Druid,
-    Fighter,
-    Monk,
-    Paladin,
-    Ranger,
-    Rogue,
-    Sorcerer,
-    Warlock,
-    Wizard,
-}
-
-impl Class {
-    fn hit_die(&self) -> u8 {
-        match self {
-            Class::Barbarian => 12,
-            Class::Fighter | Class::Paladin | Class::Ranger => 10,
-            Class::Bard | Class::Cleric | Class::Druid | Class::Monk | Class::Rogue | Class::Warlock => 8,
-            Class::Sorcerer | Class::Wizard => 6,
+// short rest, spending hit dice one at a time
+fn short_rest(character: &mut Character) {
+    let mut rng = rand::thread_rng();
+    let mut summary = RestSummary::default();
+
+    while character.hit_dice_remaining() > 0 && character.hit_points.current < character.hit_point_maximum() {
+        println!(
+            "\nHP {}/{}, {} hit dice (d{}) left. Spend a hit die? (y/n)",
+            character.hit_points.current,
+            character.hit_point_maximum(),
+            character.hit_dice_remaining(),
+            character.class.hit_die()
+        );
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+        if !input.trim().eq_ignore_ascii_case("y") {
+            break;
         }
-    }
 
-    fn saving_throws(&self) -> (Ability, Ability) {
-        match self {
-            Class::Barbarian => (Ability::Strength, Ability::Constitution),
-            Class::Bard => (Ability::Dexterity, Ability::Charisma),
-            Class::Cleric => (Ability::Wisdom, Ability::Charisma),
-            Class::Druid => (Ability::Intelligence, Ability::Wisdom),
-            Class::Fighter => (Ability::Strength, Ability::Constitution),
-            Class::Monk => (Ability::Strength, Ability::Dexterity),
-            Class::Paladin => (Ability::Wisdom, Ability::Charisma),
-            Class::Ranger => (Ability::Strength, Ability::Dexterity),
-            Class::Rogue => (Ability::Dexterity, Ability::Intelligence),
-            Class::Sorcerer => (Ability::Constitution, Ability::Charisma),
-            Class::Warlock => (Ability::Wisdom, Ability::Charisma),
-            Class::Wizard => (Ability::Intelligence, Ability::Wisdom),
+        if let Some((roll, healed)) = character.spend_hit_die(&mut rng) {
+            println!("Rolled {}, regained {} HP.", roll, healed);
+            summary.hit_dice_rolls.push(roll);
+            summary.hit_points_regained += healed;
         }
     }
+
+    // the dice are already spent, this only brings back short rest resources, once
+    character.short_rest(0, &mut rng);
+
+    println!(
+        "\nShort rest finished. Regained {} HP from {} hit dice.",
+        summary.hit_points_regained,
+        summary.hit_dice_rolls.len()
+    );
 }
 
-// abilities
-#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
-enum Ability {
-    Strength,
-    Dexterity,
-    Constitution,
-    Intelligence,
-    Wisdom,
-    Charisma,
-}
-
-// skills and their associated abilities
-#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
-enum Skill {
-    Acrobatics,
-    AnimalHandling,
-    Arcana,
-    Athletics,
-    Deception,
-    History,
-    Insight,
-    Intimidation,
-    Investigation,
-    Medicine,
-    Nature,
-    Perception,
-    Performance,
-    Persuasion,
-    Religion,
-    SleightOfHand,
-    Stealth,
-    Survival,
-}
-
-impl Skill {
-    fn associated_ability(&self) -> Ability {
-        match self {
-            Skill::Acrobatics => Ability::Dexterity,
-            Skill::AnimalHandling => Ability::Wisdom,
-            Skill::Arcana => Ability::Intelligence,
-            Skill::Athletics => Ability::Strength,
-            Skill::Deception => Ability::Charisma,
-            Skill::History => Ability::Intelligence,
-            Skill::Insight => Ability::Wisdom,
-            Skill::Intimidation => Ability::Charisma,
-            Skill::Investigation => Ability::Intelligence,
-            Skill::Medicine => Ability::Wisdom,
-            Skill::Nature => Ability::Intelligence,
-            Skill::Perception => Ability::Wisdom,
-            Skill::Performance => Ability::Charisma,
-            Skill::Persuasion => Ability::Charisma,
-            Skill::Religion => Ability::Intelligence,
-            Skill::SleightOfHand => Ability::Dexterity,
-            Skill::Stealth => Ability::Dexterity,
-            Skill::Survival => Ability::Wisdom,
+// select a damage type
+fn select_damage_type() -> DamageType {
+    const DAMAGE_TYPES: [DamageType; 13] = [
+        DamageType::Acid,
+        DamageType::Bludgeoning,
+        DamageType::Cold,
+        DamageType::Fire,
+        DamageType::Force,
+        DamageType::Lightning,
+        DamageType::Necrotic,
+        DamageType::Piercing,
+        DamageType::Poison,
+        DamageType::Psychic,
+        DamageType::Radiant,
+        DamageType::Slashing,
+        DamageType::Thunder,
+    ];
+
+    println!("\nSelect the damage type:");
+    for (i, damage_type) in DAMAGE_TYPES.iter().enumerate() {
+        println!("{}. {:?}", i + 1, damage_type);
+    }
+
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= DAMAGE_TYPES.len() => return DAMAGE_TYPES[n - 1],
+            _ => println!("Invalid selection. Please choose 1-{}.", DAMAGE_TYPES.len()),
         }
     }
 }
 
-// backgrounds
-#[derive(Debug, Clone, Copy)]
-enum Background {
-    Acolyte,
-    Charlatan,
-    Criminal,
-    Entertainer,
-    FolkHero,
-    GuildArtisan,
-    Hermit,
-    Noble,
-    Outlander,
-    Sage,
-    Sailor,
-    Soldier,
-    Urchin,
-}
-
-impl Background {
-    fn skills(&self) -> [Skill; 2] {
-        match self {
-            Background::Acolyte => [Skill::Insight, Skill::Religion],
-            Background::Charlatan => [Skill::Deception, Skill::SleightOfHand],
-            Background::Criminal => [Skill::Deception, Skill::Stealth],
-            Background::Entertainer => [Skill::Acrobatics, Skill::Performance],
-            Background::FolkHero => [Skill::AnimalHandling, Skill::Survival],
-            Background::GuildArtisan => [Skill::Insight, Skill::Persuasion],
-            Background::Hermit => [Skill::Medicine, Skill::Religion],
-            Background::Noble => [Skill::History, Skill::Persuasion],
-            Background::Outlander => [Skill::Athletics, Skill::Survival],
-            Background::Sage => [Skill::Arcana, Skill::History],
-            Background::Sailor => [Skill::Athletics, Skill::Perception],
-            Background::Soldier => [Skill::Athletics, Skill::Intimidation],
-            Background::Urchin => [Skill::SleightOfHand, Skill::Stealth],
+// read a number of hit points
+fn read_amount(prompt: &str) -> u16 {
+    println!("\n{}", prompt);
+
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u16>() {
+            Ok(n) => return n,
+            _ => println!("Invalid amount. Please enter a whole number."),
         }
     }
 }
 
-// stats with modifiers
-#[derive(Debug)]
-struct AbilityScores {
-    strength: u8,
-    dexterity: u8,
-    constitution: u8,
-    intelligence: u8,
-    wisdom: u8,
-    charisma: u8,
-}
+// report the current hit points and dying state after a change
+fn print_hit_points(character: &Character) {
+    print!("HP {}/{}", character.hit_points.current, character.hit_points.max);
+    if character.hit_points.temp > 0 {
+        print!(" (+{} temporary)", character.hit_points.temp);
+    }
 
-impl AbilityScores {
-    fn modifier(&self, ability: Ability) -> i8 {
-        let score = match ability {
-            Ability::Strength => self.strength,
-            Ability::Dexterity => self.dexterity,
-            Ability::Constitution => self.constitution,
-            Ability::Intelligence => self.intelligence,
-            Ability::Wisdom => self.wisdom,
-            Ability::Charisma => self.charisma,
-        };
+    match character.life_state {
+        LifeState::Conscious => println!(),
+        LifeState::Dying => println!(
+            " - dying ({} successes, {} failures)",
+            character.death_saves.successes, character.death_saves.failures
+        ),
+        LifeState::Stable => println!(" - unconscious but stable"),
+        LifeState::Dead => println!(" - dead"),
+    }
+}
 
-        ((score as i8) - 10) / 2
+// select one of the six abilities
+fn select_ability() -> Ability {
+    println!("\nSelect an ability:");
+    for (i, ability) in Ability::ALL.iter().enumerate() {
+        println!("{}. {:?}", i + 1, ability);
     }
 
-    fn apply_racial_bonuses(&mut self, race: Race) {
-        let bonuses = race.ability_bonuses();
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
 
-        for (ability, bonus) in bonuses {
-            match ability {
-                Ability::Strength => self.strength = (self.strength as i8 + bonus) as u8,
-                Ability::Dexterity => self.dexterity = (self.dexterity as i8 + bonus) as u8,
-                Ability::Constitution => self.constitution = (self.constitution as i8 + bonus) as u8,
-                Ability::Intelligence => self.intelligence = (self.intelligence as i8 + bonus) as u8,
-                Ability::Wisdom => self.wisdom = (self.wisdom as i8 + bonus) as u8,
-                Ability::Charisma => self.charisma = (self.charisma as i8 + bonus) as u8,
-            }
+        match input.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= Ability::ALL.len() => return Ability::ALL[n - 1],
+            _ => println!("Invalid selection. Please choose 1-{}.", Ability::ALL.len()),
         }
+    }
+}
 
-        // handle Half-Elf's floating +1 bonuses
-        if let Race::HalfElf = race {
-            println!("\nHalf-Elves get +1 to two abilities of your choice (excluding Charisma)");
-            for i in 0..2 {
-                println!("\nChoose ability {} to receive +1:", i + 1);
-                println!("1. Strength");
-                println!("2. Dexterity");
-                println!("3. Constitution");
-                println!("4. Intelligence");
-                println!("5. Wisdom");
+// select one of the eighteen skills
+fn select_skill() -> Skill {
+    println!("\nSelect a skill:");
+    for (i, skill) in Skill::ALL.iter().enumerate() {
+        println!("{}. {:?}", i + 1, skill);
+    }
 
-                loop {
-                    let mut input = String::new();
-                    io::stdin().read_line(&mut input).expect("Failed to read line");
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
 
-                    match input.trim().parse::<u8>() {
-                        Ok(1) => { self.strength += 1; break; },
-                        Ok(2) => { self.dexterity += 1; break; },
-                        Ok(3) => { self.constitution += 1; break; },
-                        Ok(4) => { self.intelligence += 1; break; },
-                        Ok(5) => { self.wisdom += 1; break; },
-                        _ => println!("Invalid selection. Please choose 1-5."),
-                    }
-                }
-            }
+        match input.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= Skill::ALL.len() => return Skill::ALL[n - 1],
+            _ => println!("Invalid selection. Please choose 1-{}.", Skill::ALL.len()),
         }
     }
 }
 
-// character structure
-#[derive(Debug)]
-struct Character {
-    name: String,
-    race: Race,
-    class: Class,
-    background: Background,
-    level: u8,
-    abilities: AbilityScores,
-    hit_points: u8,
-    skills: HashSet<Skill>,
-    expertise: HashSet<Skill>,
-    saving_throws: HashSet<Ability>,
-}
-
-impl Character {
-    // display character
-    fn display(&self) {
-        println!("\n=== CHARACTER SHEET ===");
-        println!("Name: {}", self.name);
-        println!("Race: {:?}", self.race);
-        println!("Class: {:?}", self.class);
-        println!("Background: {:?}", self.background);
-        println!("Level: {}", self.level);
-
-        println!("\nAbility Scores:");
-        println!("Strength: {} ({:+})", self.abilities.strength, self.abilities.modifier(Ability::Strength));
-        println!("Dexterity: {} ({:+})", self.abilities.dexterity, self.abilities.modifier(Ability::Dexterity));
-        println!("Constitution: {} ({:+})", self.abilities.constitution, self.abilities.modifier(Ability::Constitution));
-        println!("Intelligence: {} ({:+})", self.abilities.intelligence, self.abilities.modifier(Ability::Intelligence));
-        println!("Wisdom: {} ({:+})", self.abilities.wisdom, self.abilities.modifier(Ability::Wisdom));
-        println!("Charisma: {} ({:+})", self.abilities.charisma, self.abilities.modifier(Ability::Charisma));
-
-        println!("\nHit Points: {}", self.hit_points);
-
-        println!("\nSaving Throw Proficiencies:");
-        for ability in &self.saving_throws {
-            println!("{:?} ({:+})", ability, self.abilities.modifier(*ability) + 2); // +2 for proficiency
-        }
+// select normal, advantage or disadvantage
+fn select_roll_mode() -> RollMode {
+    println!("\nRoll with:");
+    println!("1. Normal");
+    println!("2. Advantage");
+    println!("3. Disadvantage");
 
-        println!("\nSkill Proficiencies:");
-        if self.skills.is_empty() {
-            println!("None");
-        } else {
-            for skill in &self.skills {
-                println!("{:?} ({:+}){}",
-                         skill,
-                         self.skill_modifier(*skill),
-                         if self.expertise.contains(skill) { " (Expertise)" } else { "" });
-            }
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u8>() {
+            Ok(1) => return RollMode::Normal,
+            Ok(2) => return RollMode::Advantage,
+            Ok(3) => return RollMode::Disadvantage,
+            _ => println!("Invalid selection. Please choose 1-3."),
         }
     }
+}
 
-    // calculate skill modifier
-    fn skill_modifier(&self, skill: Skill) -> i8 {
-        let base = self.abilities.modifier(skill.associated_ability());
-        let proficiency = if self.skills.contains(&skill) { 2 } else { 0 };
-        let expertise = if self.expertise.contains(&skill) { 2 } else { 0 };
+// ask for advantage/disadvantage and any situational bonus
+fn select_roll_options() -> (RollMode, i8) {
+    let mode = select_roll_mode();
 
-        base + proficiency + expertise
+    println!("\nSituational bonus (e.g. 2 or -1, leave blank for none):");
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+        let input = input.trim();
+
+        if input.is_empty() {
+            return (mode, 0);
+        }
+        match input.trim_start_matches('+').parse::<i8>() {
+            Ok(bonus) => return (mode, bonus),
+            _ => println!("Invalid bonus. Please enter a whole number."),
+        }
     }
 }
 
-// roll a die
-fn roll_die(sides: u8) -> u8 {
-    rand::thread_rng().gen_range(1..=sides)
-}
+// roll a skill check, ability check or saving throw
+fn roll_d20(character: &Character) {
+    println!("\nWhat kind of roll?");
+    println!("1. Skill check");
+    println!("2. Ability check");
+    println!("3. Saving throw");
+    println!("4. Attack roll");
 
-// roll 4d6 and drop the lowest
-fn roll_ability_score() -> u8 {
-    let mut rolls = vec![
-        roll_die(6),
-        roll_die(6),
-        roll_die(6),
-        roll_die(6),
-    ];
+    let mut rng = rand::thread_rng();
+    let roll = loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u8>() {
+            Ok(1) => {
+                let skill = select_skill();
+                let (mode, bonus) = select_roll_options();
+                break character.roll_check(skill, mode, bonus, &mut rng);
+            },
+            Ok(2) => {
+                let ability = select_ability();
+                let (mode, bonus) = select_roll_options();
+                break character.roll_ability_check(ability, mode, bonus, &mut rng);
+            },
+            Ok(3) => {
+                let ability = select_ability();
+                let (mode, bonus) = select_roll_options();
+                break character.roll_save(ability, mode, bonus, &mut rng);
+            },
+            Ok(4) => {
+                let ability = select_ability();
+                println!("\nAre you proficient with this attack? (y/n)");
+                let mut input = String::new();
+                io::stdin().read_line(&mut input).expect("Failed to read line");
+                let proficient = input.trim().eq_ignore_ascii_case("y");
+                let (mode, bonus) = select_roll_options();
+                break character.roll_attack(ability, proficient, mode, bonus, &mut rng);
+            },
+            _ => println!("Invalid selection. Please choose 1-4."),
+        }
+    };
 
-    // sort and drop the lowest
-    rolls.sort();
-    rolls[1..].iter().sum()
+    println!("\n{}", roll.breakdown());
 }
 
-// ability scores
-fn generate_ability_scores() -> AbilityScores {
-    AbilityScores {
-        strength: roll_ability_score(),
-        dexterity: roll_ability_score(),
-        constitution: roll_ability_score(),
-        intelligence: roll_ability_score(),
-        wisdom: roll_ability_score(),
-        charisma: roll_ability_score(),
+// select a condition, asking for the level of exhaustion
+fn select_condition() -> Condition {
+    println!("\nSelect a condition:");
+    for (i, condition) in Condition::STANDARD.iter().enumerate() {
+        println!("{}. {:?}", i + 1, condition);
     }
-}
+    println!("{}. Exhaustion", Condition::STANDARD.len() + 1);
+
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
 
-// select a race
-fn select_race() -> Race {
-    println!("\nSelect a race:");
-    println!("1. Human");
-    println!("2. Elf");
-    println!("3. Dwarf");
-    println!("4. Halfling");
-    println!("5. Dragonborn");
-    println!("6. Gnome");
-    println!("7. Half-Elf");
-    println!("8. Half-Orc");
-    println!("9. Tiefling");
+        match input.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= Condition::STANDARD.len() => return Condition::STANDARD[n - 1],
+            Ok(n) if n == Condition::STANDARD.len() + 1 => break,
+            _ => println!("Invalid selection. Please choose 1-{}.", Condition::STANDARD.len() + 1),
+        }
+    }
 
+    println!("\nExhaustion level (1-6):");
     loop {
         let mut input = String::new();
         io::stdin().read_line(&mut input).expect("Failed to read line");
 
         match input.trim().parse::<u8>() {
-            Ok(1) => return Race::Human,
-            Ok(2) => return Race::Elf,
-            Ok(3) => return Race::Dwarf,
-            Ok(4) => return Race::Halfling,
-            Ok(5) => return Race::Dragonborn,
-            Ok(6) => return Race::Gnome,
-            Ok(7) => return Race::HalfElf,
-            Ok(8) => return Race::HalfOrc,
-            Ok(9) => return Race::Tiefling,
-            _ => println!("Invalid selection. Please choose 1-9."),
+            Ok(level @ 1..=6) => return Condition::Exhaustion(level),
+            _ => println!("Invalid level. Please choose 1-6."),
         }
     }
 }
 
-// select a class
-fn select_class() -> Class {
-    println!("\nSelect a class:");
-    println!("1. Barbarian");
-    println!("2. Bard");
-    println!("3. Cleric");
-    println!("4. Druid");
-    println!("5. Fighter");
-    println!("6. Monk");
-    println!("7. Paladin");
-    println!("8. Ranger");
-    println!("9. Rogue");
-    println!("10. Sorcerer");
-    println!("11. Warlock");
-    println!("12. Wizard");
+// apply or remove conditions and let time pass
+fn manage_conditions(character: &mut Character) {
+    println!("\nConditions:");
+    println!("1. Apply a condition");
+    println!("2. Remove a condition");
+    println!("3. Advance time (rounds)");
 
     loop {
         let mut input = String::new();
         io::stdin().read_line(&mut input).expect("Failed to read line");
 
         match input.trim().parse::<u8>() {
-            Ok(1) => return Class::Barbarian,
-            Ok(2) => return Class::Bard,
-            Ok(3) => return Class::Cleric,
-            Ok(4) => return Class::Druid,
-            Ok(5) => return Class::Fighter,
-            Ok(6) => return Class::Monk,
-            Ok(7) => return Class::Paladin,
-            Ok(8) => return Class::Ranger,
-            Ok(9) => return Class::Rogue,
-            Ok(10) => return Class::Sorcerer,
-            Ok(11) => return Class::Warlock,
-            Ok(12) => return Class::Wizard,
-            _ => println!("Invalid selection. Please choose 1-12."),
+            Ok(1) => {
+                let condition = select_condition();
+
+                println!("\nDuration in rounds (10 rounds = 1 minute, leave blank until removed):");
+                let duration = loop {
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input).expect("Failed to read line");
+                    let input = input.trim();
+
+                    if input.is_empty() {
+                        break ConditionDuration::UntilRemoved;
+                    }
+                    match input.parse::<u32>() {
+                        Ok(rounds) if rounds > 0 => break ConditionDuration::Rounds(rounds),
+                        _ => println!("Invalid duration. Please enter a number of rounds."),
+                    }
+                };
+
+                character.apply_condition(condition, duration);
+                println!("{:?} applied.", condition);
+                return;
+            },
+            Ok(2) => {
+                let condition = select_condition();
+                if character.remove_condition(condition) {
+                    println!("{:?} removed.", condition);
+                } else {
+                    println!("{:?} wasn't applied.", condition);
+                }
+                return;
+            },
+            Ok(3) => {
+                let rounds = read_amount("How many rounds pass?");
+                for condition in character.advance_rounds(rounds as u32) {
+                    println!("{:?} has worn off.", condition);
+                }
+                return;
+            },
+            _ => println!("Invalid selection. Please choose 1-3."),
         }
     }
 }
 
-// select a background
-fn select_background() -> Background {
-    println!("\nSelect a background:");
-    println!("1. Acolyte");
-    println!("2. Charlatan");
-    println!("3. Criminal");
-    println!("4. Entertainer");
-    println!("5. Folk Hero");
-    println!("6. Guild Artisan");
-    println!("7. Hermit");
-    println!("8. Noble");
-    println!("9. Outlander");
-    println!("10. Sage");
-    println!("11. Sailor");
-    println!("12. Soldier");
-    println!("13. Urchin");
+// choose between XP and milestone levelling
+fn select_advancement_mode() -> AdvancementMode {
+    println!("\nHow does your campaign level up?");
+    println!("1. Experience points");
+    println!("2. Milestones");
 
     loop {
         let mut input = String::new();
         io::stdin().read_line(&mut input).expect("Failed to read line");
 
         match input.trim().parse::<u8>() {
-            Ok(1) => return Background::Acolyte,
-            Ok(2) => return Background::Charlatan,
-            Ok(3) => return Background::Criminal,
-            Ok(4) => return Background::Entertainer,
-            Ok(5) => return Background::FolkHero,
-            Ok(6) => return Background::GuildArtisan,
-            Ok(7) => return Background::Hermit,
-            Ok(8) => return Background::Noble,
-            Ok(9) => return Background::Outlander,
-            Ok(10) => return Background::Sage,
-            Ok(11) => return Background::Sailor,
-            Ok(12) => return Background::Soldier,
-            Ok(13) => return Background::Urchin,
-            _ => println!("Invalid selection. Please choose 1-13."),
+            Ok(1) => return AdvancementMode::Experience,
+            Ok(2) => return AdvancementMode::Milestone,
+            _ => println!("Invalid selection. Please choose 1-2."),
         }
     }
 }
 
-// get class skills based on the selected class
-fn get_class_skills(class: &Class) -> Vec<Skill> {
-    match class {
-        Class::Barbarian => vec![
-            Skill::AnimalHandling,
-            Skill::Athletics,
-            Skill::Intimidation,
-            Skill::Nature,
-            Skill::Perception,
-            Skill::Survival,
-        ],
-        Class::Bard => vec![
-            Skill::Acrobatics,
-            Skill::AnimalHandling,
-            Skill::Arcana,
-            Skill::Athletics,
-            Skill::Deception,
-            Skill::History,
-            Skill::Insight,
-            Skill::Intimidation,
-            Skill::Investigation,
-            Skill::Medicine,
-            Skill::Nature,
-            Skill::Perception,
-            Skill::Performance,
-            Skill::Persuasion,
-            Skill::Religion,
-            Skill::SleightOfHand,
-            Skill::Stealth,
-            Skill::Survival,
-        ],
-        Class::Cleric => vec![
-            Skill::History,
-            Skill::Insight,
-            Skill::Medicine,
-            Skill::Persuasion,
-            Skill::Religion,
-        ],
-        Class::Druid => vec![
-            Skill::Arcana,
-            Skill::AnimalHandling,
-            Skill::Insight,
-            Skill::Medicine,
-            Skill::Nature,
-            Skill::Perception,
-            Skill::Religion,
-            Skill::Survival,
-        ],
-        Class::Fighter => vec![
-            Skill::Acrobatics,
-            Skill::AnimalHandling,
-            Skill::Athletics,
-            Skill::History,
-            Skill::Insight,
-            Skill::Intimidation,
-            Skill::Perception,
-            Skill::Survival,
-        ],
-        Class::Monk => vec![
-            Skill::Acrobatics,
-            Skill::Athletics,
-            Skill::History,
-            Skill::Insight,
-            Skill::Religion,
-            Skill::Stealth,
-        ],
-        Class::Paladin => vec![
-            Skill::Athletics,
-            Skill::Insight,
-            Skill::Intimidation,
-            Skill::Medicine,
-            Skill::Persuasion,
-            Skill::Religion,
-        ],
-        Class::Ranger => vec![
-            Skill::AnimalHandling,
-            Skill::Athletics,
-            Skill::Insight,
-            Skill::Investigation,
-            Skill::Nature,
-            Skill::Perception,
-            Skill::Stealth,
-            Skill::Survival,
-        ],
-        Class::Rogue => vec![
-            Skill::Acrobatics,
-            Skill::Athletics,
-            Skill::Deception,
-            Skill::Insight,
-            Skill::Intimidation,
-            Skill::Investigation,
-            Skill::Perception,
-            Skill::Performance,
-            Skill::Persuasion,
-            Skill::SleightOfHand,
-            Skill::Stealth,
-        ],
-        Class::Sorcerer => vec![
-            Skill::Arcana,
-            Skill::Deception,
-            Skill::Insight,
-            Skill::Intimidation,
-            Skill::Persuasion,
-            Skill::Religion,
-        ],
-        Class::Warlock => vec![
-            Skill::Arcana,
-            Skill::Deception,
-            Skill::History,
-            Skill::Intimidation,
-            Skill::Investigation,
-            Skill::Nature,
-            Skill::Religion,
-        ],
-        Class::Wizard => vec![
-            Skill::Arcana,
-            Skill::History,
-            Skill::Insight,
-            Skill::Investigation,
-            Skill::Medicine,
-            Skill::Religion,
-        ],
-    }
-}
-
-// get the number of skill proficiencies based on class
-fn get_skill_proficiency_count(class: &Class) -> usize {
-    match class {
-        Class::Barbarian => 2,
-        Class::Bard => 3,
-        Class::Cleric => 2,
-        Class::Druid => 2,
-        Class::Fighter => 2,
-        Class::Monk => 2,
-        Class::Paladin => 2,
-        Class::Ranger => 3,
-        Class::Rogue => 4,
-        Class::Sorcerer => 2,
-        Class::Warlock => 2,
-        Class::Wizard => 2,
-    }
-}
-
-// select skills from the available class skills
-fn select_skills(class: &Class, background: Background, race: Race) -> HashSet<Skill> {
-    let mut selected_skills = HashSet::new();
-
-    // add background skills
-    let [bg_skill1, bg_skill2] = background.skills();
-    selected_skills.insert(bg_skill1);
-    selected_skills.insert(bg_skill2);
-
-    // add racial skills
-    for skill in race.racial_skills() {
-        selected_skills.insert(skill);
-    }
-
-    // get class skills and count
-    let class_skills = get_class_skills(class);
-    let skill_count = get_skill_proficiency_count(class);
-
-    // if we already have enough skills from background / race, return
-    if selected_skills.len() >= skill_count {
-        return selected_skills;
-    }
-
-    println!("\nSelect {} additional skill proficiencies from:", skill_count - selected_skills.len());
-
-    // display available skills with numbers
-    let mut available_skills = Vec::new();
-    for skill in class_skills {
-        if !selected_skills.contains(&skill) {
-            available_skills.push(skill);
+// ability score improvement: +2 to one ability, +1 to two, or a feat
+// returns "ASI" or "feat" for the history
+fn select_ability_score_improvement(character: &mut Character) -> &'static str {
+    println!("\nAbility Score Improvement:");
+    println!("1. +2 to one ability");
+    println!("2. +1 to two abilities");
+    println!("3. Take a feat instead");
+
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u8>() {
+            Ok(1) => {
+                let ability = select_ability();
+                let increase = character.improve_ability(ability, 2);
+                println!("{:?} +{}", ability, increase);
+                return "ASI";
+            },
+            Ok(2) => {
+                let first = select_ability();
+                let second = loop {
+                    let ability = select_ability();
+                    if ability != first {
+                        break ability;
+                    }
+                    println!("Please choose a different ability.");
+                };
+                for ability in [first, second] {
+                    let increase = character.improve_ability(ability, 1);
+                    println!("{:?} +{}", ability, increase);
+                }
+                return "ASI";
+            },
+            Ok(3) => {
+                let feats: Vec<Feat> = [Feat::Alert, Feat::Mobile, Feat::Observant]
+                    .into_iter()
+                    .filter(|feat| !character.feats.contains(feat))
+                    .collect();
+                if feats.is_empty() {
+                    println!("You already have every feat. Choose an ability increase instead.");
+                    continue;
+                }
+
+                println!("\nSelect a feat:");
+                for (i, feat) in feats.iter().enumerate() {
+                    println!("{}. {:?}", i + 1, feat);
+                }
+                loop {
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input).expect("Failed to read line");
+
+                    match input.trim().parse::<usize>() {
+                        Ok(n) if n >= 1 && n <= feats.len() => {
+                            character.feats.push(feats[n - 1]);
+                            println!("Feat added.");
+                            return "feat";
+                        },
+                        _ => println!("Invalid selection. Please choose 1-{}.", feats.len()),
+                    }
+                }
+            },
+            _ => println!("Invalid selection. Please choose 1-3."),
         }
     }
+}
 
+// pick new expertise from proficient skills that don't have it yet
+fn select_new_expertise(character: &mut Character, count: u8) {
+    let available_skills: Vec<Skill> = Skill::ALL
+        .iter()
+        .copied()
+        .filter(|s| character.skills.contains(s) && !character.expertise.contains(s))
+        .collect();
+    let count = (count as usize).min(available_skills.len());
+
+    println!("\nSelect {} skill(s) for Expertise:", count);
     for (i, skill) in available_skills.iter().enumerate() {
         println!("{}. {:?}", i + 1, skill);
     }
 
-    while selected_skills.len() < skill_count {
-        println!("\nChoose skill {} (1-{}):", selected_skills.len() + 1, available_skills.len());
+    let mut chosen = 0;
+    while chosen < count {
+        println!("\nChoose expertise skill {} (1-{}):", chosen + 1, available_skills.len());
 
         let mut input = String::new();
         io::stdin().read_line(&mut input).expect("Failed to read line");
 
         match input.trim().parse::<usize>() {
             Ok(n) if n >= 1 && n <= available_skills.len() => {
-                let skill = available_skills[n - 1];
-                selected_skills.insert(skill);
-                println!("Skill added.");
+                if character.expertise.insert(available_skills[n - 1]) {
+                    chosen += 1;
+                    println!("Expertise added.");
+                } else {
+                    println!("You already chose that skill.");
+                }
             },
             _ => println!("Invalid selection. Please choose a number between 1 and {}.", available_skills.len()),
         }
     }
+}
+
+// gain a level and make its choices
+fn level_up(character: &mut Character) {
+    println!("\nHit points for the new level:");
+    println!("1. Take the average");
+    println!("2. Roll the hit die");
+
+    let increase = loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u8>() {
+            Ok(1) => break HitPointIncrease::Average,
+            Ok(2) => break HitPointIncrease::Roll,
+            _ => println!("Invalid selection. Please choose 1-2."),
+        }
+    };
+
+    let before = character.clone();
+    let summary = match character.level_up(increase, &mut rand::thread_rng()) {
+        Some(summary) => summary,
+        None => {
+            println!("\n{} is already at the maximum level.", character.name);
+            return;
+        },
+    };
+
+    println!("\n{} is now level {}! Gained {} hit points.", character.name, summary.level, summary.hit_points_gained);
 
-    selected_skills
+    let mut reason = "level up";
+    if summary.ability_score_improvement {
+        reason = select_ability_score_improvement(character);
+    }
+    if summary.new_expertise > 0 {
+        select_new_expertise(character, summary.new_expertise);
+    }
+    character.record_changes(&before, reason);
 }
 
-// select expertise skills (for Rogues and Bards)
-fn select_expertise(class: &Class, skills: &HashSet<Skill>) -> HashSet<Skill> {
-    let expertise_count = match class {
-        Class::Rogue => 2,
-        Class::Bard => 2,
-        _ => return HashSet::new(),
+// award XP and offer any level-ups it earns
+fn award_xp(character: &mut Character) {
+    let amount = loop {
+        println!("\nHow much XP?");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u32>() {
+            Ok(n) => break n,
+            _ => println!("Invalid amount. Please enter a whole number."),
+        }
     };
 
-    if expertise_count == 0 {
-        return HashSet::new();
+    let pending = character.award_xp(amount);
+    println!("{} now has {} XP.", character.name, character.experience);
+
+    for _ in 0..pending {
+        println!("\n{} has enough XP to reach level {}! Level up now? (y/n)", character.name, character.level + 1);
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("You can level up later from the experience menu.");
+            return;
+        }
+
+        level_up(character);
     }
+}
 
-    let mut expertise = HashSet::new();
+// XP, levelling and the campaign's advancement mode
+fn manage_advancement(character: &mut Character) {
+    println!("\nExperience ({:?} advancement):", character.advancement);
+    println!("1. Award XP");
+    println!("2. Level up");
+    println!("3. Switch advancement mode");
 
-    println!("\nSelect {} skill(s) for Expertise:", expertise_count);
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
 
-    // display available skills with numbers
-    let available_skills: Vec<Skill> = skills.iter().copied().collect();
-    for (i, skill) in available_skills.iter().enumerate() {
-        println!("{}. {:?}", i + 1, skill);
+        match input.trim().parse::<u8>() {
+            Ok(1) => return award_xp(character),
+            Ok(2) => {
+                // with XP the level-up has to be earned, with milestones the DM decides
+                if character.advancement == AdvancementMode::Experience && character.pending_level_ups() == 0 {
+                    match character.next_level_xp() {
+                        Some(next) => println!("\nNot enough XP yet ({}/{}).", character.experience, next),
+                        None => println!("\n{} is already at the maximum level.", character.name),
+                    }
+                } else {
+                    level_up(character);
+                }
+                return;
+            },
+            Ok(3) => {
+                let before = character.clone();
+                character.advancement = select_advancement_mode();
+                character.record_changes(&before, "advancement mode changed");
+                return;
+            },
+            _ => println!("Invalid selection. Please choose 1-3."),
+        }
     }
+}
 
-    while expertise.len() < expertise_count {
-        println!("\nChoose expertise skill {} (1-{}):", expertise.len() + 1, available_skills.len());
+// play-session commands for a created or loaded character
+fn run_session(character: &mut Character, mut path: Option<String>) {
+    loop {
+        println!("\nWhat would you like to do?");
+        println!("1. Show character sheet");
+        println!("2. Take damage");
+        println!("3. Heal");
+        println!("4. Roll a death saving throw");
+        println!("5. Roll a check or saving throw");
+        println!("6. Conditions");
+        println!("7. Experience and levelling");
+        println!("8. Short rest");
+        println!("9. Long rest");
+        println!("10. Save");
+        println!("11. Quit");
 
         let mut input = String::new();
         io::stdin().read_line(&mut input).expect("Failed to read line");
 
-        match input.trim().parse::<usize>() {
-            Ok(n) if n >= 1 && n <= available_skills.len() => {
-                let skill = available_skills[n - 1];
-                expertise.insert(skill);
-                println!("Expertise added.");
+        match input.trim().parse::<u8>() {
+            Ok(1) => character.display(),
+            Ok(2) => {
+                let amount = read_amount("How much damage?");
+                let damage_type = select_damage_type();
+                let taken = character.take_damage(amount, damage_type);
+                println!("\nTook {} {:?} damage.", taken, damage_type);
+                print_hit_points(character);
             },
-            _ => println!("Invalid selection. Please choose a number between 1 and {}.", available_skills.len()),
+            Ok(3) => {
+                let amount = read_amount("How many hit points are regained?");
+                let healed = character.heal(amount);
+                println!("\nRegained {} HP.", healed);
+                print_hit_points(character);
+            },
+            Ok(4) => {
+                let (roll, result) = character.roll_death_save(&mut rand::thread_rng());
+                match result {
+                    DeathSaveResult::NotDying => println!("\nYou are not dying."),
+                    _ => println!("\nRolled {}: {:?}", roll, result),
+                }
+                print_hit_points(character);
+            },
+            Ok(5) => roll_d20(character),
+            Ok(6) => manage_conditions(character),
+            Ok(7) => manage_advancement(character),
+            Ok(8) => short_rest(character),
+            Ok(9) => {
+                let summary = character.long_rest();
+                println!(
+                    "\nLong rest finished. Regained {} HP and {} hit dice.",
+                    summary.hit_points_regained, summary.hit_dice_regained
+                );
+            },
+            Ok(10) => match &path {
+                Some(p) => match save_character(character, p) {
+                    Ok(()) => println!("Character saved to {}", p),
+                    Err(e) => println!("Failed to save character: {}", e),
+                },
+                None => path = prompt_save(character),
+            },
+            Ok(11) => return,
+            _ => println!("Invalid selection. Please choose 1-11."),
         }
     }
+}
 
-    expertise
+// exit codes shared by every subcommand, clap itself exits with 2 on bad arguments
+const EXIT_FAILURE: i32 = 1;
+
+const EXIT_CODES: &str = "Exit codes:
+  0  success
+  1  the command failed (unreadable or invalid file, nothing to do, failed to save)
+  2  invalid command-line arguments";
+
+#[derive(Parser)]
+#[command(name = "dnd_character", version, about = "D&D 5e character creator", after_help = EXIT_CODES)]
+struct Cli {
+    // without a subcommand the interactive menu runs
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Load extra races, classes and backgrounds from the TOML files in DIR (can be repeated)
+    #[arg(long, value_name = "DIR", global = true)]
+    content_dir: Vec<PathBuf>,
 }
 
-fn main() {
+#[derive(Subcommand)]
+enum Command {
+    /// Create a character with the interactive creator, or a random one with --random
+    New(NewArgs),
+    /// Print the character sheet of a saved character
+    Show { file: PathBuf },
+    /// Level up a saved character, prompting for any choices the new level brings
+    LevelUp {
+        file: PathBuf,
+        /// Roll the hit die instead of taking the average
+        #[arg(long)]
+        roll: bool,
+    },
+    /// Roll a dice expression such as 2d6+3, d20 or 4d6kh3
+    Roll {
+        #[arg(allow_hyphen_values = true)]
+        expression: DiceExpression,
+    },
+    /// Take a short or long rest
+    #[command(group(ArgGroup::new("kind").required(true).args(["short", "long"])))]
+    Rest {
+        file: PathBuf,
+        /// Spend hit dice and recover short rest resources
+        #[arg(long)]
+        short: bool,
+        /// Recover hit points, hit dice, spell slots and resources
+        #[arg(long)]
+        long: bool,
+        /// Hit dice to spend during a short rest
+        #[arg(long, default_value_t = 0, requires = "short")]
+        hit_dice: u8,
+    },
+    /// Export a saved character to another format
+    Export {
+        file: PathBuf,
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+        /// Write to this file instead of standard output
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Manage a party roster: a file listing the save files of several characters
+    Party {
+        /// The roster file, created by the first add
+        roster: PathBuf,
+        #[command(subcommand)]
+        action: PartyCommand,
+    },
+    /// Import a character from a virtual tabletop export
+    Import {
+        file: PathBuf,
+        #[arg(long, value_enum)]
+        format: TabletopFormat,
+        /// Save the imported character to this file
+        #[arg(long)]
+        save: PathBuf,
+    },
+    /// Compare two save files field by field, such as before and after a level-up
+    Diff {
+        before: PathBuf,
+        after: PathBuf,
+        /// Print the changes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the changes recorded in a save file, or look at or go back to an earlier version
+    #[command(group(ArgGroup::new("action").args(["show", "revert", "record"])))]
+    History {
+        file: PathBuf,
+        /// Print the character sheet as it was in this version
+        #[arg(long, value_name = "VERSION")]
+        show: Option<usize>,
+        /// Go back to this version; later versions stay in the history
+        #[arg(long, value_name = "VERSION")]
+        revert: Option<usize>,
+        /// Record changes made since the last version, such as edits to the save file by hand
+        #[arg(long)]
+        record: bool,
+        /// Why the character is being reverted or changed
+        #[arg(long, requires = "action", conflicts_with = "show")]
+        reason: Option<String>,
+    },
+    /// Check that a save file can be loaded and follows the rules
+    Validate { file: PathBuf },
+    /// Check content packs for mistakes before loading or sharing them
+    ValidateContent {
+        /// Pack directories to check along with the built-in content and any --content-dir
+        dirs: Vec<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PartyCommand {
+    /// List the members of the party
+    List,
+    /// Add saved characters to the party
+    Add {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Remove a member by character name or save file
+    Remove { name: String },
+    /// Print the character sheet of a member
+    Show { name: String },
+    /// Levels, hit points, AC, passive Perception, languages and who is best at each skill
+    Summary,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Build the whole character at random without prompts
+    #[arg(long)]
+    random: bool,
+    /// Use the full-screen builder instead of the numbered prompts
+    #[arg(long, conflicts_with = "random")]
+    tui: bool,
+    /// Answer the creator's prompts from a JSON array of strings instead of the terminal
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["random", "tui"])]
+    answers: Option<PathBuf>,
+    // parsed after the content packs are loaded, so homebrew ids work too
+    #[arg(long, requires = "random")]
+    race: Option<String>,
+    #[arg(long, requires = "random")]
+    class: Option<String>,
+    #[arg(long, requires = "random")]
+    background: Option<String>,
+    #[arg(long, requires = "random", value_parser = clap::value_parser!(u8).range(1..=20))]
+    level: Option<u8>,
+    #[arg(long, requires = "random")]
+    name: Option<String>,
+    /// Seed for a reproducible random character
+    #[arg(long, requires = "random")]
+    seed: Option<u64>,
+    /// Save the new character to this file
+    #[arg(long)]
+    save: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    #[value(alias = "md")]
+    Markdown,
+    Html,
+    Pdf,
+    /// A Foundry VTT dnd5e actor
+    Foundry,
+    /// The tool-neutral format described by data/vtt/generic-character.schema.json
+    Generic,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TabletopFormat {
+    Foundry,
+    Generic,
+}
+
+impl From<TabletopFormat> for VttFormat {
+    fn from(format: TabletopFormat) -> VttFormat {
+        match format {
+            TabletopFormat::Foundry => VttFormat::Foundry,
+            TabletopFormat::Generic => VttFormat::Generic,
+        }
+    }
+}
+
+fn load(file: &Path) -> Result<Character, String> {
+    let character = load_character(file).map_err(|e| format!("Failed to load {}: {}", file.display(), e))?;
+    for violation in character.validate() {
+        eprintln!("{}: {}", file.display(), violation);
+    }
+    Ok(character)
+}
+
+fn save(character: &Character, file: &Path) -> Result<(), String> {
+    save_character(character, file).map_err(|e| format!("Failed to save {}: {}", file.display(), e))
+}
+
+fn new_character(args: NewArgs) -> Result<(), String> {
+    let mut character = if args.random {
+        let options = RandomOptions {
+            name: args.name,
+            race: args.race.as_deref().map(str::parse).transpose()?,
+            subrace: None,
+            class: args.class.as_deref().map(str::parse).transpose()?,
+            background: args.background.as_deref().map(str::parse).transpose()?,
+            level: args.level,
+        };
+        let mut rng = match args.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        random_character(&options, &mut rng)
+    } else if args.tui {
+        match tui::build_character().map_err(|e| format!("Terminal error: {}", e))? {
+            Some(character) => character,
+            None => {
+                println!("Character creation cancelled.");
+                return Ok(());
+            },
+        }
+    } else if let Some(answers) = &args.answers {
+        let mut input =
+            JsonInput::from_file(answers).map_err(|e| format!("Failed to read {}: {}", answers.display(), e))?;
+        creator::create_character(&mut input, &mut rand::thread_rng())
+            .ok_or_else(|| format!("{} ran out of answers before the character was finished.", answers.display()))?
+    } else {
+        creator::create_character(&mut StdinInput, &mut rand::thread_rng()).ok_or("Character creation cancelled.")?
+    };
+    character.record_created("new character");
+    character.display();
+
+    match args.save {
+        Some(file) => {
+            save(&character, &file)?;
+            println!("\nCharacter saved to {}", file.display());
+        },
+        None if !args.random && args.answers.is_none() => {
+            prompt_save(&character);
+        },
+        None => {},
+    }
+    Ok(())
+}
+
+fn level_up_file(file: &Path, roll: bool) -> Result<(), String> {
+    let mut character = load(file)?;
+
+    if character.advancement == AdvancementMode::Experience && character.pending_level_ups() == 0 {
+        return Err(match character.next_level_xp() {
+            Some(next) => {
+                format!("{} doesn't have enough XP to level up ({}/{}).", character.name, character.experience, next)
+            },
+            None => format!("{} is already at the maximum level.", character.name),
+        });
+    }
+    if character.level >= MAX_LEVEL {
+        return Err(format!("{} is already at the maximum level.", character.name));
+    }
+
+    let before = character.clone();
+    let increase = if roll { HitPointIncrease::Roll } else { HitPointIncrease::Average };
+    let summary = character.level_up(increase, &mut rand::thread_rng()).expect("checked against the maximum level");
+    println!("{} is now level {}! Gained {} hit points.", character.name, summary.level, summary.hit_points_gained);
+
+    let mut reason = "level up";
+    if summary.ability_score_improvement {
+        reason = select_ability_score_improvement(&mut character);
+    }
+    if summary.new_expertise > 0 {
+        select_new_expertise(&mut character, summary.new_expertise);
+    }
+    character.record_changes(&before, reason);
+
+    save(&character, file)
+}
+
+fn rest_file(file: &Path, short: bool, hit_dice: u8) -> Result<(), String> {
+    let mut character = load(file)?;
+
+    if short {
+        let summary = character.short_rest(hit_dice, &mut rand::thread_rng());
+        println!(
+            "Short rest finished. Rolled {:?}, regained {} HP.",
+            summary.hit_dice_rolls, summary.hit_points_regained
+        );
+    } else {
+        if character.hit_points.current == 0 {
+            return Err(format!("{} needs at least 1 hit point to benefit from a long rest.", character.name));
+        }
+        let summary = character.long_rest();
+        println!(
+            "Long rest finished. Regained {} HP and {} hit dice.",
+            summary.hit_points_regained, summary.hit_dice_regained
+        );
+    }
+    print_hit_points(&character);
+
+    save(&character, file)
+}
+
+fn export_file(file: &Path, format: ExportFormat, output: Option<PathBuf>) -> Result<(), String> {
+    let character = load(file)?;
+
+    let text = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&character).map_err(|e| e.to_string())? + "\n",
+        ExportFormat::Markdown => character.export(&Markdown),
+        ExportFormat::Html => character.export(&Html),
+        ExportFormat::Foundry => tabletop_json(&character, VttFormat::Foundry)?,
+        ExportFormat::Generic => tabletop_json(&character, VttFormat::Generic)?,
+        ExportFormat::Pdf => {
+            let pdf = character.export_pdf().map_err(|e| format!("Failed to build the PDF: {}", e))?;
+            return write_output(output, &pdf);
+        },
+    };
+    write_output(output, text.as_bytes())
+}
+
+// a tabletop document, warning about whatever it can't hold
+fn tabletop_json(character: &Character, format: VttFormat) -> Result<String, String> {
+    let conversion = character.to_vtt(format);
+    for field in &conversion.unsupported {
+        eprintln!("warning: not exported: {}", field);
+    }
+    Ok(serde_json::to_string_pretty(&conversion.value).map_err(|e| e.to_string())? + "\n")
+}
+
+// the output file, or standard output when there isn't one
+fn write_output(output: Option<PathBuf>, bytes: &[u8]) -> Result<(), String> {
+    match output {
+        Some(path) => fs::write(&path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e)),
+        None => io::stdout().write_all(bytes).map_err(|e| format!("Failed to write output: {}", e)),
+    }
+}
+
+fn party(roster: &Path, action: PartyCommand) -> Result<(), String> {
+    let mut party = Party::load(roster).map_err(|e| format!("Failed to load {}: {}", roster.display(), e))?;
+    let save_party =
+        |party: &Party| party.save(roster).map_err(|e| format!("Failed to save {}: {}", roster.display(), e));
+
+    match action {
+        PartyCommand::List => {
+            if party.members.is_empty() {
+                println!("The party is empty.");
+            }
+            for (member, character) in party.characters(roster) {
+                match character {
+                    Ok(character) => println!(
+                        "{} - level {} {} {} ({})",
+                        character.name,
+                        character.level,
+                        character.race,
+                        character.class,
+                        member.display()
+                    ),
+                    Err(e) => println!("{} - failed to load: {}", member.display(), e),
+                }
+            }
+            Ok(())
+        },
+        PartyCommand::Add { files } => {
+            for file in files {
+                let character = load(&file)?;
+                if party.add(roster, &file).map_err(|e| format!("Failed to add {}: {}", file.display(), e))? {
+                    println!("Added {} to the party.", character.name);
+                } else {
+                    println!("{} is already in the party.", character.name);
+                }
+            }
+            save_party(&party)
+        },
+        PartyCommand::Remove { name } => match party.remove(roster, &name) {
+            Some(member) => {
+                println!("Removed {} from the party.", member.display());
+                save_party(&party)
+            },
+            None => Err(format!("No party member called {}.", name)),
+        },
+        PartyCommand::Show { name } => match party.find(roster, &name) {
+            Some((_, character)) => {
+                character.display();
+                Ok(())
+            },
+            None => Err(format!("No party member called {}.", name)),
+        },
+        PartyCommand::Summary => {
+            let mut characters = Vec::new();
+            for (member, character) in party.characters(roster) {
+                match character {
+                    Ok(character) => characters.push(character),
+                    Err(e) => eprintln!("Skipping {}: {}", member.display(), e),
+                }
+            }
+            PartySummary::new(&characters).display();
+            Ok(())
+        },
+    }
+}
+
+fn import_file(file: &Path, format: TabletopFormat, save_to: &Path) -> Result<(), String> {
+    let text = fs::read_to_string(file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let json = serde_json::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", file.display(), e))?;
+    let conversion =
+        Character::from_vtt(&json, format.into()).map_err(|e| format!("Failed to import {}: {}", file.display(), e))?;
+    for field in &conversion.unsupported {
+        eprintln!("warning: not imported: {}", field);
+    }
+
+    let mut character = conversion.value;
+    character.record_created(format!("imported from {}", file.display()));
+    for violation in character.validate() {
+        eprintln!("{}", violation);
+    }
+    save(&character, save_to)?;
+    println!(
+        "Imported {} (level {} {} {}) to {}.",
+        character.name,
+        character.level,
+        character.race,
+        character.class,
+        save_to.display()
+    );
+    Ok(())
+}
+
+fn diff_files(before: &Path, after: &Path, json: bool) -> Result<(), String> {
+    let diff = load(before)?.diff(&load(after)?);
+    if json {
+        let json = serde_json::to_string_pretty(&diff).map_err(|e| format!("Failed to write JSON: {}", e))?;
+        println!("{}", json);
+    } else {
+        diff.display();
+    }
+    Ok(())
+}
+
+fn history_file(
+    file: &Path,
+    show: Option<usize>,
+    revert: Option<usize>,
+    record: bool,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let mut character = load(file)?;
+
+    if let Some(version) = show {
+        let snapshot =
+            character.version(version).ok_or_else(|| format!("{} has no version {}.", character.name, version))?;
+        println!("Version {} of {}: {}", version, character.name, character.history[version - 1]);
+        snapshot.display();
+    } else if let Some(version) = revert {
+        character.revert(version, reason.unwrap_or_else(|| "reverted".to_string()))?;
+        save(&character, file)?;
+        println!("{} is back to version {}, recorded as version {}.", character.name, version, character.history.len());
+    } else if record {
+        if !character.record_edits(reason.unwrap_or_else(|| "edited by hand".to_string())) {
+            println!("Nothing has changed since version {}.", character.history.len());
+            return Ok(());
+        }
+        save(&character, file)?;
+        println!("Recorded version {}: {}", character.history.len(), character.history[character.history.len() - 1]);
+    } else {
+        character.display_history();
+    }
+    Ok(())
+}
+
+fn validate_file(file: &Path) -> Result<(), String> {
+    let character = load_character(file).map_err(|e| format!("Failed to load {}: {}", file.display(), e))?;
+    println!(
+        "{}: {} (level {} {} {}) loaded successfully.",
+        file.display(),
+        character.name,
+        character.level,
+        character.race,
+        character.class
+    );
+
+    let violations = character.validate();
+    for violation in &violations {
+        println!("{}", violation);
+    }
+    let errors = violations.iter().filter(|v| v.severity == Severity::Error).count();
+    if errors > 0 {
+        return Err(format!("{} error(s) found.", errors));
+    }
+    Ok(())
+}
+
+fn validate_content(dirs: &[PathBuf]) -> Result<(), String> {
+    let problems = check_packs(dirs).map_err(|e| format!("Failed to read content: {}", e))?;
+    if problems.is_empty() {
+        println!("No problems found.");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    Err(format!("{} problem(s) found.", problems.len()))
+}
+
+// the original menu: create or load a character, then play it
+fn interactive() {
     println!("D&D 5e Character Creator");
 
-    // get character name
-    println!("\nEnter your character's name:");
-    let mut name = String::new();
-    io::stdin().read_line(&mut name).expect("Failed to read line");
-    let name = name.trim().to_string();
-
-    // select race, class, and background
-    let race = select_race();
-    let class = select_class();
-    let background = select_background();
-
-    // generate ability scores and apply racial bonuses
-    println!("\nRolling ability scores (4d6 drop lowest)...");
-    let mut abilities = generate_ability_scores();
-    abilities.apply_racial_bonuses(race);
-
-    // calculate hit points
-    let hit_points = class.hit_die() + ((abilities.constitution as i8 - 10) / 2) as u8;
-
-    // select skills
-    let skills = select_skills(&class, background, race);
-
-    // select expertise if applicable
-    let expertise = select_expertise(&class, &skills);
-
-    // get saving throw proficiencies
-    let (saving_throw1, saving_throw2) = class.saving_throws();
-    let mut saving_throws = HashSet::new();
-    saving_throws.insert(saving_throw1);
-    saving_throws.insert(saving_throw2);
-
-    // create level 1 character
-    let character = Character {
-        name,
-        race,
-        class,
-        background,
-        level: 1,
-        abilities,
-        hit_points,
-        skills,
-        expertise,
-        saving_throws,
+    println!("\n1. Create a new character");
+    println!("2. Load a saved character");
+
+    let (mut character, path) = loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.trim().parse::<u8>() {
+            Ok(1) => {
+                let mut character = match creator::create_character(&mut StdinInput, &mut rand::thread_rng()) {
+                    Some(character) => character,
+                    None => return,
+                };
+                character.record_created("new character");
+
+                // display character sheet
+                character.display();
+
+                let path = prompt_save(&character);
+                break (character, path);
+            },
+            Ok(2) => {
+                if let Some((character, path)) = prompt_load() {
+                    character.display();
+                    break (character, Some(path));
+                }
+                println!("\n1. Create a new character");
+                println!("2. Load a saved character");
+            },
+            _ => println!("Invalid selection. Please choose 1-2."),
+        }
     };
 
-    // display character sheet
-    character.display();
-}
\ No newline at end of file
+    run_session(&mut character, path);
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    // a broken pack would stop the checker before it could report on it
+    if let Some(Command::ValidateContent { dirs }) = &cli.command {
+        let dirs: Vec<PathBuf> = cli.content_dir.iter().chain(dirs).cloned().collect();
+        if let Err(e) = validate_content(&dirs) {
+            eprintln!("{}", e);
+            process::exit(EXIT_FAILURE);
+        }
+        return;
+    }
+
+    if let Err(e) = load_content(&cli.content_dir) {
+        eprintln!("Failed to load content: {}", e);
+        process::exit(EXIT_FAILURE);
+    }
+
+    let result = match cli.command {
+        None => {
+            interactive();
+            Ok(())
+        },
+        Some(Command::New(args)) => new_character(args),
+        Some(Command::Show { file }) => load(&file).map(|character| character.display()),
+        Some(Command::LevelUp { file, roll }) => level_up_file(&file, roll),
+        Some(Command::Roll { expression }) => {
+            println!("{}", expression.roll(&mut rand::thread_rng()).breakdown());
+            Ok(())
+        },
+        Some(Command::Rest { file, short, hit_dice, .. }) => rest_file(&file, short, hit_dice),
+        Some(Command::Export { file, format, output }) => export_file(&file, format, output),
+        Some(Command::Party { roster, action }) => party(&roster, action),
+        Some(Command::Import { file, format, save }) => import_file(&file, format, &save),
+        Some(Command::Diff { before, after, json }) => diff_files(&before, &after, json),
+        Some(Command::History { file, show, revert, record, reason }) => {
+            history_file(&file, show, revert, record, reason)
+        },
+        Some(Command::Validate { file }) => validate_file(&file),
+        Some(Command::ValidateContent { .. }) => unreachable!("handled before loading content"),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILURE);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::race::Race;
+
+// letters of context the model looks back on
+const ORDER: usize = 2;
+
+// generated names shorter or longer than this are thrown away
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 10;
+
+// attempts at a new name before falling back to one from the word list
+const MAX_ATTEMPTS: usize = 100;
+
+// start and end of a word in the training data
+const START: char = '^';
+const END: char = '$';
+
+// the naming tradition a name is generated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum NameStyle {
+    Human,
+    Elvish,
+    Dwarvish,
+    Halfling,
+    Gnomish,
+    Orcish,
+    Draconic,
+    Infernal,
+}
+
+impl NameStyle {
+    pub const ALL: [NameStyle; 8] = [
+        NameStyle::Human,
+        NameStyle::Elvish,
+        NameStyle::Dwarvish,
+        NameStyle::Halfling,
+        NameStyle::Gnomish,
+        NameStyle::Orcish,
+        NameStyle::Draconic,
+        NameStyle::Infernal,
+    ];
+
+    // bundled list of names the style's model is trained on
+    fn word_list(&self) -> &'static str {
+        match self {
+            NameStyle::Human => include_str!("../data/names/human.txt"),
+            NameStyle::Elvish => include_str!("../data/names/elvish.txt"),
+            NameStyle::Dwarvish => include_str!("../data/names/dwarvish.txt"),
+            NameStyle::Halfling => include_str!("../data/names/halfling.txt"),
+            NameStyle::Gnomish => include_str!("../data/names/gnomish.txt"),
+            NameStyle::Orcish => include_str!("../data/names/orcish.txt"),
+            NameStyle::Draconic => include_str!("../data/names/draconic.txt"),
+            NameStyle::Infernal => include_str!("../data/names/infernal.txt"),
+        }
+    }
+
+    // trained once and kept for the rest of the run
+    fn model(&self) -> &'static NameModel {
+        static MODELS: OnceLock<HashMap<NameStyle, NameModel>> = OnceLock::new();
+        let models = MODELS.get_or_init(|| {
+            NameStyle::ALL.into_iter().map(|style| (style, NameModel::train(style.word_list()))).collect()
+        });
+        &models[self]
+    }
+}
+
+// character-level markov chain, maps the last ORDER letters to every letter seen after them
+struct NameModel {
+    transitions: HashMap<Vec<char>, Vec<char>>,
+    words: Vec<String>,
+    known: HashSet<String>,
+}
+
+impl NameModel {
+    // one name per line, blank lines and # comments are skipped
+    fn train(list: &str) -> NameModel {
+        let words: Vec<String> = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+
+        let mut transitions: HashMap<Vec<char>, Vec<char>> = HashMap::new();
+        for word in &words {
+            let padded: Vec<char> =
+                std::iter::repeat_n(START, ORDER).chain(word.chars()).chain(std::iter::once(END)).collect();
+            for window in padded.windows(ORDER + 1) {
+                transitions.entry(window[..ORDER].to_vec()).or_default().push(window[ORDER]);
+            }
+        }
+
+        let known = words.iter().cloned().collect();
+        NameModel { transitions, words, known }
+    }
+
+    // walk the chain from the start marker until it ends the word, None if it runs too long
+    fn walk<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<String> {
+        let mut context = vec![START; ORDER];
+        let mut name = String::new();
+
+        loop {
+            let next = *self.transitions.get(&context)?.choose(rng)?;
+            if next == END {
+                return Some(name);
+            }
+            if name.chars().count() == MAX_LENGTH {
+                return None;
+            }
+
+            name.push(next);
+            context.remove(0);
+            context.push(next);
+        }
+    }
+
+    // a name that isn't already in the word list, if the model manages one
+    fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        for _ in 0..MAX_ATTEMPTS {
+            if let Some(name) = self.walk(rng) {
+                if name.chars().count() >= MIN_LENGTH && !self.known.contains(&name) {
+                    return capitalize(&name);
+                }
+            }
+        }
+
+        // falling back on the list still keeps to the length limits
+        let fitting: Vec<&String> =
+            self.words.iter().filter(|word| (MIN_LENGTH..=MAX_LENGTH).contains(&word.chars().count())).collect();
+        fitting.choose(rng).map(|word| capitalize(word)).unwrap_or_else(|| "Nameless".to_string())
+    }
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// a new name in the given style
+pub fn generate_name<R: Rng + ?Sized>(style: NameStyle, rng: &mut R) -> String {
+    style.model().generate(rng)
+}
+
+// a random name suited to the race
+pub fn random_name<R: Rng + ?Sized>(race: Race, rng: &mut R) -> String {
+    generate_name(race.name_style(), rng)
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::character::Character;
+use crate::language::Language;
+use crate::save::load_character;
+use crate::skill::Skill;
+
+// a roster file listing the save files of everyone in the party;
+// paths are relative to the roster's own directory when they can be
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Party {
+    pub members: Vec<PathBuf>,
+}
+
+impl Party {
+    // a missing roster file is an empty party, so the first add creates it
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Party> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Party::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    // add a save file, returns false if it's already in the party
+    pub fn add(&mut self, roster: &Path, member: &Path) -> io::Result<bool> {
+        let member = fs::canonicalize(member)?;
+        let entry =
+            roster_dir(roster).and_then(|dir| member.strip_prefix(dir).ok().map(Path::to_path_buf)).unwrap_or(member);
+
+        if self.members.contains(&entry) {
+            return Ok(false);
+        }
+        self.members.push(entry);
+        Ok(true)
+    }
+
+    // where a member's save file is, for reading it
+    pub fn member_path(&self, roster: &Path, member: &Path) -> PathBuf {
+        match roster_dir(roster) {
+            Some(dir) => dir.join(member),
+            None => member.to_path_buf(),
+        }
+    }
+
+    // every member loaded, in roster order
+    pub fn characters(&self, roster: &Path) -> Vec<(PathBuf, io::Result<Character>)> {
+        self.members.iter().map(|member| (member.clone(), load_character(self.member_path(roster, member)))).collect()
+    }
+
+    // the member with this character name or save file, loading saves to compare names
+    pub fn find(&self, roster: &Path, name: &str) -> Option<(PathBuf, Character)> {
+        self.characters(roster).into_iter().find_map(|(member, character)| {
+            let character = character.ok()?;
+            let by_path = member == Path::new(name);
+            (by_path || crate::matches_name(name, &character.name)).then_some((member, character))
+        })
+    }
+
+    // drop a member by character name or save file, returns the removed entry
+    pub fn remove(&mut self, roster: &Path, name: &str) -> Option<PathBuf> {
+        let member = match self.find(roster, name) {
+            Some((member, _)) => member,
+            // members whose save file is gone can still be removed by path
+            None => self.members.iter().find(|m| m.as_path() == Path::new(name))?.clone(),
+        };
+        self.members.retain(|m| *m != member);
+        Some(member)
+    }
+}
+
+// the directory a roster's relative paths start from
+fn roster_dir(roster: &Path) -> Option<PathBuf> {
+    let dir = roster.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    fs::canonicalize(dir).ok()
+}
+
+// one line of the party table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSummary {
+    pub name: String,
+    pub race: String,
+    pub class: String,
+    pub level: u8,
+    pub hit_points: u16,
+    pub hit_point_maximum: u16,
+    pub armor_class: u8,
+    pub passive_perception: i8,
+}
+
+// who the party should send to make a check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillCoverage {
+    pub skill: Skill,
+    pub bonus: i8,
+    // everyone tied for the best bonus
+    pub best: Vec<String>,
+    pub proficient: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartySummary {
+    pub members: Vec<MemberSummary>,
+    // each language with the members who speak it
+    pub languages: BTreeMap<Language, Vec<String>>,
+    pub skills: Vec<SkillCoverage>,
+}
+
+impl PartySummary {
+    pub fn new(characters: &[Character]) -> PartySummary {
+        let members = characters
+            .iter()
+            .map(|character| MemberSummary {
+                name: character.name.clone(),
+                race: character.race.to_string(),
+                class: character.class.to_string(),
+                level: character.level,
+                hit_points: character.hit_points.current,
+                hit_point_maximum: character.hit_point_maximum(),
+                armor_class: character.armor_class(),
+                passive_perception: character.derived_stats().passive_perception,
+            })
+            .collect();
+
+        let mut languages: BTreeMap<Language, Vec<String>> = BTreeMap::new();
+        for character in characters {
+            for &language in &character.languages {
+                languages.entry(language).or_default().push(character.name.clone());
+            }
+        }
+
+        let skills = Skill::ALL
+            .into_iter()
+            .filter_map(|skill| {
+                let bonus = characters.iter().map(|c| c.skill_modifier(skill)).max()?;
+                let best: Vec<&Character> = characters.iter().filter(|c| c.skill_modifier(skill) == bonus).collect();
+                Some(SkillCoverage {
+                    skill,
+                    bonus,
+                    proficient: best.iter().any(|c| c.skills.contains(&skill)),
+                    best: best.iter().map(|c| c.name.clone()).collect(),
+                })
+            })
+            .collect();
+
+        PartySummary { members, languages, skills }
+    }
+
+    // print the summary table
+    pub fn display(&self) {
+        if self.members.is_empty() {
+            println!("The party is empty.");
+            return;
+        }
+
+        let total: u32 = self.members.iter().map(|m| m.level as u32).sum();
+        println!(
+            "\n=== PARTY ({} members, average level {:.1}) ===",
+            self.members.len(),
+            total as f32 / self.members.len() as f32
+        );
+        let width = self.members.iter().map(|m| m.name.len()).max().unwrap_or(0).max(4);
+        println!(
+            "{:<width$}  {:<22} {:>5} {:>9} {:>3} {:>7}",
+            "Name",
+            "Race and class",
+            "Level",
+            "HP",
+            "AC",
+            "Passive",
+            width = width
+        );
+        for member in &self.members {
+            println!(
+                "{:<width$}  {:<22} {:>5} {:>9} {:>3} {:>7}",
+                member.name,
+                format!("{} {}", member.race, member.class),
+                member.level,
+                format!("{}/{}", member.hit_points, member.hit_point_maximum),
+                member.armor_class,
+                member.passive_perception,
+                width = width
+            );
+        }
+
+        println!("\nLanguages:");
+        let everyone = self.members.len();
+        for (language, speakers) in &self.languages {
+            if speakers.len() == everyone {
+                println!("  {:?} (everyone)", language);
+            } else {
+                println!("  {:?} ({})", language, speakers.join(", "));
+            }
+        }
+        let unknown: Vec<String> =
+            Language::ALL.iter().filter(|l| !self.languages.contains_key(l)).map(|l| format!("{:?}", l)).collect();
+        if !unknown.is_empty() {
+            println!("  Nobody speaks: {}", unknown.join(", "));
+        }
+
+        println!("\nSkill coverage (best bonus in the party):");
+        for coverage in &self.skills {
+            println!("  {:<16} {:+3} {}", format!("{:?}", coverage.skill), coverage.bonus, coverage.best.join(", "));
+        }
+
+        let gaps: Vec<String> =
+            self.skills.iter().filter(|c| !c.proficient).map(|c| format!("{:?}", c.skill)).collect();
+        if !gaps.is_empty() {
+            println!("\nNo one is proficient in: {}", gaps.join(", "));
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, Color, Greyscale, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Rect,
+};
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::conditions::ConditionDuration;
+use crate::skill::Skill;
+
+// US Letter, like the printed sheets
+const PAGE_WIDTH: f32 = 215.9;
+const PAGE_HEIGHT: f32 = 279.4;
+const MARGIN: f32 = 12.0;
+
+// Helvetica averages about half an em per character, close enough for wrapping
+fn chars_that_fit(width: f32, size: f32) -> usize {
+    let em = size * 0.3528;
+    ((width / (em * 0.5)) as usize).max(1)
+}
+
+fn wrap(text: &str, width: f32, size: f32) -> Vec<String> {
+    let max = chars_that_fit(width, size);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.len() + 1 + word.len() > max {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// one page being drawn, measured in mm from the top left corner
+struct Page {
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+impl Page {
+    fn text(&self, text: &str, size: f32, x: f32, top: f32) {
+        self.layer.use_text(text, size, Mm(x), Mm(PAGE_HEIGHT - top), &self.regular);
+    }
+
+    fn bold(&self, text: &str, size: f32, x: f32, top: f32) {
+        self.layer.use_text(text, size, Mm(x), Mm(PAGE_HEIGHT - top), &self.bold);
+    }
+
+    // text centred on x
+    fn centred(&self, text: &str, size: f32, x: f32, top: f32, bold: bool) {
+        let width = text.len() as f32 * size * 0.3528 * 0.5;
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer.use_text(text, size, Mm(x - width / 2.0), Mm(PAGE_HEIGHT - top), font);
+    }
+
+    fn frame(&self, x: f32, top: f32, width: f32, height: f32) {
+        let bottom = PAGE_HEIGHT - top - height;
+        self.layer
+            .add_rect(Rect::new(Mm(x), Mm(bottom), Mm(x + width), Mm(PAGE_HEIGHT - top)).with_mode(PaintMode::Stroke));
+    }
+
+    // a framed box with a small caption along the bottom, like the AC and speed boxes
+    fn stat_box(&self, x: f32, top: f32, width: f32, height: f32, value: &str, caption: &str) {
+        self.frame(x, top, width, height);
+        self.centred(value, 16.0, x + width / 2.0, top + height * 0.55, true);
+        self.centred(caption, 6.0, x + width / 2.0, top + height - 2.0, false);
+    }
+
+    // a framed box with a title and wrapped lines, cut off if they don't fit
+    fn text_box(&self, x: f32, top: f32, width: f32, height: f32, title: &str, lines: &[String]) {
+        self.frame(x, top, width, height);
+        self.bold(title, 7.0, x + 2.0, top + 4.0);
+        let mut y = top + 8.5;
+        for line in lines.iter().flat_map(|line| wrap(line, width - 4.0, 7.5)) {
+            if y > top + height - 1.5 {
+                break;
+            }
+            self.text(&line, 7.5, x + 2.0, y);
+            y += 3.4;
+        }
+    }
+}
+
+// builds pages on one document, sharing the fonts
+struct Builder {
+    document: PdfDocumentReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    first: Option<PdfLayerReference>,
+}
+
+impl Builder {
+    fn new(title: &str) -> Result<Builder, printpdf::Error> {
+        let (document, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Sheet");
+        let regular = document.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = document.add_builtin_font(BuiltinFont::HelveticaBold)?;
+        let first = Some(document.get_page(page).get_layer(layer));
+        Ok(Builder { document, regular, bold, first })
+    }
+
+    fn page(&mut self) -> Page {
+        let layer = self.first.take().unwrap_or_else(|| {
+            let (page, layer) = self.document.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Sheet");
+            self.document.get_page(page).get_layer(layer)
+        });
+        layer.set_outline_thickness(0.6);
+        layer.set_outline_color(Color::Greyscale(Greyscale::new(0.25, None)));
+        Page { layer, regular: self.regular.clone(), bold: self.bold.clone() }
+    }
+}
+
+fn signed(value: i8) -> String {
+    format!("{:+}", value)
+}
+
+// name, class, race and the rest across the top of every page
+fn header(page: &Page, character: &Character, subtitle: &str) {
+    page.frame(MARGIN, MARGIN, PAGE_WIDTH - 2.0 * MARGIN, 20.0);
+    page.bold(&character.name, 18.0, MARGIN + 3.0, MARGIN + 9.0);
+    page.text(subtitle, 7.0, MARGIN + 3.0, MARGIN + 16.0);
+
+    let race = match character.subrace {
+        Some(subrace) => format!("{} ({})", character.race, subrace),
+        None => character.race.to_string(),
+    };
+    let fields = [
+        (format!("{} {}", character.class, character.level), "CLASS & LEVEL"),
+        (character.background.to_string(), "BACKGROUND"),
+        (race, "RACE"),
+        (character.experience.to_string(), "EXPERIENCE POINTS"),
+    ];
+    for (i, (value, caption)) in fields.iter().enumerate() {
+        let x = 92.0 + (i % 2) as f32 * 56.0;
+        let top = MARGIN + 7.0 + (i / 2) as f32 * 9.0;
+        page.text(value, 9.0, x, top);
+        page.text(caption, 5.5, x, top + 3.0);
+    }
+}
+
+fn front_page(page: &Page, character: &Character) {
+    header(page, character, "CHARACTER NAME");
+    let top = MARGIN + 24.0;
+
+    // left: abilities, with saves and skills next to them
+    for (i, ability) in Ability::ALL.into_iter().enumerate() {
+        let score = character.abilities.score(ability);
+        let y = top + i as f32 * 27.0;
+        page.frame(MARGIN, y, 24.0, 25.0);
+        page.centred(&format!("{:?}", ability).to_uppercase(), 6.0, MARGIN + 12.0, y + 4.5, true);
+        page.centred(&score.modifier().to_string(), 18.0, MARGIN + 12.0, y + 15.0, true);
+        page.centred(&score.to_string(), 9.0, MARGIN + 12.0, y + 22.0, false);
+    }
+
+    let x = MARGIN + 27.0;
+    page.stat_box(x, top, 42.0, 12.0, &signed(character.proficiency_bonus()), "PROFICIENCY BONUS");
+
+    page.frame(x, top + 15.0, 42.0, 30.0);
+    for (i, ability) in Ability::ALL.into_iter().enumerate() {
+        let marker = if character.saving_throws.contains(&ability) { "(*)" } else { "( )" };
+        page.text(
+            &format!("{} {} {:?}", marker, signed(character.saving_throw_modifier(ability)), ability),
+            7.5,
+            x + 2.0,
+            top + 20.0 + i as f32 * 3.6,
+        );
+    }
+    page.centred("SAVING THROWS", 6.0, x + 21.0, top + 43.5, false);
+
+    page.frame(x, top + 48.0, 42.0, 86.0);
+    let skills = Skill::grouped_by_ability()
+        .into_iter()
+        .flat_map(|(ability, skills)| skills.into_iter().map(move |skill| (ability, skill)));
+    for (i, (ability, skill)) in skills.enumerate() {
+        let marker = if character.expertise.contains(&skill) {
+            "(E)"
+        } else if character.skills.contains(&skill) {
+            "(*)"
+        } else {
+            "( )"
+        };
+        let name = format!(
+            "{} {} {:?} ({})",
+            marker,
+            signed(character.skill_modifier(skill)),
+            skill,
+            &format!("{:?}", ability)[..3]
+        );
+        page.text(&name, 7.0, x + 2.0, top + 53.0 + i as f32 * 4.25);
+    }
+    page.centred("SKILLS", 6.0, x + 21.0, top + 132.5, false);
+
+    let stats = character.derived_stats();
+    page.stat_box(
+        MARGIN,
+        top + 165.0,
+        69.0,
+        12.0,
+        &stats.passive_perception.to_string(),
+        "PASSIVE WISDOM (PERCEPTION)",
+    );
+    let proficiencies = vec![
+        format!("Languages: {}", character.languages.iter().map(|l| format!("{:?}", l)).collect::<Vec<_>>().join(", ")),
+        format!(
+            "Tools: {}",
+            if character.tool_proficiencies.is_empty() {
+                "None".to_string()
+            } else {
+                character.tool_proficiencies.join(", ")
+            }
+        ),
+    ];
+    page.text_box(
+        MARGIN,
+        top + 180.0,
+        69.0,
+        PAGE_HEIGHT - MARGIN - top - 180.0,
+        "OTHER PROFICIENCIES & LANGUAGES",
+        &proficiencies,
+    );
+
+    // middle: combat
+    let x = MARGIN + 73.0;
+    let width = 60.0;
+    let third = (width - 4.0) / 3.0;
+    page.stat_box(x, top, third, 16.0, &character.armor_class().to_string(), "ARMOR CLASS");
+    page.stat_box(x + third + 2.0, top, third, 16.0, &signed(stats.initiative), "INITIATIVE");
+    page.stat_box(x + 2.0 * (third + 2.0), top, third, 16.0, &format!("{} ft", stats.speeds.walk), "SPEED");
+
+    page.frame(x, top + 19.0, width, 22.0);
+    page.text(&format!("Hit Point Maximum: {}", character.hit_point_maximum()), 7.0, x + 2.0, top + 23.0);
+    page.centred(&character.hit_points.current.to_string(), 18.0, x + width / 2.0, top + 33.0, true);
+    page.centred("CURRENT HIT POINTS", 6.0, x + width / 2.0, top + 39.5, false);
+    let temp = if character.hit_points.temp > 0 { character.hit_points.temp.to_string() } else { String::new() };
+    page.stat_box(x, top + 43.0, width, 12.0, &temp, "TEMPORARY HIT POINTS");
+
+    let half = (width - 2.0) / 2.0;
+    let hit_dice = format!("{}d{}", stats.hit_dice.remaining, stats.hit_dice.die);
+    page.stat_box(x, top + 57.0, half, 14.0, &hit_dice, &format!("HIT DICE (TOTAL {})", stats.hit_dice.total));
+    let death_saves = format!("{}/{}", character.death_saves.successes, character.death_saves.failures);
+    page.stat_box(x + half + 2.0, top + 57.0, half, 14.0, &death_saves, "DEATH SAVES (OK/FAIL)");
+
+    page.frame(x, top + 74.0, width, 60.0);
+    page.bold("NAME", 6.0, x + 2.0, top + 78.0);
+    page.bold("ATK", 6.0, x + 30.0, top + 78.0);
+    page.bold("DAMAGE/TYPE", 6.0, x + 39.0, top + 78.0);
+    for (i, attack) in character.attacks().iter().take(12).enumerate() {
+        let y = top + 83.0 + i as f32 * 4.0;
+        let name: String = attack.name.chars().take(16).collect();
+        page.text(&name, 7.0, x + 2.0, y);
+        page.text(&signed(attack.to_hit), 7.0, x + 30.0, y);
+        page.text(&format!("{} {:?}", attack.damage, attack.damage_type), 6.5, x + 39.0, y);
+    }
+    page.centred("ATTACKS & SPELLCASTING", 6.0, x + width / 2.0, top + 132.5, false);
+
+    let mut equipment: Vec<String> = character
+        .equipment
+        .iter()
+        .map(|item| if item.quantity > 1 { format!("{} x{}", item.name, item.quantity) } else { item.name.clone() })
+        .collect();
+    equipment.push(format!("Carrying {} lb of {} lb", character.carried_weight(), stats.carrying.capacity));
+    page.text_box(x, top + 137.0, width, PAGE_HEIGHT - MARGIN - top - 137.0, "EQUIPMENT", &equipment);
+
+    // right: personality and features
+    let x = MARGIN + 137.0;
+    let width = PAGE_WIDTH - MARGIN - x;
+    let personality = &character.personality;
+    page.text_box(x, top, width, 30.0, "PERSONALITY TRAITS", &personality.traits);
+    page.text_box(x, top + 32.0, width, 18.0, "IDEALS", std::slice::from_ref(&personality.ideal));
+    page.text_box(x, top + 52.0, width, 18.0, "BONDS", std::slice::from_ref(&personality.bond));
+    page.text_box(x, top + 72.0, width, 18.0, "FLAWS", std::slice::from_ref(&personality.flaw));
+
+    let (feature, description) = character.background.feature();
+    let mut features = vec![format!("{}: {}", feature, description)];
+    for resource in character.class.resources().into_iter().filter(|r| r.max_uses(character) > 0) {
+        features.push(format!(
+            "{:?}: {}/{} uses",
+            resource,
+            character.resource_remaining(resource),
+            resource.max_uses(character)
+        ));
+    }
+    features.extend(character.feats.iter().map(|feat| format!("Feat: {:?}", feat)));
+    for (label, damage_types) in
+        [("Resistant to", &character.resistances), ("Vulnerable to", &character.vulnerabilities)]
+    {
+        let mut names: Vec<String> = damage_types.iter().map(|d| format!("{:?}", d)).collect();
+        if !names.is_empty() {
+            names.sort();
+            features.push(format!("{} {}", label, names.join(", ")));
+        }
+    }
+    features.extend(character.conditions.iter().map(|active| match active.duration {
+        ConditionDuration::Rounds(rounds) => format!("Condition: {:?} ({} rounds)", active.condition, rounds),
+        ConditionDuration::UntilRemoved => format!("Condition: {:?}", active.condition),
+    }));
+    page.text_box(x, top + 92.0, width, PAGE_HEIGHT - MARGIN - top - 92.0, "FEATURES & TRAITS", &features);
+}
+
+// the player's notes, which have no room on the front
+fn notes_page(page: &Page, character: &Character) {
+    header(page, character, "NOTES");
+    let top = MARGIN + 24.0;
+    let notes: Vec<String> = character.notes.lines().map(String::from).collect();
+    page.text_box(MARGIN, top, PAGE_WIDTH - 2.0 * MARGIN, PAGE_HEIGHT - MARGIN - top, "NOTES", &notes);
+}
+
+fn spell_page(page: &Page, character: &Character) {
+    header(page, character, "SPELLCASTING");
+    let top = MARGIN + 24.0;
+    let slots = character.class.spell_slots(character.level);
+
+    let width = (PAGE_WIDTH - 2.0 * MARGIN - 8.0 * 2.0) / 9.0;
+    for (i, &count) in slots.iter().enumerate() {
+        let x = MARGIN + i as f32 * (width + 2.0);
+        let remaining = count.saturating_sub(character.spell_slots_used[i]);
+        let value = if count == 0 { "-".to_string() } else { format!("{}/{}", remaining, count) };
+        page.stat_box(x, top, width, 14.0, &value, &format!("LEVEL {}", i + 1));
+    }
+
+    let spells =
+        if character.spells.is_empty() { vec!["No spells recorded.".to_string()] } else { character.spells.clone() };
+    let column = (PAGE_WIDTH - 2.0 * MARGIN - 4.0) / 2.0;
+    let height = PAGE_HEIGHT - MARGIN - top - 18.0;
+    let per_column = ((height - 10.0) / 3.4) as usize;
+    let (left, right) = spells.split_at(spells.len().min(per_column));
+    page.text_box(MARGIN, top + 18.0, column, height, "SPELLS KNOWN & PREPARED", left);
+    page.text_box(MARGIN + column + 4.0, top + 18.0, column, height, "", right);
+}
+
+impl Character {
+    // a printable sheet: the front page, a spell page for casters and a notes page if there are notes
+    pub fn export_pdf(&self) -> Result<Vec<u8>, printpdf::Error> {
+        let mut builder = Builder::new(&self.name)?;
+        front_page(&builder.page(), self);
+
+        let caster = self.class.spell_slots(self.level).iter().any(|&count| count > 0);
+        if caster || !self.spells.is_empty() {
+            spell_page(&builder.page(), self);
+        }
+        if !self.notes.trim().is_empty() {
+            notes_page(&builder.page(), self);
+        }
+
+        builder.document.save_to_bytes()
+    }
+}
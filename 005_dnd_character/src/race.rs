@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use crate::ability::Ability;
+use crate::content::{content, content_handle, RaceDef, SubraceDef};
+use crate::hit_points::DamageType;
+use crate::language::Language;
+use crate::names::NameStyle;
+use crate::skill::Skill;
+
+// race, one of the definitions loaded from the content packs
+#[derive(Clone, Copy)]
+pub struct Race(&'static RaceDef);
+
+impl Race {
+    // every loaded race, in load order
+    pub fn all() -> Vec<Race> {
+        content().races.iter().map(Race).collect()
+    }
+
+    pub fn id(&self) -> &'static str {
+        &self.0.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        &self.0.name
+    }
+
+    pub fn ability_bonuses(&self) -> HashMap<Ability, i8> {
+        self.0.ability_bonuses.clone()
+    }
+
+    // +1 bonuses the player puts into abilities without a racial bonus, like the Half-Elf's
+    pub fn ability_choices(&self) -> usize {
+        self.0.ability_choices
+    }
+
+    // abilities the chosen +1 bonuses can go into
+    pub fn ability_choice_options(&self) -> Vec<Ability> {
+        Ability::ALL.into_iter().filter(|a| !self.0.ability_bonuses.contains_key(a)).collect()
+    }
+
+    pub fn racial_skills(&self) -> Vec<Skill> {
+        self.0.skills.clone()
+    }
+
+    pub fn languages(&self) -> Vec<Language> {
+        self.0.languages.clone()
+    }
+
+    // extra languages of the player's choice
+    pub fn extra_languages(&self) -> usize {
+        self.0.extra_languages
+    }
+
+    // base walking speed in feet
+    pub fn speed(&self) -> u8 {
+        self.0.speed
+    }
+
+    // a swimming or climbing speed of its own, 0 if the race swims and climbs at the
+    // cost of extra movement like everyone else, which is all of the core races
+    pub fn swim_speed(&self) -> u8 {
+        self.0.swim_speed
+    }
+
+    pub fn climb_speed(&self) -> u8 {
+        self.0.climb_speed
+    }
+
+    pub fn damage_resistances(&self) -> Vec<DamageType> {
+        self.0.resistances.clone()
+    }
+
+    // the naming tradition used for random names
+    pub fn name_style(&self) -> NameStyle {
+        self.0.name_style
+    }
+
+    pub fn subraces(&self) -> Vec<Subrace> {
+        self.0.subraces.iter().map(Subrace).collect()
+    }
+
+    // a one-line summary shown when choosing a race
+    pub fn description(&self) -> &'static str {
+        &self.0.description
+    }
+}
+
+// subraces
+#[derive(Clone, Copy)]
+pub struct Subrace(&'static SubraceDef);
+
+impl Subrace {
+    // every loaded subrace, across all races
+    pub fn all() -> Vec<Subrace> {
+        Race::all().iter().flat_map(Race::subraces).collect()
+    }
+
+    pub fn id(&self) -> &'static str {
+        &self.0.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        &self.0.name
+    }
+
+    // the race this subrace belongs to
+    pub fn race(&self) -> Race {
+        Race::all().into_iter().find(|race| race.subraces().contains(self)).expect("subrace without a race")
+    }
+
+    pub fn ability_bonuses(&self) -> HashMap<Ability, i8> {
+        self.0.ability_bonuses.clone()
+    }
+
+    pub fn damage_resistances(&self) -> Vec<DamageType> {
+        self.0.resistances.clone()
+    }
+
+    // e.g. dwarven toughness
+    pub fn hit_points_per_level(&self) -> u16 {
+        self.0.hit_points_per_level
+    }
+
+    // e.g. fleet of foot
+    pub fn speed_bonus(&self) -> u8 {
+        self.0.speed_bonus
+    }
+
+    pub fn swim_speed(&self) -> u8 {
+        self.0.swim_speed
+    }
+
+    pub fn climb_speed(&self) -> u8 {
+        self.0.climb_speed
+    }
+
+    // a one-line summary shown when choosing a subrace
+    pub fn description(&self) -> &'static str {
+        &self.0.description
+    }
+}
+
+content_handle!(Race, "race");
+content_handle!(Subrace, "subrace");
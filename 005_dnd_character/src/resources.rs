@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::class::Class;
+
+// when a resource comes back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recharge {
+    ShortRest,
+    LongRest,
+}
+
+// limited-use class features
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ClassResource {
+    Rage,
+    BardicInspiration,
+    ChannelDivinity,
+    WildShape,
+    SecondWind,
+    ActionSurge,
+    Ki,
+    DivineSense,
+    LayOnHands,
+    SorceryPoints,
+    ArcaneRecovery,
+}
+
+impl ClassResource {
+    pub fn recharge(&self, level: u8) -> Recharge {
+        match self {
+            // Font of Inspiration makes it a short rest resource from 5th level
+            ClassResource::BardicInspiration if level >= 5 => Recharge::ShortRest,
+            ClassResource::ChannelDivinity
+            | ClassResource::WildShape
+            | ClassResource::SecondWind
+            | ClassResource::ActionSurge
+            | ClassResource::Ki => Recharge::ShortRest,
+            _ => Recharge::LongRest,
+        }
+    }
+
+    // maximum uses for a character, or 0 if the feature isn't available yet
+    pub fn max_uses(&self, character: &Character) -> u8 {
+        let level = character.level;
+        let charisma = character.abilities.modifier(Ability::Charisma);
+
+        match self {
+            ClassResource::Rage => match level {
+                1..=2 => 2,
+                3..=5 => 3,
+                6..=11 => 4,
+                12..=16 => 5,
+                17..=19 => 6,
+                _ => u8::MAX, // unlimited at 20th level
+            },
+            ClassResource::BardicInspiration => charisma.max(1) as u8,
+            ClassResource::ChannelDivinity => match level {
+                0..=1 => 0,
+                2..=5 => 1,
+                6..=17 => 2,
+                _ => 3,
+            },
+            ClassResource::WildShape if level >= 2 => 2,
+            ClassResource::SecondWind => 1,
+            ClassResource::ActionSurge => match level {
+                0..=1 => 0,
+                2..=16 => 1,
+                _ => 2,
+            },
+            ClassResource::Ki if level >= 2 => level,
+            ClassResource::DivineSense => (1 + charisma).max(1) as u8,
+            ClassResource::LayOnHands => level.saturating_mul(5),
+            ClassResource::SorceryPoints if level >= 2 => level,
+            ClassResource::ArcaneRecovery => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl Class {
+    pub fn resources(&self) -> Vec<ClassResource> {
+        self.def().resources.clone()
+    }
+}
@@ -0,0 +1,100 @@
+use rand::Rng;
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::conditions::{Condition, ConditionDuration, ROUNDS_PER_HOUR};
+use crate::dice::roll_die_with;
+use crate::resources::Recharge;
+use crate::spellcasting::Spellcasting;
+
+// what happened during a rest
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestSummary {
+    pub hit_dice_rolls: Vec<u8>,
+    pub hit_points_regained: u16,
+    pub hit_dice_regained: u8,
+}
+
+impl Character {
+    pub fn hit_dice_remaining(&self) -> u8 {
+        self.level.saturating_sub(self.hit_dice_spent)
+    }
+
+    // spend one hit die, returns the roll or None if there are no hit dice left
+    pub fn spend_hit_die<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<(u8, u16)> {
+        if self.hit_dice_remaining() == 0 || !self.is_conscious() {
+            return None;
+        }
+
+        self.hit_dice_spent += 1;
+
+        // a die never heals less than 0, even with a negative Con modifier
+        let roll = roll_die_with(rng, self.class.hit_die());
+        let con_modifier = self.abilities.modifier(Ability::Constitution) as i16;
+        let amount = (roll as i16 + con_modifier).max(0) as u16;
+
+        Some((roll, self.restore_hit_points(amount)))
+    }
+
+    // short rest: spend up to `hit_dice` hit dice and recover short rest resources
+    pub fn short_rest<R: Rng + ?Sized>(&mut self, hit_dice: u8, rng: &mut R) -> RestSummary {
+        let mut summary = RestSummary::default();
+
+        for _ in 0..hit_dice {
+            match self.spend_hit_die(rng) {
+                Some((roll, healed)) => {
+                    summary.hit_dice_rolls.push(roll);
+                    summary.hit_points_regained += healed;
+                },
+                None => break,
+            }
+        }
+
+        self.restore_resources(Recharge::ShortRest);
+        self.advance_rounds(ROUNDS_PER_HOUR);
+
+        // pact magic slots come back on a short rest
+        if let Spellcasting::Pact = self.class.spellcasting() {
+            self.spell_slots_used = [0; 9];
+        }
+
+        summary
+    }
+
+    // long rest: full hit points, half the spent hit dice back, all slots and resources
+    pub fn long_rest(&mut self) -> RestSummary {
+        // a long rest needs at least 1 hit point at the start to do anything
+        if self.hit_points.current == 0 {
+            return RestSummary::default();
+        }
+
+        // finishing a long rest removes one level of exhaustion
+        match self.exhaustion_level() {
+            0 => {},
+            1 => {
+                self.remove_condition(Condition::Exhaustion(1));
+            },
+            level => self.apply_condition(Condition::Exhaustion(level - 1), ConditionDuration::UntilRemoved),
+        }
+        self.advance_rounds(8 * ROUNDS_PER_HOUR);
+
+        let hit_points_regained = self.restore_hit_points(self.hit_points.max);
+        self.hit_points.temp = 0;
+
+        // regain half of the spent hit dice (minimum of one)
+        let hit_dice_regained = (self.hit_dice_spent / 2).max(1).min(self.hit_dice_spent);
+        self.hit_dice_spent -= hit_dice_regained;
+
+        self.spell_slots_used = [0; 9];
+        self.restore_resources(Recharge::LongRest);
+
+        RestSummary { hit_dice_rolls: Vec::new(), hit_points_regained, hit_dice_regained }
+    }
+
+    // long rests restore everything, short rests only the short rest resources
+    fn restore_resources(&mut self, rest: Recharge) {
+        let level = self.level;
+        self.resources_used
+            .retain(|resource, _| rest == Recharge::ShortRest && resource.recharge(level) == Recharge::LongRest);
+    }
+}
@@ -0,0 +1,222 @@
+use rand::Rng;
+
+use crate::ability::Ability;
+use crate::character::Character;
+use crate::conditions::{Condition, RollKind};
+use crate::dice::roll_die_with;
+use crate::skill::Skill;
+
+// roll one d20, or two and keep the higher/lower
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollMode {
+    #[default]
+    Normal,
+    Advantage,
+    Disadvantage,
+}
+
+impl RollMode {
+    // advantage and disadvantage cancel out, no matter how many sources of each
+    pub fn resolve(advantage: bool, disadvantage: bool) -> RollMode {
+        match (advantage, disadvantage) {
+            (true, false) => RollMode::Advantage,
+            (false, true) => RollMode::Disadvantage,
+            _ => RollMode::Normal,
+        }
+    }
+}
+
+// a d20 roll with its full breakdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct D20Roll {
+    pub mode: RollMode,
+    // every die rolled, two with advantage or disadvantage
+    pub rolls: Vec<u8>,
+    // the die that was kept
+    pub natural: u8,
+    // the value used for the total, differs from natural when Reliable Talent kicks in
+    pub die: u8,
+    pub ability_modifier: i8,
+    // proficiency, expertise or Jack of All Trades
+    pub proficiency: i8,
+    // situational bonus or penalty
+    pub bonus: i8,
+    pub total: i16,
+    // set by conditions such as paralyzed, the roll fails whatever the total
+    pub automatic_failure: Option<Condition>,
+}
+
+impl D20Roll {
+    pub fn is_natural_20(&self) -> bool {
+        self.natural == 20
+    }
+
+    pub fn is_natural_1(&self) -> bool {
+        self.natural == 1
+    }
+
+    // whether the roll meets a DC (or AC)
+    pub fn succeeds(&self, dc: i16) -> bool {
+        self.automatic_failure.is_none() && self.total >= dc
+    }
+
+    // e.g. "15 (d20: 12, 7 advantage) +3 ability +2 proficiency = 17"
+    pub fn breakdown(&self) -> String {
+        let rolls: Vec<String> = self.rolls.iter().map(|r| r.to_string()).collect();
+        let mut text = format!("d20: {}", rolls.join(", "));
+
+        match self.mode {
+            RollMode::Normal => {},
+            RollMode::Advantage => text.push_str(" advantage"),
+            RollMode::Disadvantage => text.push_str(" disadvantage"),
+        }
+        if self.die != self.natural {
+            text.push_str(&format!(", counts as {}", self.die));
+        }
+
+        let mut text = format!("{} ({})", self.die, text);
+        text.push_str(&format!(" {:+} ability", self.ability_modifier));
+        if self.proficiency != 0 {
+            text.push_str(&format!(" {:+} proficiency", self.proficiency));
+        }
+        if self.bonus != 0 {
+            text.push_str(&format!(" {:+} bonus", self.bonus));
+        }
+        text.push_str(&format!(" = {}", self.total));
+
+        if let Some(condition) = self.automatic_failure {
+            text.push_str(&format!(" (automatic failure: {:?})", condition));
+        } else if self.is_natural_20() {
+            text.push_str(" (natural 20!)");
+        } else if self.is_natural_1() {
+            text.push_str(" (natural 1!)");
+        }
+
+        text
+    }
+}
+
+// roll the d20(s) for a mode, returns every die and the one kept
+fn roll_d20<R: Rng + ?Sized>(rng: &mut R, mode: RollMode) -> (Vec<u8>, u8) {
+    let first = roll_die_with(rng, 20);
+
+    match mode {
+        RollMode::Normal => (vec![first], first),
+        RollMode::Advantage | RollMode::Disadvantage => {
+            let second = roll_die_with(rng, 20);
+            let kept = if mode == RollMode::Advantage { first.max(second) } else { first.min(second) };
+            (vec![first, second], kept)
+        },
+    }
+}
+
+impl Character {
+    // +2 at 1st level, going up by one every four levels
+    pub fn proficiency_bonus(&self) -> i8 {
+        2 + (self.level.clamp(1, 20) as i8 - 1) / 4
+    }
+
+    // Jack of All Trades: half the proficiency bonus on checks the character isn't proficient in
+    // (Bards from 2nd level)
+    pub(crate) fn jack_of_all_trades(&self) -> i8 {
+        if self.class.def().jack_of_all_trades.is_some_and(|level| self.level >= level) {
+            self.proficiency_bonus() / 2
+        } else {
+            0
+        }
+    }
+
+    // Reliable Talent: a d20 of 9 or lower counts as a 10 on proficient checks (Rogues from 11th level)
+    fn has_reliable_talent(&self) -> bool {
+        self.class.def().reliable_talent.is_some_and(|level| self.level >= level)
+    }
+
+    pub(crate) fn skill_proficiency(&self, skill: Skill) -> i8 {
+        if self.expertise.contains(&skill) {
+            self.proficiency_bonus() * 2
+        } else if self.skills.contains(&skill) {
+            self.proficiency_bonus()
+        } else {
+            self.jack_of_all_trades()
+        }
+    }
+
+    pub fn saving_throw_modifier(&self, ability: Ability) -> i8 {
+        let proficiency = if self.saving_throws.contains(&ability) { self.proficiency_bonus() } else { 0 };
+
+        self.abilities.modifier(ability) + proficiency
+    }
+
+    // roll a skill check
+    pub fn roll_check<R: Rng + ?Sized>(&self, skill: Skill, mode: RollMode, bonus: i8, rng: &mut R) -> D20Roll {
+        let reliable = self.has_reliable_talent() && self.skills.contains(&skill);
+        let proficiency = self.skill_proficiency(skill);
+
+        self.roll_d20_test(RollKind::AbilityCheck, skill.associated_ability(), proficiency, reliable, mode, bonus, rng)
+    }
+
+    // roll a raw ability check with no skill attached
+    pub fn roll_ability_check<R: Rng + ?Sized>(
+        &self,
+        ability: Ability,
+        mode: RollMode,
+        bonus: i8,
+        rng: &mut R,
+    ) -> D20Roll {
+        self.roll_d20_test(RollKind::AbilityCheck, ability, self.jack_of_all_trades(), false, mode, bonus, rng)
+    }
+
+    // roll a saving throw
+    pub fn roll_save<R: Rng + ?Sized>(&self, ability: Ability, mode: RollMode, bonus: i8, rng: &mut R) -> D20Roll {
+        let proficiency = if self.saving_throws.contains(&ability) { self.proficiency_bonus() } else { 0 };
+
+        self.roll_d20_test(RollKind::SavingThrow(ability), ability, proficiency, false, mode, bonus, rng)
+    }
+
+    // roll an attack, proficient is whether the character is proficient with the weapon or spell
+    pub fn roll_attack<R: Rng + ?Sized>(
+        &self,
+        ability: Ability,
+        proficient: bool,
+        mode: RollMode,
+        bonus: i8,
+        rng: &mut R,
+    ) -> D20Roll {
+        let proficiency = if proficient { self.proficiency_bonus() } else { 0 };
+
+        self.roll_d20_test(RollKind::Attack, ability, proficiency, false, mode, bonus, rng)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn roll_d20_test<R: Rng + ?Sized>(
+        &self,
+        kind: RollKind,
+        ability: Ability,
+        proficiency: i8,
+        reliable: bool,
+        mode: RollMode,
+        bonus: i8,
+        rng: &mut R,
+    ) -> D20Roll {
+        // conditions add their own advantage or disadvantage to whatever was asked for
+        let (advantage, disadvantage) = self.condition_roll_sources(kind);
+        let mode =
+            RollMode::resolve(advantage || mode == RollMode::Advantage, disadvantage || mode == RollMode::Disadvantage);
+
+        let (rolls, natural) = roll_d20(rng, mode);
+        let die = if reliable { natural.max(10) } else { natural };
+        let ability_modifier = self.abilities.modifier(ability);
+
+        D20Roll {
+            mode,
+            rolls,
+            natural,
+            die,
+            ability_modifier,
+            proficiency,
+            bonus,
+            total: die as i16 + ability_modifier as i16 + proficiency as i16 + bonus as i16,
+            automatic_failure: self.condition_auto_fail(kind),
+        }
+    }
+}
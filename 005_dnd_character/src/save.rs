@@ -0,0 +1,17 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::character::Character;
+
+// write a character to a JSON save file
+pub fn save_character(character: &Character, path: impl AsRef<Path>) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(character).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, json)
+}
+
+// read a character back from a JSON save file
+pub fn load_character(path: impl AsRef<Path>) -> io::Result<Character> {
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
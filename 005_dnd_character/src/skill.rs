@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ability::Ability;
+
+// skills and their associated abilities
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Skill {
+    Acrobatics,
+    AnimalHandling,
+    Arcana,
+    Athletics,
+    Deception,
+    History,
+    Insight,
+    Intimidation,
+    Investigation,
+    Medicine,
+    Nature,
+    Perception,
+    Performance,
+    Persuasion,
+    Religion,
+    SleightOfHand,
+    Stealth,
+    Survival,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 18] = [
+        Skill::Acrobatics,
+        Skill::AnimalHandling,
+        Skill::Arcana,
+        Skill::Athletics,
+        Skill::Deception,
+        Skill::History,
+        Skill::Insight,
+        Skill::Intimidation,
+        Skill::Investigation,
+        Skill::Medicine,
+        Skill::Nature,
+        Skill::Perception,
+        Skill::Performance,
+        Skill::Persuasion,
+        Skill::Religion,
+        Skill::SleightOfHand,
+        Skill::Stealth,
+        Skill::Survival,
+    ];
+
+    // every skill under its ability, abilities in sheet order and skills alphabetical
+    pub fn grouped_by_ability() -> Vec<(Ability, Vec<Skill>)> {
+        Ability::ALL
+            .iter()
+            .filter_map(|&ability| {
+                let skills: Vec<Skill> =
+                    Skill::ALL.iter().copied().filter(|s| s.associated_ability() == ability).collect();
+                if skills.is_empty() {
+                    None
+                } else {
+                    Some((ability, skills))
+                }
+            })
+            .collect()
+    }
+
+    pub fn associated_ability(&self) -> Ability {
+        match self {
+            Skill::Acrobatics => Ability::Dexterity,
+            Skill::AnimalHandling => Ability::Wisdom,
+            Skill::Arcana => Ability::Intelligence,
+            Skill::Athletics => Ability::Strength,
+            Skill::Deception => Ability::Charisma,
+            Skill::History => Ability::Intelligence,
+            Skill::Insight => Ability::Wisdom,
+            Skill::Intimidation => Ability::Charisma,
+            Skill::Investigation => Ability::Intelligence,
+            Skill::Medicine => Ability::Wisdom,
+            Skill::Nature => Ability::Intelligence,
+            Skill::Perception => Ability::Wisdom,
+            Skill::Performance => Ability::Charisma,
+            Skill::Persuasion => Ability::Charisma,
+            Skill::Religion => Ability::Intelligence,
+            Skill::SleightOfHand => Ability::Dexterity,
+            Skill::Stealth => Ability::Dexterity,
+            Skill::Survival => Ability::Wisdom,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use serde::Deserialize;
+
+use crate::class::Class;
+
+// full caster slots per level, indexed by character level - 1 then spell level - 1
+const FULL_CASTER_SLOTS: [[u8; 9]; 20] = [
+    [2, 0, 0, 0, 0, 0, 0, 0, 0],
+    [3, 0, 0, 0, 0, 0, 0, 0, 0],
+    [4, 2, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 2, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 1, 0, 0, 0, 0, 0],
+    [4, 3, 3, 2, 0, 0, 0, 0, 0],
+    [4, 3, 3, 3, 1, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 2, 1, 1],
+];
+
+// how a class gets its spell slots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum Spellcasting {
+    #[default]
+    None,
+    Full,
+    Half,
+    Pact,
+}
+
+impl Class {
+    pub fn spellcasting(&self) -> Spellcasting {
+        self.def().spellcasting
+    }
+
+    // maximum spell slots for each spell level (index 0 is 1st level)
+    pub fn spell_slots(&self, level: u8) -> [u8; 9] {
+        let level = level.clamp(1, 20);
+
+        match self.spellcasting() {
+            Spellcasting::None => [0; 9],
+            Spellcasting::Full => FULL_CASTER_SLOTS[level as usize - 1],
+            // half casters start casting at 2nd level and use half their level, rounded up
+            Spellcasting::Half if level < 2 => [0; 9],
+            Spellcasting::Half => FULL_CASTER_SLOTS[level.div_ceil(2) as usize - 1],
+            // pact magic slots are all the same level
+            Spellcasting::Pact => {
+                let count = match level {
+                    1 => 1,
+                    2..=10 => 2,
+                    11..=16 => 3,
+                    _ => 4,
+                };
+                let slot_level = level.div_ceil(2).min(5);
+
+                let mut slots = [0; 9];
+                slots[slot_level as usize - 1] = count;
+                slots
+            },
+        }
+    }
+}
@@ -0,0 +1,783 @@
+use std::collections::HashSet;
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use dnd_character::ability::Ability;
+use dnd_character::class::get_expertise_count;
+use dnd_character::creator::{Draft, Step};
+use dnd_character::dice::generate_ability_scores;
+use dnd_character::equipment::describe_option;
+use dnd_character::{random_name, AdvancementMode, Background, Character, Class, Race, Skill, Subrace};
+
+// the builder's screens, one for each creator step that applies, except that every starting
+// equipment choice and every personality table gets a screen of its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Race,
+    Subrace,
+    Name,
+    Class,
+    Background,
+    Abilities,
+    AbilityChoices,
+    Skills,
+    Expertise,
+    Languages,
+    Traits,
+    Ideal,
+    Bond,
+    Flaw,
+    Equipment(usize),
+    Advancement,
+    Review,
+}
+
+impl Screen {
+    fn title(&self) -> String {
+        match self {
+            Screen::Race => "Race".to_string(),
+            Screen::Subrace => "Subrace".to_string(),
+            Screen::Name => "Name".to_string(),
+            Screen::Class => "Class".to_string(),
+            Screen::Background => "Background".to_string(),
+            Screen::Abilities => "Ability Scores".to_string(),
+            Screen::AbilityChoices => "Ability Bonuses".to_string(),
+            Screen::Skills => "Skills".to_string(),
+            Screen::Expertise => "Expertise".to_string(),
+            Screen::Languages => "Languages".to_string(),
+            Screen::Traits => "Personality Traits".to_string(),
+            Screen::Ideal => "Ideal".to_string(),
+            Screen::Bond => "Bond".to_string(),
+            Screen::Flaw => "Flaw".to_string(),
+            Screen::Equipment(i) => format!("Equipment {}", i + 1),
+            Screen::Advancement => "Advancement".to_string(),
+            Screen::Review => "Review".to_string(),
+        }
+    }
+
+    // the creator step the screen's choices belong to
+    fn step(&self) -> Step {
+        match self {
+            Screen::Race => Step::Race,
+            Screen::Subrace => Step::Subrace,
+            Screen::Name => Step::Name,
+            Screen::Class => Step::Class,
+            Screen::Background => Step::Background,
+            Screen::Abilities => Step::AbilityScores,
+            Screen::AbilityChoices => Step::AbilityChoices,
+            Screen::Skills => Step::Skills,
+            Screen::Expertise => Step::Expertise,
+            Screen::Languages => Step::Languages,
+            Screen::Traits | Screen::Ideal | Screen::Bond | Screen::Flaw => Step::Personality,
+            Screen::Equipment(_) => Step::Equipment,
+            Screen::Advancement => Step::Advancement,
+            Screen::Review => Step::Review,
+        }
+    }
+}
+
+// an entry in a screen's list with the text shown in the description pane
+struct Choice {
+    label: String,
+    description: Vec<String>,
+}
+
+impl Choice {
+    fn new(label: impl Into<String>, description: Vec<String>) -> Choice {
+        Choice { label: label.into(), description }
+    }
+}
+
+fn signed(n: i8) -> String {
+    format!("{:+}", n)
+}
+
+fn bonus_list(bonuses: &[(Ability, i8)]) -> String {
+    let mut bonuses = bonuses.to_vec();
+    bonuses.sort_by_key(|&(ability, _)| Ability::ALL.iter().position(|&a| a == ability));
+    let parts: Vec<String> =
+        bonuses.iter().map(|(ability, bonus)| format!("{:?} {}", ability, signed(*bonus))).collect();
+    parts.join(", ")
+}
+
+fn skill_list<'a>(skills: impl IntoIterator<Item = &'a Skill>) -> String {
+    let names: Vec<String> = skills.into_iter().map(|s| format!("{:?}", s)).collect();
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn race_choice(race: Race) -> Choice {
+    let bonuses: Vec<(Ability, i8)> = race.ability_bonuses().into_iter().collect();
+    let languages: Vec<String> = race.languages().iter().map(|l| format!("{:?}", l)).collect();
+
+    let mut description = vec![
+        race.description().to_string(),
+        String::new(),
+        format!("Ability bonuses: {}", bonus_list(&bonuses)),
+        format!("Speed: {} ft.", race.speed()),
+        format!("Languages: {}", languages.join(", ")),
+    ];
+    if race.extra_languages() > 0 {
+        description.push(format!("Extra languages: {}", race.extra_languages()));
+    }
+    if !race.racial_skills().is_empty() {
+        description.push(format!("Skills: {}", skill_list(&race.racial_skills())));
+    }
+    if !race.damage_resistances().is_empty() {
+        description.push(format!("Resistances: {:?}", race.damage_resistances()));
+    }
+    if race.ability_choices() > 0 {
+        description.push(format!("Plus +1 to {} abilities without a racial bonus.", race.ability_choices()));
+    }
+    Choice::new(race.to_string(), description)
+}
+
+fn subrace_choice(subrace: Subrace) -> Choice {
+    let bonuses: Vec<(Ability, i8)> = subrace.ability_bonuses().into_iter().collect();
+    let mut description =
+        vec![subrace.description().to_string(), String::new(), format!("Ability bonuses: {}", bonus_list(&bonuses))];
+    if subrace.speed_bonus() > 0 {
+        description.push(format!("Speed: +{} ft.", subrace.speed_bonus()));
+    }
+    if subrace.hit_points_per_level() > 0 {
+        description.push(format!("Hit points: +{} per level", subrace.hit_points_per_level()));
+    }
+    if !subrace.damage_resistances().is_empty() {
+        description.push(format!("Resistances: {:?}", subrace.damage_resistances()));
+    }
+    Choice::new(subrace.to_string(), description)
+}
+
+fn class_choice(class: Class) -> Choice {
+    let (first, second) = class.saving_throws();
+    Choice::new(
+        class.to_string(),
+        vec![
+            class.description().to_string(),
+            String::new(),
+            format!("Hit die: d{}", class.hit_die()),
+            format!("Primary ability: {:?}", class.primary_ability()),
+            format!("Saving throws: {:?}, {:?}", first, second),
+        ],
+    )
+}
+
+fn background_choice(background: Background) -> Choice {
+    let (feature, feature_description) = background.feature();
+    let mut description = vec![
+        background.description().to_string(),
+        String::new(),
+        format!("Skills: {}", skill_list(&background.skills())),
+    ];
+    if !background.tool_proficiencies().is_empty() {
+        description.push(format!("Tools: {}", background.tool_proficiencies().join(", ")));
+    }
+    if background.extra_languages() > 0 {
+        description.push(format!("Extra languages: {}", background.extra_languages()));
+    }
+    description.push(format!("Feature: {}", feature));
+    description.push(feature_description.to_string());
+    Choice::new(background.to_string(), description)
+}
+
+const ADVANCEMENT_MODES: [AdvancementMode; 2] = [AdvancementMode::Experience, AdvancementMode::Milestone];
+
+fn advancement_choice(mode: AdvancementMode) -> Choice {
+    match mode {
+        AdvancementMode::Experience => {
+            Choice::new("Experience points", vec!["Level up on reaching each experience point threshold.".to_string()])
+        },
+        AdvancementMode::Milestone => {
+            Choice::new("Milestones", vec!["Level up when the DM says so, experience isn't tracked.".to_string()])
+        },
+    }
+}
+
+// add or remove an entry of a multiple choice screen, never going over the number of picks
+fn flip<T: PartialEq>(chosen: &mut Vec<T>, item: T, limit: usize) {
+    match chosen.iter().position(|c| *c == item) {
+        Some(i) => {
+            chosen.remove(i);
+        },
+        None if chosen.len() < limit => chosen.push(item),
+        None => {},
+    }
+}
+
+fn flip_skill(chosen: &mut HashSet<Skill>, skill: Skill, limit: usize) {
+    if !chosen.remove(&skill) && chosen.len() < limit {
+        chosen.insert(skill);
+    }
+}
+
+struct App {
+    draft: Draft,
+    screen: usize,
+    list: ListState,
+    // shown in place of the key help until the next key press
+    message: Option<String>,
+}
+
+impl App {
+    fn new() -> App {
+        let draft = Draft { rolls: Some(generate_ability_scores()), ..Draft::default() };
+        let mut app = App { draft, screen: 0, list: ListState::default(), message: None };
+        app.enter_screen();
+        app
+    }
+
+    // the screens for the choices made so far
+    fn screens(&self) -> Vec<Screen> {
+        let mut screens = Vec::new();
+        for step in Step::ALL.into_iter().filter(|&step| self.draft.applies(step)) {
+            match step {
+                Step::Race => screens.push(Screen::Race),
+                Step::Subrace => screens.push(Screen::Subrace),
+                Step::Name => screens.push(Screen::Name),
+                Step::Class => screens.push(Screen::Class),
+                Step::Background => screens.push(Screen::Background),
+                Step::AbilityScores => screens.push(Screen::Abilities),
+                Step::AbilityChoices => screens.push(Screen::AbilityChoices),
+                Step::Skills => screens.push(Screen::Skills),
+                Step::Expertise => screens.push(Screen::Expertise),
+                Step::Languages => screens.push(Screen::Languages),
+                Step::Personality => screens.extend([Screen::Traits, Screen::Ideal, Screen::Bond, Screen::Flaw]),
+                // choices with a single option are given without asking
+                Step::Equipment => {
+                    if let Some(class) = self.draft.class {
+                        for (i, choice) in class.starting_equipment().iter().enumerate() {
+                            if choice.len() > 1 {
+                                screens.push(Screen::Equipment(i));
+                            }
+                        }
+                    }
+                },
+                Step::Advancement => screens.push(Screen::Advancement),
+                Step::Review => screens.push(Screen::Review),
+            }
+        }
+        screens
+    }
+
+    fn current(&self) -> Screen {
+        self.screens()[self.screen]
+    }
+
+    fn background_table(&self, screen: Screen) -> Vec<&'static str> {
+        match (self.draft.background, screen) {
+            (Some(background), Screen::Traits) => background.personality_traits(),
+            (Some(background), Screen::Ideal) => background.ideals(),
+            (Some(background), Screen::Bond) => background.bonds(),
+            (Some(background), Screen::Flaw) => background.flaws(),
+            _ => Vec::new(),
+        }
+    }
+
+    // how many entries the screen needs, 1 for a single choice and 0 for screens without a list
+    fn picks(&self, screen: Screen) -> usize {
+        match screen {
+            Screen::Race | Screen::Subrace | Screen::Class | Screen::Background | Screen::Equipment(_) => 1,
+            Screen::Ideal | Screen::Bond | Screen::Flaw | Screen::Advancement => 1,
+            Screen::Traits => 2,
+            Screen::AbilityChoices => self.draft.ability_choice_count(),
+            Screen::Skills => {
+                let (available, remaining) = self.draft.skill_choices();
+                remaining.min(available.len())
+            },
+            Screen::Expertise => self.draft.class.map_or(0, |c| get_expertise_count(&c)),
+            Screen::Languages => self.draft.extra_languages().min(self.draft.language_choices().len()),
+            Screen::Abilities | Screen::Name | Screen::Review => 0,
+        }
+    }
+
+    fn options(&self, screen: Screen) -> Vec<Choice> {
+        let draft = &self.draft;
+        match screen {
+            Screen::Race => Race::all().into_iter().map(race_choice).collect(),
+            Screen::Subrace => {
+                draft.race.map(|r| r.subraces()).unwrap_or_default().into_iter().map(subrace_choice).collect()
+            },
+            Screen::Class => Class::all().into_iter().map(class_choice).collect(),
+            Screen::Background => Background::all().into_iter().map(background_choice).collect(),
+            Screen::AbilityChoices => draft
+                .ability_choice_options()
+                .into_iter()
+                .map(|a| Choice::new(format!("{:?}", a), vec![format!("+1 {:?}", a)]))
+                .collect(),
+            Screen::Skills => draft
+                .skill_choices()
+                .0
+                .into_iter()
+                .map(|s| Choice::new(format!("{:?}", s), vec![format!("Uses {:?}.", s.associated_ability())]))
+                .collect(),
+            Screen::Expertise => draft
+                .expertise_choices()
+                .into_iter()
+                .map(|s| {
+                    Choice::new(format!("{:?}", s), vec!["Doubles your proficiency bonus for this skill.".to_string()])
+                })
+                .collect(),
+            Screen::Languages => {
+                draft.language_choices().into_iter().map(|l| Choice::new(format!("{:?}", l), Vec::new())).collect()
+            },
+            Screen::Equipment(i) => match draft.class {
+                Some(class) => class.starting_equipment()[i]
+                    .iter()
+                    .map(|option| Choice::new(describe_option(option), Vec::new()))
+                    .collect(),
+                None => Vec::new(),
+            },
+            Screen::Traits | Screen::Ideal | Screen::Bond | Screen::Flaw => self
+                .background_table(screen)
+                .into_iter()
+                .map(|entry| Choice::new(entry, vec![entry.to_string()]))
+                .collect(),
+            Screen::Advancement => ADVANCEMENT_MODES.into_iter().map(advancement_choice).collect(),
+            Screen::Abilities | Screen::Name | Screen::Review => Vec::new(),
+        }
+    }
+
+    // indices of the screen's options that are currently chosen
+    fn chosen(&self, screen: Screen) -> Vec<usize> {
+        fn positions<T>(options: &[T], chosen: impl Fn(&T) -> bool) -> Vec<usize> {
+            options.iter().enumerate().filter(|(_, option)| chosen(option)).map(|(i, _)| i).collect()
+        }
+
+        let draft = &self.draft;
+        let personality = draft.personality.clone().unwrap_or_default();
+        let table = self.background_table(screen);
+        match screen {
+            Screen::Race => positions(&Race::all(), |&r| draft.race == Some(r)),
+            Screen::Subrace => {
+                positions(&draft.race.map(|r| r.subraces()).unwrap_or_default(), |&s| draft.subrace == Some(s))
+            },
+            Screen::Class => positions(&Class::all(), |&c| draft.class == Some(c)),
+            Screen::Background => positions(&Background::all(), |&b| draft.background == Some(b)),
+            Screen::AbilityChoices => positions(&draft.ability_choice_options(), |a| draft.ability_choices.contains(a)),
+            Screen::Skills => positions(&draft.skill_choices().0, |s| draft.skills.contains(s)),
+            Screen::Expertise => positions(&draft.expertise_choices(), |s| draft.expertise.contains(s)),
+            Screen::Languages => positions(&draft.language_choices(), |l| draft.languages.contains(l)),
+            Screen::Equipment(i) => draft.equipment.get(i).copied().into_iter().collect(),
+            Screen::Traits => positions(&table, |&entry| personality.traits.iter().any(|t| t == entry)),
+            Screen::Ideal => positions(&table, |&entry| personality.ideal == entry),
+            Screen::Bond => positions(&table, |&entry| personality.bond == entry),
+            Screen::Flaw => positions(&table, |&entry| personality.flaw == entry),
+            Screen::Advancement => positions(&ADVANCEMENT_MODES, |&mode| draft.advancement == mode),
+            Screen::Abilities | Screen::Name | Screen::Review => Vec::new(),
+        }
+    }
+
+    // set a single choice, the draft clears anything that depended on the old one
+    fn choose(&mut self, screen: Screen, index: usize) {
+        let entry = self.background_table(screen).get(index).map(|entry| entry.to_string());
+        let draft = &mut self.draft;
+        match screen {
+            Screen::Race => draft.set_race(Race::all()[index]),
+            Screen::Subrace => draft.subrace = draft.race.map(|r| r.subraces()[index]),
+            Screen::Class => draft.set_class(Class::all()[index]),
+            Screen::Background => draft.set_background(Background::all()[index]),
+            Screen::Equipment(i) => draft.equipment[i] = index,
+            Screen::Ideal | Screen::Bond | Screen::Flaw => {
+                let personality = draft.personality.get_or_insert_with(Default::default);
+                let field = match screen {
+                    Screen::Ideal => &mut personality.ideal,
+                    Screen::Bond => &mut personality.bond,
+                    _ => &mut personality.flaw,
+                };
+                *field = entry.unwrap_or_default();
+            },
+            Screen::Advancement => draft.advancement = ADVANCEMENT_MODES[index],
+            _ => {},
+        }
+    }
+
+    // add or remove an entry of a multiple choice screen
+    fn toggle(&mut self, screen: Screen, index: usize) {
+        let limit = self.picks(screen);
+        let table = self.background_table(screen);
+        let draft = &mut self.draft;
+        match screen {
+            Screen::AbilityChoices => {
+                let ability = draft.ability_choice_options()[index];
+                flip(&mut draft.ability_choices, ability, limit);
+            },
+            Screen::Skills => {
+                let skill = draft.skill_choices().0[index];
+                flip_skill(&mut draft.skills, skill, limit);
+                draft.expertise.remove(&skill);
+            },
+            Screen::Expertise => {
+                let skill = draft.expertise_choices()[index];
+                flip_skill(&mut draft.expertise, skill, limit);
+            },
+            Screen::Languages => {
+                let language = draft.language_choices()[index];
+                flip(&mut draft.languages, language, limit);
+            },
+            Screen::Traits => {
+                let personality = draft.personality.get_or_insert_with(Default::default);
+                flip(&mut personality.traits, table[index].to_string(), limit);
+            },
+            _ => {},
+        }
+    }
+
+    // the live character sheet pane
+    fn sheet(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let draft = &self.draft;
+
+        let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        lines.push(Line::from(format!("Name: {}", draft.name.as_deref().filter(|n| !n.is_empty()).unwrap_or("-"))));
+        lines.push(Line::from(format!(
+            "Race: {}{}",
+            or_dash(draft.race.map(|r| r.to_string())),
+            draft.subrace.map(|s| format!(" ({})", s)).unwrap_or_default()
+        )));
+        lines.push(Line::from(format!("Class: {}", or_dash(draft.class.map(|c| c.to_string())))));
+        lines.push(Line::from(format!("Background: {}", or_dash(draft.background.map(|b| b.to_string())))));
+        lines.push(Line::from(""));
+
+        if let Some(abilities) = draft.abilities() {
+            for ability in Ability::ALL {
+                lines.push(Line::from(format!(
+                    "{:<13} {:>2} ({})",
+                    format!("{:?}", ability),
+                    abilities.score(ability),
+                    signed(abilities.modifier(ability))
+                )));
+            }
+        }
+
+        let character = match draft.preview() {
+            Some(character) => character,
+            None => return lines,
+        };
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Hit Points: {}   Armor Class: {}   Speed: {} ft.",
+            character.hit_points.max,
+            character.armor_class(),
+            character.speed()
+        )));
+
+        let saves: Vec<&Ability> = Ability::ALL.iter().filter(|a| character.saving_throws.contains(a)).collect();
+        lines.push(Line::from(format!("Saving Throws: {:?}", saves)));
+
+        let skills: Vec<String> = Skill::ALL
+            .into_iter()
+            .filter(|s| character.skills.contains(s))
+            .map(|s| {
+                let marker = if character.expertise.contains(&s) { "*" } else { "" };
+                format!("{:?}{} {}", s, marker, signed(character.skill_modifier(s)))
+            })
+            .collect();
+        lines.push(Line::from(format!("Skills: {}", skills.join(", "))));
+
+        let languages: Vec<String> = character.languages.iter().map(|l| format!("{:?}", l)).collect();
+        lines.push(Line::from(format!("Languages: {}", languages.join(", "))));
+        if !character.tool_proficiencies.is_empty() {
+            lines.push(Line::from(format!("Tools: {}", character.tool_proficiencies.join(", "))));
+        }
+
+        if !character.equipment.is_empty() {
+            let items: Vec<String> =
+                character
+                    .equipment
+                    .iter()
+                    .map(|item| {
+                        if item.quantity > 1 {
+                            format!("{} x{}", item.name, item.quantity)
+                        } else {
+                            item.name.clone()
+                        }
+                    })
+                    .collect();
+            lines.push(Line::from(format!("Equipment: {}", items.join(", "))));
+        }
+
+        let personality = &character.personality;
+        for trait_text in &personality.traits {
+            lines.push(Line::from(format!("Trait: {}", trait_text)));
+        }
+        for (label, text) in [("Ideal", &personality.ideal), ("Bond", &personality.bond), ("Flaw", &personality.flaw)] {
+            if !text.is_empty() {
+                lines.push(Line::from(format!("{}: {}", label, text)));
+            }
+        }
+
+        lines
+    }
+
+    // put the cursor on the current choice when arriving at a screen
+    fn enter_screen(&mut self) {
+        let screen = self.current();
+        let cursor = self.chosen(screen).first().copied().unwrap_or(0);
+        let has_options = !self.options(screen).is_empty();
+        self.list.select(if has_options { Some(cursor) } else { None });
+    }
+
+    fn back(&mut self) {
+        if self.screen > 0 {
+            self.screen -= 1;
+            self.enter_screen();
+        }
+    }
+
+    fn next(&mut self) {
+        let screens = self.screens();
+        let screen = screens[self.screen];
+
+        // a step split over several screens is checked by the draft on its last one
+        let last_of_step = screens.get(self.screen + 1).is_none_or(|next| next.step() != screen.step());
+        let complete = if last_of_step {
+            self.draft.is_complete(screen.step())
+        } else {
+            self.chosen(screen).len() == self.picks(screen)
+        };
+        if !complete {
+            self.message = Some(match screen {
+                Screen::Name => "Enter a name first (Tab suggests one).".to_string(),
+                _ => format!("Choose {} before continuing.", self.picks(screen)),
+            });
+            return;
+        }
+
+        // the screens can change with the choice just made
+        self.screen = (self.screen + 1).min(self.screens().len() - 1);
+        self.enter_screen();
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let count = self.options(self.current()).len();
+        if count == 0 {
+            return;
+        }
+        let cursor = self.list.selected().unwrap_or(0) as isize;
+        self.list.select(Some((cursor + delta).rem_euclid(count as isize) as usize));
+    }
+
+    // returns the finished character once Enter is pressed on the review screen
+    fn confirm(&mut self) -> Option<Character> {
+        let screen = self.current();
+        match (screen, self.list.selected()) {
+            (Screen::Review, _) => {
+                let character = self.draft.build();
+                if character.is_none() {
+                    self.message = Some("Some choices are still missing, go back and make them.".to_string());
+                }
+                return character;
+            },
+            (_, Some(index)) if self.picks(screen) == 1 => self.choose(screen, index),
+            _ => {},
+        }
+        self.next();
+        None
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(frame.area());
+        let [header, body, details, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(10),
+            Constraint::Length(1),
+        ])
+        .areas(left);
+
+        let screen = self.current();
+        let screens = self.screens();
+        let progress = format!("Step {}/{}: {}", self.screen + 1, screens.len(), screen.title());
+        frame.render_widget(
+            Paragraph::new(progress).block(Block::default().borders(Borders::ALL).title("Character Builder")),
+            header,
+        );
+
+        match screen {
+            Screen::Abilities => self.draw_abilities(frame, body),
+            Screen::Name => self.draw_name(frame, body),
+            Screen::Review => self.draw_review(frame, body),
+            _ => self.draw_list(frame, body),
+        }
+
+        let options = self.options(screen);
+        let description: Vec<Line> = match self.list.selected().and_then(|i| options.get(i)) {
+            Some(choice) => choice.description.iter().map(|l| Line::from(l.clone())).collect(),
+            None => Vec::new(),
+        };
+        frame.render_widget(
+            Paragraph::new(description)
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Details")),
+            details,
+        );
+
+        let keys = match screen {
+            Screen::Name => "type a name  Tab suggest  Enter next  Esc back  Ctrl-C quit",
+            Screen::Review => "Enter create character  Esc back  q quit",
+            _ if self.picks(screen) > 1 => "Up/Down move  Space toggle  Enter next  Esc back  q quit",
+            _ => "Up/Down move  Enter choose  Esc back  q quit",
+        };
+        let help_text = self.message.clone().unwrap_or_else(|| keys.to_string());
+        frame.render_widget(Paragraph::new(help_text).style(Style::default().fg(Color::DarkGray)), help);
+
+        frame.render_widget(
+            Paragraph::new(self.sheet())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Character Sheet")),
+            right,
+        );
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let screen = self.current();
+        let picks = self.picks(screen);
+        let chosen = self.chosen(screen);
+
+        let items: Vec<ListItem> = self
+            .options(screen)
+            .into_iter()
+            .enumerate()
+            .map(|(i, choice)| {
+                let marker = match (picks > 1, chosen.contains(&i)) {
+                    (true, true) => "[x] ",
+                    (true, false) => "[ ] ",
+                    (false, true) => "* ",
+                    (false, false) => "  ",
+                };
+                ListItem::new(format!("{}{}", marker, choice.label))
+            })
+            .collect();
+
+        let title =
+            if picks > 1 { format!("Choose {} ({}/{})", picks, chosen.len(), picks) } else { "Choose one".to_string() };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.list);
+    }
+
+    fn draw_abilities(&self, frame: &mut Frame, area: Rect) {
+        let (rolls, totals) = match (&self.draft.rolls, self.draft.abilities()) {
+            (Some(rolls), Some(totals)) => (rolls, totals),
+            _ => return,
+        };
+        let mut lines = vec![Line::from("Rolled 4d6, dropping the lowest die:"), Line::from("")];
+        for ability in Ability::ALL {
+            let bonus = totals.score(ability).value() as i16 - rolls.score(ability).value() as i16;
+            let bonus = if bonus == 0 { String::new() } else { format!(" {:+} racial", bonus) };
+            lines.push(Line::from(format!(
+                "{:<13} {:>2}{} = {}",
+                format!("{:?}", ability),
+                rolls.score(ability),
+                bonus,
+                totals.score(ability)
+            )));
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Ability Scores")),
+            area,
+        );
+    }
+
+    fn draw_name(&self, frame: &mut Frame, area: Rect) {
+        let text = vec![
+            Line::from("Enter your character's name:"),
+            Line::from(""),
+            Line::from(format!("> {}_", self.draft.name.as_deref().unwrap_or(""))),
+        ];
+        frame.render_widget(Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Name")), area);
+    }
+
+    fn draw_review(&self, frame: &mut Frame, area: Rect) {
+        let text = vec![
+            Line::from("Check the character sheet on the right."),
+            Line::from(""),
+            Line::from("Press Enter to create the character, or Esc to go back and change something."),
+        ];
+        frame.render_widget(
+            Paragraph::new(text)
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Review")),
+            area,
+        );
+    }
+
+    // None when the player quits
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<Option<Character>> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            self.message = None;
+
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return Ok(None);
+            }
+
+            let screen = self.current();
+            if screen == Screen::Name {
+                match key.code {
+                    KeyCode::Char(c) => self.draft.name.get_or_insert_with(String::new).push(c),
+                    KeyCode::Backspace => {
+                        if let Some(name) = &mut self.draft.name {
+                            name.pop();
+                        }
+                    },
+                    KeyCode::Tab => {
+                        if let Some(race) = self.draft.race {
+                            self.draft.name = Some(random_name(race, &mut rand::thread_rng()));
+                        }
+                    },
+                    KeyCode::Enter => self.next(),
+                    KeyCode::Esc => self.back(),
+                    _ => {},
+                }
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') => return Ok(None),
+                KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+                KeyCode::Char(' ') => {
+                    if let Some(index) = self.list.selected() {
+                        if self.picks(screen) > 1 {
+                            self.toggle(screen, index);
+                        }
+                    }
+                },
+                KeyCode::Enter | KeyCode::Right => {
+                    if let Some(character) = self.confirm() {
+                        return Ok(Some(character));
+                    }
+                },
+                KeyCode::Esc | KeyCode::Left | KeyCode::Backspace => self.back(),
+                _ => {},
+            }
+        }
+    }
+}
+
+// run the full-screen builder, None if the player quit before finishing
+pub fn build_character() -> io::Result<Option<Character>> {
+    let mut terminal = ratatui::init();
+    let result = App::new().run(&mut terminal);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,168 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::ability::Ability;
+use crate::advancement::{level_for_xp, AdvancementMode, MAX_LEVEL, XP_THRESHOLDS};
+use crate::character::Character;
+use crate::class::{get_class_skills, get_skill_proficiency_count, granted_skills};
+use crate::skill::Skill;
+
+// how bad a broken rule is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    // allowed by some table rule or magic item, but worth a second look
+    Warning,
+    // the sheet can't be right as it stands
+    Error,
+}
+
+// a rule the character breaks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.message),
+            Severity::Error => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+impl Character {
+    // check the rules the creator and level-up enforce, for characters edited by hand or imported
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut report = |severity: Severity, message: String| violations.push(Violation { severity, message });
+
+        // level and experience
+        if !(1..=MAX_LEVEL).contains(&self.level) {
+            report(Severity::Error, format!("level {} is outside 1-{}", self.level, MAX_LEVEL));
+        } else if self.advancement == AdvancementMode::Experience && level_for_xp(self.experience) < self.level {
+            report(
+                Severity::Warning,
+                format!(
+                    "level {} needs {} XP but the character has {}",
+                    self.level,
+                    XP_THRESHOLDS[self.level as usize - 1],
+                    self.experience
+                ),
+            );
+        }
+
+        // ability scores: 20 is the most the rules give, scores above 30 never load
+        for ability in Ability::ALL {
+            let score = self.abilities.score(ability).value();
+            if score > 20 {
+                report(Severity::Warning, format!("{:?} {} is above 20", ability, score));
+            }
+        }
+
+        // saving throws come from the class
+        let (first, second) = self.class.saving_throws();
+        let expected: HashSet<Ability> = [first, second].into();
+        if self.saving_throws != expected {
+            report(Severity::Error, format!("{} saving throws should be {:?} and {:?}", self.class, first, second));
+        }
+
+        // skills: background and racial ones are free, the rest come from the class list
+        let granted = granted_skills(self.background, self.race);
+        let class_skills = get_class_skills(&self.class);
+        let mut chosen: Vec<Skill> =
+            Skill::ALL.into_iter().filter(|s| self.skills.contains(s) && !granted.contains(s)).collect();
+        for skill in chosen.iter().filter(|s| !class_skills.contains(s)) {
+            report(
+                Severity::Warning,
+                format!("{:?} isn't a {} skill or granted by race or background", skill, self.class),
+            );
+        }
+        chosen.retain(|s| class_skills.contains(s));
+        let allowed = get_skill_proficiency_count(&self.class).saturating_sub(granted.len());
+        if chosen.len() > allowed {
+            report(Severity::Error, format!("{} class skills chosen, only {} allowed", chosen.len(), allowed));
+        }
+        for skill in Skill::ALL.into_iter().filter(|s| granted.contains(s) && !self.skills.contains(s)) {
+            report(Severity::Warning, format!("missing {:?}, granted by race or background", skill));
+        }
+
+        // expertise needs proficiency, and only as many as the class has given by now
+        for skill in Skill::ALL.into_iter().filter(|s| self.expertise.contains(s) && !self.skills.contains(s)) {
+            report(Severity::Error, format!("expertise in {:?} without proficiency in it", skill));
+        }
+        let expertise_allowed: usize = (1..=self.level).map(|level| self.class.expertise_at(level) as usize).sum();
+        if self.expertise.len() > expertise_allowed {
+            report(
+                Severity::Error,
+                format!(
+                    "{} expertise picks, a level {} {} has {}",
+                    self.expertise.len(),
+                    self.level,
+                    self.class,
+                    expertise_allowed
+                ),
+            );
+        }
+
+        // subrace belongs to the race
+        match self.subrace {
+            Some(subrace) if !self.race.subraces().contains(&subrace) => {
+                report(Severity::Error, format!("{} isn't a subrace of {}", subrace, self.race));
+            },
+            None if !self.race.subraces().is_empty() => {
+                report(Severity::Warning, format!("{} without a subrace", self.race))
+            },
+            _ => {},
+        }
+
+        for language in self.race.languages().into_iter().filter(|l| !self.languages.contains(l)) {
+            report(Severity::Warning, format!("missing {:?}, known by every {}", language, self.race));
+        }
+
+        // hit points: at least 1 per level, at most a full hit die per level
+        let level = self.level.max(1) as i32;
+        let con_modifier = self.abilities.score(Ability::Constitution).modifier();
+        let toughness = self.subrace.map_or(0, |s| s.hit_points_per_level()) as i32;
+        let most = level * (con_modifier.hit_points(self.class.hit_die()) as i32 + toughness);
+        if (self.hit_points.max as i32) < level {
+            report(Severity::Error, format!("{} maximum hit points is less than 1 per level", self.hit_points.max));
+        } else if self.hit_points.max as i32 > most {
+            report(
+                Severity::Warning,
+                format!(
+                    "{} maximum hit points is more than the {} a level {} {} can roll",
+                    self.hit_points.max, most, self.level, self.class
+                ),
+            );
+        }
+        if self.hit_points.current > self.hit_points.max {
+            report(
+                Severity::Error,
+                format!(
+                    "{} current hit points is above the maximum of {}",
+                    self.hit_points.current, self.hit_points.max
+                ),
+            );
+        }
+
+        if self.hit_dice_spent > self.level {
+            report(Severity::Error, format!("{} hit dice spent but only {} in total", self.hit_dice_spent, self.level));
+        }
+
+        let slots = self.class.spell_slots(self.level);
+        for (i, (&used, &max)) in self.spell_slots_used.iter().zip(&slots).enumerate() {
+            if used > max {
+                report(
+                    Severity::Error,
+                    format!("{} level {} spell slots used, {} has {}", used, i + 1, self.class, max),
+                );
+            }
+        }
+
+        violations.sort_by_key(|v| Reverse(v.severity));
+        violations
+    }
+}
@@ -0,0 +1,746 @@
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashSet};
+
+use crate::ability::{Ability, AbilityScore, AbilityScores};
+use crate::advancement::AdvancementMode;
+use crate::background::{Background, Personality};
+use crate::character::Character;
+use crate::class::Class;
+use crate::death::{DeathSaves, LifeState};
+use crate::equipment::{find_item, Armor, ArmorCategory, Item, ItemKind, Weapon, WeaponCategory};
+use crate::feat::Feat;
+use crate::hit_points::{DamageType, HitPoints};
+use crate::language::Language;
+use crate::race::{Race, Subrace};
+use crate::skill::Skill;
+use crate::{saved_name, sorted_names};
+
+// the JSON schema the generic format follows
+pub const GENERIC_SCHEMA: &str = include_str!("../data/vtt/generic-character.schema.json");
+
+// virtual tabletop formats we can read and write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VttFormat {
+    // a dnd5e actor as exported from Foundry VTT
+    Foundry,
+    // the tool-neutral format described by GENERIC_SCHEMA
+    Generic,
+}
+
+// a converted character or actor, with everything the other side had no room for
+#[derive(Debug, Clone)]
+pub struct Conversion<T> {
+    pub value: T,
+    pub unsupported: Vec<String>,
+}
+
+const FEATS: [Feat; 3] = [Feat::Alert, Feat::Mobile, Feat::Observant];
+
+// every path the Foundry import reads, "*" standing for any one key; values it works out
+// itself (skill abilities, slot maximums) and document metadata count as read
+const FOUNDRY_FIELDS: [&str; 34] = [
+    "_id",
+    "_stats",
+    "flags",
+    "folder",
+    "img",
+    "ownership",
+    "prototypeToken",
+    "sort",
+    "name",
+    "type",
+    "items",
+    "effects",
+    "system.abilities.*.value",
+    "system.abilities.*.proficient",
+    "system.skills.*.value",
+    "system.skills.*.ability",
+    "system.attributes.hp.value",
+    "system.attributes.hp.max",
+    "system.attributes.hp.temp",
+    "system.attributes.death.success",
+    "system.attributes.death.failure",
+    "system.details.xp.value",
+    "system.details.trait",
+    "system.details.ideal",
+    "system.details.bond",
+    "system.details.flaw",
+    "system.details.biography.value",
+    "system.traits.languages.value",
+    "system.traits.dr.value",
+    "system.traits.dv.value",
+    "system.traits.toolProf.custom",
+    "system.spells.*.value",
+    "system.spells.*.max",
+    "system.currency",
+];
+
+// every path the generic import reads
+const GENERIC_FIELDS: [&str; 33] = [
+    "format",
+    "version",
+    "name",
+    "race",
+    "subrace",
+    "class",
+    "background",
+    "level",
+    "experience",
+    "milestone",
+    "abilities.strength",
+    "abilities.dexterity",
+    "abilities.constitution",
+    "abilities.intelligence",
+    "abilities.wisdom",
+    "abilities.charisma",
+    "hit_points.current",
+    "hit_points.max",
+    "hit_points.temp",
+    "hit_dice_spent",
+    "saving_throws",
+    "skills",
+    "expertise",
+    "languages",
+    "tools",
+    "resistances",
+    "vulnerabilities",
+    "feats",
+    "equipment",
+    "spells",
+    "spell_slots_used",
+    "personality",
+    "notes",
+];
+
+fn ability_code(ability: Ability) -> &'static str {
+    match ability {
+        Ability::Strength => "str",
+        Ability::Dexterity => "dex",
+        Ability::Constitution => "con",
+        Ability::Intelligence => "int",
+        Ability::Wisdom => "wis",
+        Ability::Charisma => "cha",
+    }
+}
+
+fn skill_code(skill: Skill) -> &'static str {
+    match skill {
+        Skill::Acrobatics => "acr",
+        Skill::AnimalHandling => "ani",
+        Skill::Arcana => "arc",
+        Skill::Athletics => "ath",
+        Skill::Deception => "dec",
+        Skill::History => "his",
+        Skill::Insight => "ins",
+        Skill::Intimidation => "itm",
+        Skill::Investigation => "inv",
+        Skill::Medicine => "med",
+        Skill::Nature => "nat",
+        Skill::Perception => "prc",
+        Skill::Performance => "prf",
+        Skill::Persuasion => "per",
+        Skill::Religion => "rel",
+        Skill::SleightOfHand => "slt",
+        Skill::Stealth => "ste",
+        Skill::Survival => "sur",
+    }
+}
+
+fn language_code(language: Language) -> String {
+    match language {
+        Language::DeepSpeech => "deep".to_string(),
+        _ => format!("{:?}", language).to_lowercase(),
+    }
+}
+
+fn parse_name<T: DeserializeOwned>(name: &str) -> Option<T> {
+    serde_json::from_value(Value::String(name.to_string())).ok()
+}
+
+// a value at a dotted path, e.g. "system.attributes.hp.value"
+fn at<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(json, |value, key| value.get(key))
+}
+
+fn number_at(json: &Value, path: &str) -> Option<u64> {
+    at(json, path).and_then(Value::as_u64)
+}
+
+fn text_at(json: &Value, path: &str) -> Option<String> {
+    at(json, path).and_then(Value::as_str).map(String::from)
+}
+
+fn strings_at(json: &Value, path: &str) -> Vec<String> {
+    at(json, path)
+        .and_then(Value::as_array)
+        .map(|values| values.iter().filter_map(Value::as_str).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+// whether a known path reaches a key: Some(true) if it names the key, Some(false) if it goes on below it
+fn reaches(known: &str, path: &str) -> Option<bool> {
+    let mut known = known.split('.');
+    for key in path.split('.') {
+        match known.next() {
+            Some(k) if k == "*" || k == key => {},
+            _ => return None,
+        }
+    }
+    Some(known.next().is_none())
+}
+
+// every key of the input that no known path reaches, so nothing is dropped without a word
+fn unread_fields(json: &Value, prefix: &str, known: &[&str], unsupported: &mut Vec<String>) {
+    let Some(object) = json.as_object() else {
+        return;
+    };
+    for (key, value) in object {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        let reached: Vec<bool> = known.iter().filter_map(|k| reaches(k, &path)).collect();
+        if reached.contains(&true) {
+            continue;
+        }
+        if reached.is_empty() || !value.is_object() {
+            unsupported.push(format!("unread field {}", path));
+        } else {
+            unread_fields(value, &path, known, unsupported);
+        }
+    }
+}
+
+fn small(value: Option<u64>) -> u8 {
+    value.unwrap_or(0).min(u8::MAX as u64) as u8
+}
+
+fn medium(value: Option<u64>) -> u16 {
+    value.unwrap_or(0).min(u16::MAX as u64) as u16
+}
+
+// "fire" to "Fire", for enum names that are a single word
+fn capitalize(code: &str) -> String {
+    let mut chars = code.chars();
+    chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
+
+// weapon stats from a Foundry weapon item
+fn foundry_weapon(item: &Value) -> Option<Weapon> {
+    let weapon_type = text_at(item, "system.weaponType")?;
+    let category = if weapon_type.starts_with("martial") { WeaponCategory::Martial } else { WeaponCategory::Simple };
+    let part = at(item, "system.damage.parts")?.as_array()?.first()?.as_array()?;
+    Some(Weapon {
+        category,
+        damage: part.first()?.as_str()?.to_string(),
+        damage_type: parse_name(&capitalize(part.get(1)?.as_str()?))?,
+        finesse: at(item, "system.properties.fin").and_then(Value::as_bool).unwrap_or(false),
+        ranged: weapon_type.ends_with('R'),
+    })
+}
+
+// armor stats from a Foundry equipment item
+fn foundry_armor(item: &Value) -> Option<Armor> {
+    Some(Armor {
+        category: parse_name::<ArmorCategory>(&capitalize(&text_at(item, "system.armor.type")?))?,
+        base_ac: small(number_at(item, "system.armor.value")),
+    })
+}
+
+// parts of a character that neither format has a place for
+fn tracking_state(character: &Character, unsupported: &mut Vec<String>) {
+    if !character.resources_used.is_empty() {
+        unsupported.push("class resource uses".to_string());
+    }
+    if !character.conditions.is_empty() {
+        unsupported.push(format!("{} active condition(s)", character.conditions.len()));
+    }
+    if character.life_state != LifeState::Conscious {
+        unsupported.push(format!("life state {:?}", character.life_state));
+    }
+    if !character.history.is_empty() {
+        unsupported.push(format!("change history ({} versions)", character.history.len()));
+    }
+}
+
+// items the catalog can't rebuild from a name alone
+fn custom_items(character: &Character, unsupported: &mut Vec<String>) {
+    for item in &character.equipment {
+        let known = find_item(&item.name, item.quantity).is_some_and(|found| found.kind == item.kind);
+        if !known && item.kind != ItemKind::Gear {
+            unsupported.push(format!("weapon or armor stats of {}", item.name));
+        }
+    }
+}
+
+// an inventory item from its name, quantity and weight, using the catalog for stats
+fn catalog_item(name: &str, quantity: u16, weight: Option<f64>) -> Item {
+    let mut item = find_item(name, quantity).unwrap_or(Item {
+        name: name.to_string(),
+        quantity,
+        weight: 0.0,
+        kind: ItemKind::Gear,
+    });
+    if let Some(weight) = weight {
+        item.weight = weight as f32;
+    }
+    item
+}
+
+// the parts of the import both formats share: race, class and background by id
+fn identity(
+    race: &str,
+    subrace: Option<&str>,
+    class: &str,
+    background: &str,
+) -> Result<(Race, Option<Subrace>, Class, Background), String> {
+    let race: Race = race.parse()?;
+    let subrace = match subrace {
+        Some(subrace) => Some(subrace.parse::<Subrace>()?),
+        None => None,
+    };
+    Ok((race, subrace, class.parse()?, background.parse()?))
+}
+
+fn blank_character(
+    name: String,
+    identity: (Race, Option<Subrace>, Class, Background),
+    abilities: AbilityScores,
+) -> Character {
+    let (race, subrace, class, background) = identity;
+    Character::new(name, race, subrace, class, background, abilities, HashSet::new(), HashSet::new())
+}
+
+impl Character {
+    pub fn to_vtt(&self, format: VttFormat) -> Conversion<Value> {
+        match format {
+            VttFormat::Foundry => self.to_foundry(),
+            VttFormat::Generic => self.to_generic(),
+        }
+    }
+
+    pub fn from_vtt(json: &Value, format: VttFormat) -> Result<Conversion<Character>, String> {
+        match format {
+            VttFormat::Foundry => Character::from_foundry(json),
+            VttFormat::Generic => Character::from_generic(json),
+        }
+    }
+
+    fn to_foundry(&self) -> Conversion<Value> {
+        let mut unsupported = Vec::new();
+        tracking_state(self, &mut unsupported);
+        if self.advancement == AdvancementMode::Milestone {
+            unsupported.push("milestone advancement".to_string());
+        }
+
+        let abilities: Map<String, Value> = Ability::ALL
+            .iter()
+            .map(|&ability| {
+                let proficient = self.saving_throws.contains(&ability) as u8;
+                (
+                    ability_code(ability).to_string(),
+                    json!({ "value": self.abilities.score(ability).value(), "proficient": proficient }),
+                )
+            })
+            .collect();
+
+        let skills: Map<String, Value> = Skill::ALL
+            .iter()
+            .map(|&skill| {
+                let value = if self.expertise.contains(&skill) { 2 } else { self.skills.contains(&skill) as u8 };
+                (
+                    skill_code(skill).to_string(),
+                    json!({ "value": value, "ability": ability_code(skill.associated_ability()) }),
+                )
+            })
+            .collect();
+
+        let slots = self.class.spell_slots(self.level);
+        let spells: Map<String, Value> = (0..9)
+            .map(|i| {
+                (
+                    format!("spell{}", i + 1),
+                    json!({ "value": slots[i].saturating_sub(self.spell_slots_used[i]), "max": slots[i] }),
+                )
+            })
+            .collect();
+
+        let mut items = vec![
+            json!({
+                "name": self.class.name(),
+                "type": "class",
+                "system": {
+                    "identifier": self.class.id(),
+                    "levels": self.level,
+                    "hitDice": format!("d{}", self.class.hit_die()),
+                    "hitDiceUsed": self.hit_dice_spent,
+                },
+            }),
+            json!({
+                "name": self.subrace.map_or(self.race.name(), |s| s.name()),
+                "type": "race",
+                "system": {
+                    "identifier": self.race.id(),
+                    "type": { "value": "humanoid", "subtype": self.subrace.map(|s| s.id()) },
+                },
+            }),
+            json!({
+                "name": self.background.name(),
+                "type": "background",
+                "system": { "identifier": self.background.id() },
+            }),
+        ];
+        for item in &self.equipment {
+            let mut system = json!({ "quantity": item.quantity, "weight": item.weight });
+            let kind = match &item.kind {
+                ItemKind::Gear => "loot",
+                ItemKind::Weapon(weapon) => {
+                    let category = match weapon.category {
+                        WeaponCategory::Simple => "simple",
+                        WeaponCategory::Martial => "martial",
+                    };
+                    system["weaponType"] = json!(format!("{}{}", category, if weapon.ranged { "R" } else { "M" }));
+                    system["damage"] =
+                        json!({ "parts": [[weapon.damage, saved_name(&weapon.damage_type).to_lowercase()]] });
+                    system["properties"] = json!({ "fin": weapon.finesse });
+                    "weapon"
+                },
+                ItemKind::Armor(armor) => {
+                    system["armor"] =
+                        json!({ "value": armor.base_ac, "type": saved_name(&armor.category).to_lowercase() });
+                    "equipment"
+                },
+            };
+            items.push(json!({ "name": item.name, "type": kind, "system": system }));
+        }
+        items.extend(self.spells.iter().map(|spell| json!({ "name": spell, "type": "spell", "system": {} })));
+        items.extend(self.feats.iter().map(|feat| json!({ "name": saved_name(feat), "type": "feat", "system": {} })));
+
+        let lowercase = |values: Vec<String>| -> Vec<String> { values.into_iter().map(|v| v.to_lowercase()).collect() };
+        let actor = json!({
+            "name": self.name,
+            "type": "character",
+            "system": {
+                "abilities": abilities,
+                "skills": skills,
+                "attributes": {
+                    "hp": {
+                        "value": self.hit_points.current,
+                        "max": self.hit_points.max,
+                        "temp": self.hit_points.temp,
+                    },
+                    "death": { "success": self.death_saves.successes, "failure": self.death_saves.failures },
+                },
+                "details": {
+                    "xp": { "value": self.experience },
+                    "trait": self.personality.traits.join("\n"),
+                    "ideal": self.personality.ideal,
+                    "bond": self.personality.bond,
+                    "flaw": self.personality.flaw,
+                    "biography": { "value": self.notes },
+                },
+                "traits": {
+                    "languages": { "value": self.languages.iter().map(|&l| language_code(l)).collect::<Vec<_>>() },
+                    "dr": { "value": lowercase(sorted_names(&self.resistances)) },
+                    "dv": { "value": lowercase(sorted_names(&self.vulnerabilities)) },
+                    "toolProf": { "custom": self.tool_proficiencies.join(";") },
+                },
+                "spells": spells,
+            },
+            "items": items,
+        });
+
+        Conversion { value: actor, unsupported }
+    }
+
+    fn from_foundry(actor: &Value) -> Result<Conversion<Character>, String> {
+        let mut unsupported = Vec::new();
+        let items: Vec<&Value> =
+            at(actor, "items").and_then(Value::as_array).map(|items| items.iter().collect()).unwrap_or_default();
+        let of_type = |kind: &str| -> Vec<&Value> {
+            items.iter().copied().filter(|item| text_at(item, "type").as_deref() == Some(kind)).collect()
+        };
+
+        let classes = of_type("class");
+        let class = classes.first().ok_or("the actor has no class item")?;
+        if classes.len() > 1 {
+            unsupported.push(format!("multiclassing, only {} was kept", text_at(class, "name").unwrap_or_default()));
+        }
+        let race = *of_type("race").first().ok_or("the actor has no race item")?;
+        let background = *of_type("background").first().ok_or("the actor has no background item")?;
+        let identifier =
+            |item: &Value| text_at(item, "system.identifier").or_else(|| text_at(item, "name")).unwrap_or_default();
+
+        let mut abilities = Vec::new();
+        for ability in Ability::ALL {
+            let path = format!("system.abilities.{}.value", ability_code(ability));
+            let score = number_at(actor, &path).ok_or(format!("missing {}", path))?;
+            abilities.push(AbilityScore::try_from(score.min(u8::MAX as u64) as u8)?);
+        }
+        let abilities = AbilityScores {
+            strength: abilities[0],
+            dexterity: abilities[1],
+            constitution: abilities[2],
+            intelligence: abilities[3],
+            wisdom: abilities[4],
+            charisma: abilities[5],
+        };
+
+        let name = text_at(actor, "name").unwrap_or_default();
+        let subrace = text_at(race, "system.type.subtype");
+        let identity = identity(&identifier(race), subrace.as_deref(), &identifier(class), &identifier(background))?;
+        let mut character = blank_character(name, identity, abilities);
+
+        character.level = small(number_at(class, "system.levels")).max(1);
+        character.hit_dice_spent = small(number_at(class, "system.hitDiceUsed"));
+        character.experience = number_at(actor, "system.details.xp.value").unwrap_or(0).min(u32::MAX as u64) as u32;
+        character.hit_points = HitPoints {
+            current: medium(number_at(actor, "system.attributes.hp.value")),
+            max: medium(number_at(actor, "system.attributes.hp.max")),
+            temp: medium(number_at(actor, "system.attributes.hp.temp")),
+        };
+        character.death_saves = DeathSaves {
+            successes: small(number_at(actor, "system.attributes.death.success")),
+            failures: small(number_at(actor, "system.attributes.death.failure")),
+        };
+
+        character.saving_throws = Ability::ALL
+            .into_iter()
+            .filter(|&a| number_at(actor, &format!("system.abilities.{}.proficient", ability_code(a))).unwrap_or(0) > 0)
+            .collect();
+        character.skills.clear();
+        for skill in Skill::ALL {
+            match number_at(actor, &format!("system.skills.{}.value", skill_code(skill))).unwrap_or(0) {
+                0 => {},
+                1 => {
+                    character.skills.insert(skill);
+                },
+                2 => {
+                    character.skills.insert(skill);
+                    character.expertise.insert(skill);
+                },
+                other => unsupported.push(format!("{:?} proficiency level {}", skill, other)),
+            }
+        }
+
+        character.languages = BTreeSet::new();
+        for code in strings_at(actor, "system.traits.languages.value") {
+            match Language::ALL.into_iter().find(|&l| language_code(l) == code) {
+                Some(language) => {
+                    character.languages.insert(language);
+                },
+                None => unsupported.push(format!("language {}", code)),
+            }
+        }
+        for (path, set) in [
+            ("system.traits.dr.value", &mut character.resistances),
+            ("system.traits.dv.value", &mut character.vulnerabilities),
+        ] {
+            set.clear();
+            for code in strings_at(actor, path) {
+                match parse_name::<DamageType>(&capitalize(&code)) {
+                    Some(damage_type) => {
+                        set.insert(damage_type);
+                    },
+                    None => unsupported.push(format!("damage type {}", code)),
+                }
+            }
+        }
+        let tools = text_at(actor, "system.traits.toolProf.custom").unwrap_or_default();
+        character.tool_proficiencies =
+            tools.split(';').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect();
+
+        let slots = character.class.spell_slots(character.level);
+        for (i, &max) in slots.iter().enumerate() {
+            let remaining =
+                number_at(actor, &format!("system.spells.spell{}.value", i + 1)).map_or(max, |v| small(Some(v)));
+            character.spell_slots_used[i] = max.saturating_sub(remaining);
+        }
+
+        let traits = text_at(actor, "system.details.trait").unwrap_or_default();
+        character.personality = Personality {
+            traits: traits.lines().map(String::from).filter(|t| !t.is_empty()).collect(),
+            ideal: text_at(actor, "system.details.ideal").unwrap_or_default(),
+            bond: text_at(actor, "system.details.bond").unwrap_or_default(),
+            flaw: text_at(actor, "system.details.flaw").unwrap_or_default(),
+        };
+        character.notes = text_at(actor, "system.details.biography.value").unwrap_or_default();
+
+        for item in items.iter().copied() {
+            let name = text_at(item, "name").unwrap_or_default();
+            let quantity = medium(number_at(item, "system.quantity").or(Some(1)));
+            let weight = at(item, "system.weight").and_then(Value::as_f64);
+            match text_at(item, "type").as_deref() {
+                Some("class" | "race" | "background") => {},
+                Some("spell") => character.spells.push(name),
+                Some("feat") => match FEATS.into_iter().find(|&f| crate::matches_name(&name, &saved_name(&f))) {
+                    Some(feat) => character.feats.push(feat),
+                    None => unsupported.push(format!("feat {}", name)),
+                },
+                Some("loot" | "consumable" | "tool" | "container" | "backpack") => {
+                    character.equipment.push(catalog_item(&name, quantity, weight))
+                },
+                Some("weapon") => {
+                    let mut owned = catalog_item(&name, quantity, weight);
+                    if let Some(weapon) = foundry_weapon(item) {
+                        owned.kind = ItemKind::Weapon(weapon);
+                    }
+                    character.equipment.push(owned);
+                },
+                Some("equipment") => {
+                    let mut owned = catalog_item(&name, quantity, weight);
+                    if let Some(armor) = foundry_armor(item) {
+                        owned.kind = ItemKind::Armor(armor);
+                    }
+                    character.equipment.push(owned);
+                },
+                other => unsupported.push(format!("{} item {}", other.unwrap_or("untyped"), name)),
+            }
+        }
+
+        if let Some(currency) = at(actor, "system.currency").and_then(Value::as_object) {
+            if currency.values().any(|v| v.as_u64().unwrap_or(0) > 0) {
+                unsupported.push("currency".to_string());
+            }
+        }
+        if at(actor, "effects").and_then(Value::as_array).is_some_and(|effects| !effects.is_empty()) {
+            unsupported.push("active effects".to_string());
+        }
+        unread_fields(actor, "", &FOUNDRY_FIELDS, &mut unsupported);
+
+        Ok(Conversion { value: character, unsupported })
+    }
+
+    fn to_generic(&self) -> Conversion<Value> {
+        let mut unsupported = Vec::new();
+        tracking_state(self, &mut unsupported);
+        custom_items(self, &mut unsupported);
+        if self.death_saves != DeathSaves::default() {
+            unsupported.push("death saves".to_string());
+        }
+
+        let abilities: Map<String, Value> = Ability::ALL
+            .iter()
+            .map(|&ability| (saved_name(&ability).to_lowercase(), json!(self.abilities.score(ability).value())))
+            .collect();
+        let equipment: Vec<Value> = self
+            .equipment
+            .iter()
+            .map(|item| json!({ "name": item.name, "quantity": item.quantity, "weight": item.weight }))
+            .collect();
+
+        let document = json!({
+            "format": "generic-character",
+            "version": 1,
+            "name": self.name,
+            "race": self.race.id(),
+            "subrace": self.subrace.map(|s| s.id()),
+            "class": self.class.id(),
+            "background": self.background.id(),
+            "level": self.level,
+            "experience": self.experience,
+            "milestone": self.advancement == AdvancementMode::Milestone,
+            "abilities": abilities,
+            "hit_points": {
+                "current": self.hit_points.current,
+                "max": self.hit_points.max,
+                "temp": self.hit_points.temp,
+            },
+            "hit_dice_spent": self.hit_dice_spent,
+            "saving_throws": sorted_names(&self.saving_throws),
+            "skills": sorted_names(&self.skills),
+            "expertise": sorted_names(&self.expertise),
+            "languages": sorted_names(&self.languages),
+            "tools": self.tool_proficiencies,
+            "resistances": sorted_names(&self.resistances),
+            "vulnerabilities": sorted_names(&self.vulnerabilities),
+            "feats": sorted_names(&self.feats),
+            "equipment": equipment,
+            "spells": self.spells,
+            "spell_slots_used": self.spell_slots_used,
+            "personality": self.personality,
+            "notes": self.notes,
+        });
+
+        Conversion { value: document, unsupported }
+    }
+
+    fn from_generic(document: &Value) -> Result<Conversion<Character>, String> {
+        if text_at(document, "format").as_deref() != Some("generic-character") {
+            return Err("not a generic-character document".to_string());
+        }
+        let mut unsupported = Vec::new();
+        let required = |key: &str| text_at(document, key).ok_or(format!("missing {}", key));
+
+        let mut scores = Vec::new();
+        for ability in Ability::ALL {
+            let key = format!("abilities.{}", saved_name(&ability).to_lowercase());
+            let score = number_at(document, &key).ok_or(format!("missing {}", key))?;
+            scores.push(AbilityScore::try_from(score.min(u8::MAX as u64) as u8)?);
+        }
+        let abilities = AbilityScores {
+            strength: scores[0],
+            dexterity: scores[1],
+            constitution: scores[2],
+            intelligence: scores[3],
+            wisdom: scores[4],
+            charisma: scores[5],
+        };
+
+        let subrace = text_at(document, "subrace");
+        let identity = identity(&required("race")?, subrace.as_deref(), &required("class")?, &required("background")?)?;
+        let mut character = blank_character(required("name")?, identity, abilities);
+
+        character.level = small(number_at(document, "level")).max(1);
+        character.experience = number_at(document, "experience").unwrap_or(0).min(u32::MAX as u64) as u32;
+        if at(document, "milestone").and_then(Value::as_bool) == Some(true) {
+            character.advancement = AdvancementMode::Milestone;
+        }
+        character.hit_points = HitPoints {
+            current: medium(number_at(document, "hit_points.current")),
+            max: medium(number_at(document, "hit_points.max")),
+            temp: medium(number_at(document, "hit_points.temp")),
+        };
+        character.hit_dice_spent = small(number_at(document, "hit_dice_spent"));
+
+        // every list of enum names, reporting the ones we don't know
+        fn parse_all<T: DeserializeOwned>(document: &Value, key: &str, unsupported: &mut Vec<String>) -> Vec<T> {
+            let mut values = Vec::new();
+            for name in strings_at(document, key) {
+                match parse_name(&name) {
+                    Some(value) => values.push(value),
+                    None => unsupported.push(format!("{} entry {}", key, name)),
+                }
+            }
+            values
+        }
+        character.saving_throws = parse_all(document, "saving_throws", &mut unsupported).into_iter().collect();
+        character.skills = parse_all(document, "skills", &mut unsupported).into_iter().collect();
+        character.expertise = parse_all(document, "expertise", &mut unsupported).into_iter().collect();
+        character.languages = parse_all(document, "languages", &mut unsupported).into_iter().collect();
+        character.resistances = parse_all(document, "resistances", &mut unsupported).into_iter().collect();
+        character.vulnerabilities = parse_all(document, "vulnerabilities", &mut unsupported).into_iter().collect();
+        character.feats = parse_all(document, "feats", &mut unsupported);
+        character.tool_proficiencies = strings_at(document, "tools");
+        character.spells = strings_at(document, "spells");
+
+        if let Some(used) = at(document, "spell_slots_used").and_then(Value::as_array) {
+            for (slot, value) in character.spell_slots_used.iter_mut().zip(used) {
+                *slot = small(value.as_u64());
+            }
+        }
+        if let Some(items) = at(document, "equipment").and_then(Value::as_array) {
+            for item in items {
+                let name = text_at(item, "name").unwrap_or_default();
+                let quantity = medium(number_at(item, "quantity").or(Some(1)));
+                character.equipment.push(catalog_item(&name, quantity, at(item, "weight").and_then(Value::as_f64)));
+            }
+        }
+        if let Some(personality) = at(document, "personality") {
+            character.personality =
+                serde_json::from_value(personality.clone()).map_err(|e| format!("personality: {}", e))?;
+        }
+        character.notes = text_at(document, "notes").unwrap_or_default();
+        unread_fields(document, "", &GENERIC_FIELDS, &mut unsupported);
+
+        Ok(Conversion { value: character, unsupported })
+    }
+}
@@ -0,0 +1,88 @@
+use proptest::prelude::*;
+
+use dnd_character::{Ability, AbilityScore, AbilityScores, Race};
+
+fn score() -> impl Strategy<Value = AbilityScore> {
+    (AbilityScore::MIN..=AbilityScore::MAX).prop_map(AbilityScore::new)
+}
+
+#[test]
+fn modifiers_round_down() {
+    let modifiers: Vec<i8> =
+        [1, 8, 9, 10, 11, 12, 30].into_iter().map(|s| AbilityScore::new(s).modifier().value()).collect();
+    assert_eq!(modifiers, [-5, -1, -1, 0, 0, 1, 10]);
+    assert_eq!(AbilityScore::new(9).modifier().to_string(), "-1");
+}
+
+#[test]
+fn scores_outside_the_range_do_not_load() {
+    assert!(serde_json::from_str::<AbilityScore>("31").is_err());
+    assert!(serde_json::from_str::<AbilityScore>("0").is_err());
+    assert_eq!(serde_json::from_str::<AbilityScore>("18").unwrap().value(), 18);
+}
+
+proptest! {
+    #[test]
+    fn new_always_clamps(raw in any::<u8>()) {
+        let score = AbilityScore::new(raw).value();
+        prop_assert!((AbilityScore::MIN..=AbilityScore::MAX).contains(&score));
+        if (AbilityScore::MIN..=AbilityScore::MAX).contains(&raw) {
+            prop_assert_eq!(score, raw);
+        }
+    }
+
+    #[test]
+    fn modifier_is_floored_half_of_the_distance_from_ten(score in score()) {
+        let modifier = score.modifier().value() as i16;
+        let distance = score.value() as i16 - 10;
+        prop_assert!(2 * modifier <= distance && distance < 2 * modifier + 2);
+        prop_assert!((-5..=10).contains(&modifier));
+    }
+
+    #[test]
+    fn modifier_never_goes_down_as_the_score_goes_up(a in score(), b in score()) {
+        if a <= b {
+            prop_assert!(a.modifier() <= b.modifier());
+        }
+    }
+
+    #[test]
+    fn checked_add_stays_in_range_or_refuses(score in score(), bonus in any::<i8>()) {
+        let total = score.value() as i16 + bonus as i16;
+        match score.checked_add(bonus) {
+            Some(result) => prop_assert_eq!(result.value() as i16, total),
+            None => prop_assert!(!(1..=30).contains(&total)),
+        }
+    }
+
+    #[test]
+    fn saturating_add_clamps(score in score(), bonus in any::<i8>()) {
+        let total = score.value() as i16 + bonus as i16;
+        prop_assert_eq!(score.saturating_add(bonus).value() as i16, total.clamp(1, 30));
+    }
+
+    #[test]
+    fn every_hit_die_gives_at_least_one_hit_point(score in score(), roll in 1u8..=12) {
+        let hit_points = score.modifier().hit_points(roll);
+        prop_assert!(hit_points >= 1);
+        prop_assert_eq!(hit_points as i16, (roll as i16 + score.modifier().value() as i16).max(1));
+    }
+
+    #[test]
+    fn racial_bonuses_never_leave_the_range(
+        scores in prop::array::uniform6(score()),
+        choices in prop::collection::vec(0usize..6, 0..40),
+    ) {
+        let [strength, dexterity, constitution, intelligence, wisdom, charisma] = scores;
+        let mut abilities = AbilityScores { strength, dexterity, constitution, intelligence, wisdom, charisma };
+        for race in Race::all() {
+            abilities.apply_racial_bonuses(race);
+        }
+        for choice in choices {
+            abilities.apply_chosen_bonus(Ability::ALL[choice]);
+        }
+        for ability in Ability::ALL {
+            prop_assert!(abilities.score(ability).value() <= AbilityScore::MAX);
+        }
+    }
+}
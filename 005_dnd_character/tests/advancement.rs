@@ -0,0 +1,66 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::advancement::MAX_LEVEL;
+use dnd_character::{
+    AdvancementMode, Condition, ConditionDuration, DamageType, HitPointIncrease, HitPoints, LifeState,
+};
+
+mod common;
+
+#[test]
+fn experience_earns_level_ups_unless_advancing_by_milestone() {
+    let mut character = common::level_one("fighter");
+    assert_eq!(character.next_level_xp(), Some(300));
+    assert_eq!(character.award_xp(299), 0);
+    assert_eq!(character.award_xp(1), 1);
+    assert_eq!(character.award_xp(600), 2);
+
+    character.advancement = AdvancementMode::Milestone;
+    assert_eq!(character.pending_level_ups(), 0);
+}
+
+#[test]
+fn level_ups_grant_hit_points_and_report_the_choices() {
+    let mut character = common::level_one("fighter");
+    let mut rng = StdRng::seed_from_u64(1);
+    let max = character.hit_points.max;
+
+    // Con 13 is +1 on top of the average of a d10
+    let summary = character.level_up(HitPointIncrease::Average, &mut rng).unwrap();
+    assert_eq!((summary.level, summary.hit_points_gained), (2, 7));
+    assert_eq!(character.hit_points, HitPoints { max: max + 7, current: max + 7, temp: 0 });
+    assert!(!summary.ability_score_improvement);
+
+    let summary = character.level_up(HitPointIncrease::Average, &mut rng).unwrap();
+    assert!(!summary.ability_score_improvement);
+    let summary = character.level_up(HitPointIncrease::Average, &mut rng).unwrap();
+    assert!(summary.ability_score_improvement);
+
+    character.level = MAX_LEVEL;
+    assert!(character.level_up(HitPointIncrease::Roll, &mut rng).is_none());
+}
+
+#[test]
+fn levelling_up_only_heals_the_conscious_up_to_the_effective_maximum() {
+    let mut rng = StdRng::seed_from_u64(11);
+
+    // dying at 0 hit points stays at 0
+    let mut dying = common::level_one("fighter");
+    dying.take_damage(dying.hit_points.current, DamageType::Force);
+    assert_eq!(dying.life_state, LifeState::Dying);
+    let max = dying.hit_points.max;
+    let summary = dying.level_up(HitPointIncrease::Average, &mut rng).unwrap();
+    assert_eq!(dying.hit_points.current, 0);
+    assert_eq!(dying.life_state, LifeState::Dying);
+    assert_eq!(dying.hit_points.max, max + summary.hit_points_gained);
+
+    // exhaustion 4 halves the maximum, which current can't go past
+    let mut exhausted = common::level_one("fighter");
+    exhausted.hit_points = HitPoints::new(30);
+    exhausted.apply_condition(Condition::Exhaustion(4), ConditionDuration::UntilRemoved);
+    assert_eq!(exhausted.hit_points.current, 15);
+    exhausted.level_up(HitPointIncrease::Average, &mut rng).unwrap();
+    assert_eq!(exhausted.hit_points.current, exhausted.hit_point_maximum());
+    assert_eq!(exhausted.hit_points.current, 18);
+}
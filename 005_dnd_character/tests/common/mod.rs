@@ -0,0 +1,78 @@
+// fixtures shared by the integration tests, each test file only uses some of them
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::{random_character, AbilityScore, AbilityScores, Character, DamageType, Feat, RandomOptions};
+
+// the standard array, best scores in Str, Dex and Con
+pub fn standard_array() -> AbilityScores {
+    let score = AbilityScore::new;
+    AbilityScores {
+        strength: score(15),
+        dexterity: score(14),
+        constitution: score(13),
+        intelligence: score(12),
+        wisdom: score(10),
+        charisma: score(8),
+    }
+}
+
+// a level 1 human soldier of a class with the standard array
+pub fn level_one(class: &str) -> Character {
+    let abilities = standard_array();
+    Character::new(
+        "Brom".to_string(),
+        parse("human"),
+        None,
+        parse(class),
+        parse("soldier"),
+        abilities,
+        HashSet::new(),
+        HashSet::new(),
+    )
+}
+
+// a race, subrace, class or background by id
+pub fn parse<T: FromStr<Err = String>>(id: &str) -> T {
+    id.parse().unwrap_or_else(|e| panic!("{}", e))
+}
+
+// the same random character on every run
+pub fn seeded(options: &RandomOptions, seed: u64) -> Character {
+    random_character(options, &mut StdRng::seed_from_u64(seed))
+}
+
+// a random character of one class and level
+pub fn character(class: &str, level: u8, seed: u64) -> Character {
+    let options = RandomOptions { class: Some(parse(class)), level: Some(level), ..RandomOptions::default() };
+    seeded(&options, seed)
+}
+
+// a level 1 party member
+pub fn member(name: &str, class: &str, seed: u64) -> Character {
+    let options = RandomOptions { name: Some(name.to_string()), class: Some(parse(class)), ..RandomOptions::default() };
+    seeded(&options, seed)
+}
+
+// a character with something in every field both formats carry
+pub fn filled_in(seed: u64) -> Character {
+    let options = RandomOptions { level: Some(1 + seed as u8 % 20), ..RandomOptions::default() };
+    let mut character = seeded(&options, seed);
+    character.experience += 10;
+    character.hit_points.current = character.hit_points.max / 2;
+    character.hit_points.temp = 4;
+    character.hit_dice_spent = 1;
+    character.vulnerabilities.insert(DamageType::Thunder);
+    character.feats.push(Feat::Alert);
+    character.spells = vec!["Shield".to_string(), "Fireball".to_string()];
+    character.notes = "Owes the guild 50 gp.\nSecretly a noble.".to_string();
+    character.tool_proficiencies.push("Dice set, marked".to_string());
+    let slots = character.class.spell_slots(character.level);
+    character.spell_slots_used[0] = slots[0].min(1);
+    character
+}
@@ -0,0 +1,81 @@
+use dnd_character::{Ability, Condition, ConditionDuration, DamageType, HitPoints, LifeState, RollKind, RollMode};
+
+mod common;
+
+#[test]
+fn conditions_bring_their_implied_conditions_and_wear_off() {
+    let mut character = common::level_one("monk");
+    character.apply_condition(Condition::Stunned, ConditionDuration::Rounds(2));
+    character.apply_condition(Condition::Poisoned, ConditionDuration::minutes(1));
+    assert_eq!(character.active_conditions(), [Condition::Stunned, Condition::Incapacitated, Condition::Poisoned]);
+
+    assert_eq!(character.advance_rounds(1), []);
+    assert_eq!(character.advance_rounds(1), [Condition::Stunned]);
+    assert_eq!(character.advance_rounds(8), [Condition::Poisoned]);
+    assert!(character.active_conditions().is_empty());
+
+    // dropping to 0 hit points means unconscious and prone, until healed
+    character.take_damage(character.hit_points.current, DamageType::Force);
+    assert!(character.has_condition(Condition::Unconscious) && character.has_condition(Condition::Prone));
+    character.heal(1);
+    assert!(!character.has_condition(Condition::Unconscious));
+}
+
+#[test]
+fn conditions_change_rolls_and_speed() {
+    let mut character = common::level_one("monk");
+    let speed = character.speed();
+    character.apply_condition(Condition::Poisoned, ConditionDuration::UntilRemoved);
+    assert_eq!(character.condition_roll_mode(RollKind::Attack), RollMode::Disadvantage);
+    assert_eq!(character.condition_roll_mode(RollKind::AbilityCheck), RollMode::Disadvantage);
+    assert_eq!(character.condition_roll_mode(RollKind::SavingThrow(Ability::Wisdom)), RollMode::Normal);
+
+    // advantage and disadvantage cancel out
+    character.apply_condition(Condition::Invisible, ConditionDuration::UntilRemoved);
+    assert_eq!(character.condition_roll_mode(RollKind::Attack), RollMode::Normal);
+
+    character.apply_condition(Condition::Restrained, ConditionDuration::UntilRemoved);
+    assert_eq!(character.condition_roll_mode(RollKind::SavingThrow(Ability::Dexterity)), RollMode::Disadvantage);
+    assert_eq!(character.speed(), 0);
+    assert!(character.remove_condition(Condition::Restrained));
+    assert!(!character.remove_condition(Condition::Restrained));
+    assert_eq!(character.speed(), speed);
+
+    character.apply_condition(Condition::Paralyzed, ConditionDuration::UntilRemoved);
+    assert_eq!(character.condition_auto_fail(RollKind::SavingThrow(Ability::Strength)), Some(Condition::Paralyzed));
+    assert_eq!(character.condition_auto_fail(RollKind::SavingThrow(Ability::Constitution)), None);
+}
+
+#[test]
+fn exhaustion_levels_stack_up_to_death() {
+    let mut character = common::level_one("monk");
+    character.hit_points = HitPoints::new(30);
+    let speed = character.speed();
+    let max = character.hit_points.max;
+
+    character.apply_condition(Condition::Exhaustion(2), ConditionDuration::UntilRemoved);
+    assert_eq!(character.speed(), speed / 2);
+    assert_eq!(character.condition_roll_mode(RollKind::AbilityCheck), RollMode::Disadvantage);
+    assert_eq!(character.condition_roll_mode(RollKind::Attack), RollMode::Normal);
+
+    // a new level replaces the old one rather than adding a second
+    character.apply_condition(Condition::Exhaustion(4), ConditionDuration::UntilRemoved);
+    assert_eq!(character.exhaustion_level(), 4);
+    assert_eq!(character.active_conditions(), [Condition::Exhaustion(4)]);
+    assert_eq!(character.hit_point_maximum(), max / 2);
+    assert_eq!(character.hit_points.current, max / 2);
+    assert_eq!(character.condition_roll_mode(RollKind::Attack), RollMode::Disadvantage);
+
+    character.apply_condition(Condition::Exhaustion(9), ConditionDuration::UntilRemoved);
+    assert_eq!(character.exhaustion_level(), 6);
+    assert_eq!(character.life_state, LifeState::Dead);
+}
+
+#[test]
+fn petrified_creatures_resist_damage_and_ignore_poison() {
+    let mut character = common::level_one("monk");
+    character.apply_condition(Condition::Petrified, ConditionDuration::UntilRemoved);
+    assert_eq!(character.take_damage(9, DamageType::Slashing), 4);
+    assert_eq!(character.take_damage(9, DamageType::Poison), 0);
+    assert!(character.has_condition(Condition::Incapacitated));
+}
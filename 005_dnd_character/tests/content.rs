@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::PathBuf;
+
+use dnd_character::{check_packs, Background, Class, Content, Race};
+
+// a pack directory holding the given files, removed again by the caller
+fn pack(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dnd_character_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for (file, text) in files {
+        fs::write(dir.join(file), text).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn the_srd_pack_is_built_in() {
+    assert_eq!(Race::all().len(), 9);
+    assert_eq!(Class::all().len(), 12);
+    assert_eq!(Background::all().len(), 13);
+
+    let half_elf: Race = "half-elf".parse().unwrap();
+    assert_eq!(half_elf.name(), "Half-Elf");
+    assert_eq!(half_elf.ability_choices(), 2);
+    assert_eq!("barbarian".parse::<Class>().unwrap().hit_die(), 12);
+}
+
+#[test]
+fn old_variant_names_still_load() {
+    let race: Race = serde_json::from_str("\"HalfElf\"").unwrap();
+    let background: Background = serde_json::from_str("\"FolkHero\"").unwrap();
+
+    assert_eq!(race.id(), "half-elf");
+    assert_eq!(background.id(), "folk-hero");
+    assert_eq!(serde_json::to_string(&race).unwrap(), "\"half-elf\"");
+}
+
+#[test]
+fn extra_packs_add_to_the_srd() {
+    let dir = pack(
+        "homebrew",
+        &[(
+            "goblin.toml",
+            r#"
+[[race]]
+id = "goblin"
+name = "Goblin"
+ability_bonuses = { Dexterity = 2, Constitution = 1 }
+languages = ["Common", "Goblin"]
+climb_speed = 20
+"#,
+        )],
+    );
+    let content = Content::with_packs(std::slice::from_ref(&dir));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let content = content.unwrap();
+    assert_eq!(content.races.len(), 10);
+    let goblin = content.races.last().unwrap();
+    assert_eq!(goblin.id, "goblin");
+    assert_eq!(goblin.speed, 30);
+    assert_eq!((goblin.swim_speed, goblin.climb_speed), (0, 20));
+}
+
+#[test]
+fn duplicate_ids_and_unknown_fields_are_rejected() {
+    let duplicate = pack(
+        "duplicate",
+        &[(
+            "fighter.toml",
+            r#"
+[[class]]
+id = "fighter"
+name = "Fighter Again"
+hit_die = 10
+primary_ability = "Strength"
+saving_throws = ["Strength", "Constitution"]
+"#,
+        )],
+    );
+    let typo = pack("typo", &[("elf.toml", "[[race]]\nid = \"sea-elf\"\nname = \"Sea Elf\"\nsped = 30\n")]);
+
+    let duplicate_error = Content::with_packs(std::slice::from_ref(&duplicate)).unwrap_err().to_string();
+    let typo_error = Content::with_packs(std::slice::from_ref(&typo)).unwrap_err().to_string();
+    fs::remove_dir_all(&duplicate).unwrap();
+    fs::remove_dir_all(&typo).unwrap();
+
+    assert!(duplicate_error.contains("duplicate class id 'fighter'"), "{}", duplicate_error);
+    assert!(typo_error.contains("sped"), "{}", typo_error);
+}
+
+// the error from loading a pack made of one file, the pack is removed again
+fn load_error(name: &str, text: &str) -> String {
+    let dir = pack(name, &[("brew.toml", text)]);
+    let error = Content::with_packs(std::slice::from_ref(&dir)).unwrap_err().to_string();
+    fs::remove_dir_all(&dir).unwrap();
+    error
+}
+
+#[test]
+fn the_loader_enforces_the_class_rules() {
+    let class = |id: &str, hit_die: u8, skill_count: u8, expertise: &str| {
+        format!(
+            "[[class]]\nid = \"{}\"\nname = \"Brewed\"\nhit_die = {}\nprimary_ability = \"Strength\"\n\
+             saving_throws = [\"Strength\", \"Constitution\"]\n\
+             skill_choices = [\"Athletics\", \"Stealth\", \"Athletics\"]\nskill_count = {}\nexpertise = {}\n",
+            id, hit_die, skill_count, expertise
+        )
+    };
+    let die_error = load_error("odd_die", &class("odd-die", 7, 2, "[]"));
+    let count_error = load_error("greedy", &class("greedy", 10, 3, "[]"));
+    let expertise_error = load_error("expert", &class("expert", 10, 2, "[[1, 2], [6, 3]]"));
+
+    // the same messages validate-content gives
+    assert!(die_error.contains("class 'odd-die' has an invalid hit die d7"), "{}", die_error);
+    assert!(count_error.contains("class 'greedy' asks for 3 skills but only offers 2"), "{}", count_error);
+    assert!(
+        expertise_error.contains("class 'expert' gives 3 expertise picks at level 6 but only 2 skills"),
+        "{}",
+        expertise_error
+    );
+}
+
+#[test]
+fn the_loader_enforces_the_race_rules() {
+    let race = |id: &str, choices: u8| {
+        format!(
+            "[[race]]\nid = \"{}\"\nname = \"Brewed\"\n\
+             ability_bonuses = {{ Strength = 1, Dexterity = 1 }}\nability_choices = {}\n",
+            id, choices
+        )
+    };
+    let error = load_error("crowded", &race("crowded", 5));
+    assert!(
+        error.contains("race 'crowded' has 5 ability choices but only 4 abilities without a racial bonus"),
+        "{}",
+        error
+    );
+
+    let dir = pack("roomy", &[("brew.toml", &race("roomy", 4))]);
+    let content = Content::with_packs(std::slice::from_ref(&dir));
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(content.unwrap().races.last().unwrap().ability_choices, 4);
+}
+
+#[test]
+fn the_srd_pack_passes_the_checker() {
+    assert_eq!(check_packs(&[]).unwrap(), Vec::new());
+}
+
+#[test]
+fn the_checker_reports_every_problem_with_its_line() {
+    let dir = pack(
+        "checked",
+        &[(
+            "brew.toml",
+            r#"[[race]]
+id = "goblin"
+name = "Goblin"
+ability_bonuses = { Dexterity = 2, Wits = 1 }
+skills = ["Sneaking"]
+
+[[class]]
+id = "fighter"
+name = "Fighter"
+hit_die = 7
+primary_ability = "Strength"
+saving_throws = ["Strength", "Constitution"]
+skill_choices = ["Athletics", "Stealth"]
+skill_count = 3
+"#,
+        )],
+    );
+    let problems = check_packs(std::slice::from_ref(&dir));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let problems: Vec<(usize, String)> = problems.unwrap().into_iter().map(|p| (p.line, p.message)).collect();
+    let lines: Vec<usize> = problems.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, [4, 5, 8, 10, 14], "{:?}", problems);
+    assert!(problems[0].1.contains("unknown ability `Wits`"));
+    assert!(problems[1].1.contains("unknown skill `Sneaking`"));
+    assert!(problems[2].1.contains("duplicate class id 'fighter'"));
+    assert!(problems[3].1.contains("invalid hit die d7"));
+    assert!(problems[4].1.contains("asks for 3 skills but only offers 2"));
+}
+
+#[test]
+fn the_checker_and_loader_share_the_choice_rules() {
+    let text = r#"[[race]]
+id = "crowded"
+name = "Crowded"
+ability_bonuses = { Strength = 1, Dexterity = 1 }
+ability_choices = 5
+
+[[class]]
+id = "expert"
+name = "Expert"
+hit_die = 8
+primary_ability = "Dexterity"
+saving_throws = ["Dexterity", "Intelligence"]
+skill_choices = ["Athletics", "Stealth"]
+skill_count = 2
+expertise = [[1, 2], [6, 3]]
+"#;
+    let dir = pack("shared", &[("brew.toml", text)]);
+    let problems = check_packs(std::slice::from_ref(&dir));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let problems: Vec<(usize, String)> = problems.unwrap().into_iter().map(|p| (p.line, p.message)).collect();
+    assert_eq!(
+        problems,
+        [
+            (5, "race 'crowded' has 5 ability choices but only 4 abilities without a racial bonus".to_string()),
+            (15, "class 'expert' gives 3 expertise picks at level 6 but only 2 skills".to_string()),
+        ]
+    );
+}
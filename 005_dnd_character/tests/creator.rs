@@ -0,0 +1,308 @@
+use std::fs;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::class::{class_skill_choices, get_expertise_count, granted_skills};
+use dnd_character::creator::{create_character, Draft, Step};
+use dnd_character::{
+    Ability, AbilityScore, AdvancementMode, Background, Character, Class, Input, JsonInput, Language, Personality,
+    Race, ScriptedInput, Skill, Subrace,
+};
+
+mod common;
+
+fn race(id: &str) -> Race {
+    id.parse().unwrap()
+}
+
+fn class(id: &str) -> Class {
+    id.parse().unwrap()
+}
+
+fn background(id: &str) -> Background {
+    id.parse().unwrap()
+}
+
+fn subrace(id: &str) -> Subrace {
+    id.parse().unwrap()
+}
+
+// a wood elf rogue urchin with every step filled in
+fn finished() -> Draft {
+    let mut draft = Draft::default();
+    draft.set_race(race("elf"));
+    draft.subrace = Some(subrace("wood-elf"));
+    draft.name = Some("Sylla".to_string());
+    draft.set_class(class("rogue"));
+    draft.set_background(background("urchin"));
+    draft.rolls = Some(common::standard_array());
+    let (available, remaining) = draft.skill_choices();
+    draft.skills.extend(available.into_iter().take(remaining));
+    // expertise in two of the granted skills, so the class skills can be taken away on their own
+    draft.expertise.extend(draft.granted_skills().into_iter().take(2));
+    draft.personality = Some(Personality {
+        traits: vec!["One".to_string(), "Two".to_string()],
+        ideal: "Ideal".to_string(),
+        bond: "Bond".to_string(),
+        flaw: "Flaw".to_string(),
+    });
+    draft
+}
+
+#[test]
+fn steps_only_apply_once_they_have_something_to_ask() {
+    let mut draft = Draft::default();
+    assert!(!draft.applies(Step::Subrace));
+    assert!(!draft.applies(Step::AbilityChoices));
+    assert!(!draft.applies(Step::Skills));
+
+    // the half-elf's and soldier's skills already make up the fighter's two
+    draft.set_race(race("half-elf"));
+    draft.set_class(class("fighter"));
+    draft.set_background(background("soldier"));
+    assert!(!draft.applies(Step::Subrace));
+    assert!(draft.applies(Step::AbilityChoices));
+    assert!(!draft.applies(Step::Skills));
+    assert!(!draft.applies(Step::Expertise));
+    assert!(draft.applies(Step::Languages));
+    assert_eq!(draft.ability_choice_options().len(), 5);
+
+    draft.set_race(race("human"));
+    draft.set_class(class("rogue"));
+    assert!(!draft.applies(Step::AbilityChoices));
+    assert!(draft.applies(Step::Skills));
+    assert!(draft.applies(Step::Expertise));
+}
+
+#[test]
+fn changing_race_or_background_clears_the_choices_that_depended_on_it() {
+    let mut draft = finished();
+    draft.set_race(race("elf"));
+    assert_eq!(draft.subrace, Some(subrace("wood-elf")));
+    assert!(!draft.skills.is_empty() && !draft.expertise.is_empty());
+
+    draft.set_race(race("dwarf"));
+    assert_eq!(draft.subrace, None);
+    assert!(draft.skills.is_empty() && draft.expertise.is_empty());
+
+    let mut draft = finished();
+    draft.languages = vec![Language::Dwarvish];
+    draft.set_background(background("sage"));
+    assert!(draft.skills.is_empty() && draft.expertise.is_empty());
+    assert!(draft.languages.is_empty());
+    assert_eq!(draft.personality, None);
+
+    let mut draft = finished();
+    draft.ability_choices = vec![Ability::Strength, Ability::Wisdom];
+    draft.set_race(race("human"));
+    assert!(draft.ability_choices.is_empty());
+}
+
+#[test]
+fn changing_class_clears_skills_and_resets_equipment() {
+    let mut draft = finished();
+    draft.equipment = vec![1, 1, 0];
+    draft.set_class(class("rogue"));
+    assert_eq!(draft.equipment, vec![1, 1, 0]);
+
+    draft.set_class(class("fighter"));
+    assert!(draft.skills.is_empty() && draft.expertise.is_empty());
+    assert_eq!(draft.equipment, vec![0; class("fighter").starting_equipment().len()]);
+}
+
+#[test]
+fn building_waits_for_every_step() {
+    let draft = finished();
+    assert!(Step::ALL.iter().all(|&step| !draft.applies(step) || draft.is_complete(step)));
+    let character = draft.build().unwrap();
+    assert_eq!((character.race, character.subrace), (race("elf"), Some(subrace("wood-elf"))));
+    assert_eq!(character.expertise.len(), 2);
+    assert!(character.expertise.is_subset(&character.skills));
+
+    let mut unnamed = finished();
+    unnamed.name = Some("  ".to_string());
+    let mut no_subrace = finished();
+    no_subrace.subrace = None;
+    let mut no_rolls = finished();
+    no_rolls.rolls = None;
+    let mut short_of_skills = finished();
+    short_of_skills.skills.clear();
+    let mut short_of_expertise = finished();
+    short_of_expertise.expertise.clear();
+    let mut one_trait = finished();
+    one_trait.personality.as_mut().unwrap().traits.pop();
+
+    for draft in [unnamed, no_subrace, no_rolls, short_of_skills, short_of_expertise, one_trait] {
+        assert!(draft.build().is_none(), "{:?}", draft);
+    }
+
+    // the builder's live sheet only needs race, class, background and scores
+    let mut partial = Draft { rolls: Some(common::standard_array()), ..Draft::default() };
+    partial.set_race(race("human"));
+    partial.set_class(class("wizard"));
+    assert!(partial.preview().is_none());
+    partial.set_background(background("sage"));
+    assert!(partial.build().is_none());
+    assert_eq!(partial.preview().unwrap().name, "(unnamed)");
+}
+
+#[test]
+fn built_characters_keep_every_choice() {
+    let mut draft = finished();
+    draft.advancement = AdvancementMode::Milestone;
+    let character = draft.build().unwrap();
+
+    assert_eq!(character.name, "Sylla");
+    assert_eq!(character.advancement, AdvancementMode::Milestone);
+    assert_eq!(character.personality.traits.len(), 2);
+    assert!(character.skills.contains(&Skill::Perception));
+    // +2 elf and +1 wood elf on top of the rolls
+    assert_eq!(character.abilities.dexterity, AbilityScore::new(16));
+    assert_eq!(character.abilities.wisdom, AbilityScore::new(11));
+}
+
+// answers that take the first option at every prompt
+fn answers_for(race: Race, class: Class, background: Background) -> Vec<String> {
+    let mut answers = Vec::new();
+    let index = |i: usize| (i + 1).to_string();
+
+    answers.push(index(Race::all().iter().position(|&r| r == race).unwrap()));
+    if !race.subraces().is_empty() {
+        answers.push("1".to_string());
+    }
+    answers.push("Test Hero".to_string());
+    answers.push(index(Class::all().iter().position(|&c| c == class).unwrap()));
+    answers.push(index(Background::all().iter().position(|&b| b == background).unwrap()));
+
+    // ability scores are shown and confirmed
+    answers.push(String::new());
+
+    answers.extend((0..race.ability_choices()).map(index));
+
+    // the skill list stays the same between picks, the language list shrinks
+    let (_, remaining) = class_skill_choices(&class, &granted_skills(background, race));
+    answers.extend((0..remaining).map(index));
+    answers.extend((0..get_expertise_count(&class)).map(index));
+    answers.extend((0..race.extra_languages() + background.extra_languages()).map(|_| "1".to_string()));
+
+    // two traits, an ideal, a bond and a flaw
+    answers.extend(["1", "2", "1", "1", "1"].map(String::from));
+
+    for choice in class.starting_equipment() {
+        if choice.len() > 1 {
+            answers.push("1".to_string());
+        }
+    }
+
+    // experience advancement, then accept the review
+    answers.push("1".to_string());
+    answers.push(String::new());
+    answers
+}
+
+fn create(input: &mut dyn Input, seed: u64) -> Option<Character> {
+    create_character(input, &mut StdRng::seed_from_u64(seed))
+}
+
+#[test]
+fn every_race_class_and_background_can_be_created() {
+    let mut seed = 0;
+    for race in Race::all() {
+        for class in Class::all() {
+            for background in Background::all() {
+                seed += 1;
+                let mut input = ScriptedInput::new(answers_for(race, class, background));
+                let character = create(&mut input, seed)
+                    .unwrap_or_else(|| panic!("{:?} {:?} {:?} ran out of answers", race, class, background));
+                let context = format!("{:?} {:?} {:?}", race, class, background);
+
+                assert_eq!(input.remaining(), 0, "{}: unused answers", context);
+                assert_eq!(character.name, "Test Hero");
+                assert_eq!((character.race, character.class, character.background), (race, class, background));
+                assert_eq!(character.subrace, race.subraces().first().copied(), "{}", context);
+                assert_eq!(character.level, 1);
+                assert!(character.hit_points.max >= 1, "{}", context);
+
+                let granted = granted_skills(background, race);
+                let (_, remaining) = class_skill_choices(&class, &granted);
+                assert_eq!(character.skills.len(), granted.len() + remaining, "{}", context);
+                assert!(character.expertise.is_subset(&character.skills), "{}", context);
+                assert_eq!(character.expertise.len(), get_expertise_count(&class), "{}", context);
+
+                let (first, second) = class.saving_throws();
+                assert!(character.saving_throws.contains(&first) && character.saving_throws.contains(&second));
+
+                let languages = race.languages().len() + race.extra_languages() + background.extra_languages();
+                assert_eq!(character.languages.len(), languages, "{}", context);
+                assert_eq!(character.personality.traits.len(), 2, "{}", context);
+                assert!(!character.equipment.is_empty(), "{}", context);
+            }
+        }
+    }
+}
+
+#[test]
+fn going_back_undoes_racial_bonuses() {
+    let straight = answers_for(race("human"), class("fighter"), background("acolyte"));
+
+    // pick Half-Elf and its bonuses first, then step all the way back and pick Human
+    let mut detour: Vec<String> = ["7", "Test Hero", "5", "1", "", "1", "2"].map(String::from).to_vec();
+    detour.extend(["back"; 6].map(String::from));
+    detour.extend(straight.iter().cloned());
+
+    let expected = create(&mut ScriptedInput::new(straight.clone()), 7).unwrap();
+    let character = create(&mut ScriptedInput::new(detour), 7).unwrap();
+
+    assert_eq!(character.race, race("human"));
+    for ability in Ability::ALL {
+        assert_eq!(character.abilities.score(ability), expected.abilities.score(ability));
+    }
+}
+
+#[test]
+fn restart_and_summary_keep_the_flow_going() {
+    let mut answers: Vec<String> = ["2", "summary", "1", "Someone Else", "restart"].map(String::from).to_vec();
+    answers.extend(answers_for(race("dwarf"), class("cleric"), background("sage")));
+
+    let character = create(&mut ScriptedInput::new(answers), 3).unwrap();
+    assert_eq!(character.name, "Test Hero");
+    assert_eq!(character.race, race("dwarf"));
+}
+
+#[test]
+fn invalid_answers_are_asked_again() {
+    let mut answers: Vec<String> = ["0", "ten", "99"].map(String::from).to_vec();
+    answers.extend(answers_for(race("gnome"), class("wizard"), background("sage")));
+
+    let character = create(&mut ScriptedInput::new(answers), 5).unwrap();
+    assert_eq!(character.race, race("gnome"));
+}
+
+#[test]
+fn running_out_of_answers_gives_up() {
+    let mut answers = answers_for(race("elf"), class("rogue"), background("criminal"));
+    answers.pop();
+
+    assert!(create(&mut ScriptedInput::new(answers), 1).is_none());
+}
+
+#[test]
+fn answers_can_come_from_a_json_file() {
+    let path = std::env::temp_dir().join(format!("dnd_character_answers_{}.json", std::process::id()));
+    let answers = answers_for(race("tiefling"), class("warlock"), background("charlatan"));
+    fs::write(&path, serde_json::to_string(&answers).unwrap()).unwrap();
+
+    let mut input = JsonInput::from_file(&path).unwrap();
+    let character = create(&mut input, 11).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(character.race, race("tiefling"));
+    assert_eq!(character.class, class("warlock"));
+}
+
+#[test]
+fn json_answers_must_be_an_array_of_strings() {
+    assert!(JsonInput::from_json(r#"{"race": "elf"}"#).is_err());
+    assert!(JsonInput::from_json(r#"["1", 2]"#).is_err());
+}
@@ -0,0 +1,104 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::{Character, DamageType, DeathSaveResult, DeathSaves, HitPoints, LifeState};
+
+mod common;
+
+fn dying() -> Character {
+    let mut character = common::level_one("cleric");
+    character.take_damage(character.hit_points.current, DamageType::Force);
+    assert_eq!(character.life_state, LifeState::Dying);
+    character
+}
+
+#[test]
+fn every_roll_counts_the_way_the_rules_say() {
+    let mut seen = Vec::new();
+    for seed in 0..200 {
+        let mut character = dying();
+        let (roll, result) = character.roll_death_save(&mut StdRng::seed_from_u64(seed));
+        seen.push(roll);
+        match roll {
+            20 => {
+                assert_eq!(result, DeathSaveResult::Revived);
+                assert_eq!((character.hit_points.current, character.life_state), (1, LifeState::Conscious));
+                assert_eq!(character.death_saves, DeathSaves::default());
+            },
+            10..=19 => {
+                assert_eq!(result, DeathSaveResult::Success);
+                assert_eq!(character.death_saves, DeathSaves { successes: 1, failures: 0 });
+            },
+            2..=9 => {
+                assert_eq!(result, DeathSaveResult::Failure);
+                assert_eq!(character.death_saves, DeathSaves { successes: 0, failures: 1 });
+            },
+            _ => {
+                assert_eq!(result, DeathSaveResult::CriticalFailure);
+                assert_eq!(character.death_saves, DeathSaves { successes: 0, failures: 2 });
+            },
+        }
+    }
+    assert!(seen.contains(&1) && seen.contains(&20));
+}
+
+#[test]
+fn three_successes_stabilize_and_three_failures_kill() {
+    let mut character = dying();
+    character.death_saves = DeathSaves { successes: 2, failures: 1 };
+    for seed in 0.. {
+        let mut rolled = character.clone();
+        if let (10..=19, result) = rolled.roll_death_save(&mut StdRng::seed_from_u64(seed)) {
+            assert_eq!(result, DeathSaveResult::Stabilized);
+            assert_eq!((rolled.death_saves, rolled.life_state), (DeathSaves::default(), LifeState::Stable));
+
+            // stable creatures don't roll, but taking damage starts the dying over
+            assert_eq!(rolled.roll_death_save(&mut StdRng::seed_from_u64(seed)).1, DeathSaveResult::NotDying);
+            rolled.take_damage(1, DamageType::Fire);
+            assert_eq!((rolled.death_saves.failures, rolled.life_state), (1, LifeState::Dying));
+            break;
+        }
+    }
+
+    // a natural 1 on two failures still stops at three
+    let mut character = dying();
+    character.death_saves = DeathSaves { successes: 0, failures: 2 };
+    for seed in 0.. {
+        let mut rolled = character.clone();
+        if let (1, result) = rolled.roll_death_save(&mut StdRng::seed_from_u64(seed)) {
+            assert_eq!(result, DeathSaveResult::Died);
+            assert_eq!((rolled.death_saves.failures, rolled.life_state), (3, LifeState::Dead));
+            assert_eq!(rolled.roll_death_save(&mut StdRng::seed_from_u64(seed)).1, DeathSaveResult::NotDying);
+            break;
+        }
+    }
+}
+
+#[test]
+fn damage_at_zero_fails_a_save_and_massive_damage_kills_outright() {
+    let mut character = dying();
+    character.take_damage(1, DamageType::Piercing);
+    assert_eq!(character.death_saves.failures, 1);
+    assert_eq!(character.life_state, LifeState::Dying);
+
+    // dropping to 0 with at least the maximum left over
+    let mut character = common::level_one("cleric");
+    character.hit_points = HitPoints { max: 20, current: 12, temp: 0 };
+    let mut survivor = character.clone();
+    survivor.take_damage(31, DamageType::Bludgeoning);
+    assert_eq!(survivor.life_state, LifeState::Dying);
+    character.take_damage(32, DamageType::Bludgeoning);
+    assert_eq!(character.life_state, LifeState::Dead);
+
+    // the dead take no more damage and can't be healed
+    assert_eq!(character.take_damage(5, DamageType::Fire), 0);
+    assert_eq!(character.heal(5), 0);
+}
+
+#[test]
+fn healing_brings_the_dying_back_and_spending_hit_dice_needs_consciousness() {
+    let mut character = dying();
+    assert!(character.spend_hit_die(&mut StdRng::seed_from_u64(1)).is_none());
+    assert_eq!(character.heal(3), 3);
+    assert_eq!((character.life_state, character.death_saves), (LifeState::Conscious, DeathSaves::default()));
+}
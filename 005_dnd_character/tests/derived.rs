@@ -0,0 +1,83 @@
+use dnd_character::equipment::{Item, ItemKind};
+use dnd_character::{Ability, AbilityScore, Condition, ConditionDuration, Feat, RollMode, Skill};
+
+mod common;
+
+fn rock(weight: f32) -> Item {
+    Item { name: "Rock".to_string(), quantity: 1, weight, kind: ItemKind::Gear }
+}
+
+#[test]
+fn initiative_and_passive_scores_add_up() {
+    let mut fighter = common::level_one("fighter");
+    fighter.level = 5;
+    fighter.abilities.set(Ability::Wisdom, AbilityScore::new(12));
+    assert_eq!(fighter.initiative(), 2);
+    assert_eq!(fighter.passive_score(Skill::Perception, RollMode::Normal), 11);
+    assert_eq!(fighter.passive_score(Skill::Perception, RollMode::Advantage), 16);
+
+    fighter.feats.extend([Feat::Alert, Feat::Observant]);
+    assert_eq!(fighter.initiative(), 7);
+    assert_eq!(fighter.passive_score(Skill::Perception, RollMode::Normal), 16);
+    assert_eq!(fighter.passive_score(Skill::Insight, RollMode::Normal), 11);
+
+    // poisoned cancels the advantage
+    fighter.apply_condition(Condition::Poisoned, ConditionDuration::UntilRemoved);
+    assert_eq!(fighter.passive_score(Skill::Perception, RollMode::Advantage), 16);
+    assert_eq!(fighter.derived_stats().passive_perception, 11);
+
+    // Jack of All Trades adds half the proficiency bonus from 2nd level
+    let mut bard = common::level_one("bard");
+    bard.level = 2;
+    assert_eq!(bard.initiative(), 3);
+}
+
+#[test]
+fn carrying_capacity_and_hit_dice_come_from_strength_and_level() {
+    let mut character = common::level_one("fighter");
+    character.level = 5;
+    character.hit_dice_spent = 2;
+
+    let stats = character.derived_stats();
+    assert_eq!(stats.proficiency_bonus, 3);
+    assert_eq!((stats.carrying.capacity, stats.carrying.push_drag_lift), (225, 450));
+    assert_eq!((stats.carrying.encumbered, stats.carrying.heavily_encumbered), (75, 150));
+    assert_eq!((stats.hit_dice.die, stats.hit_dice.total, stats.hit_dice.remaining), (10, 5, 3));
+}
+
+#[test]
+fn speeds_follow_feats_and_conditions() {
+    let mut character = common::level_one("fighter");
+
+    // no racial swim or climb speed, so those cost extra movement instead of having a speed
+    let speeds = character.speeds();
+    assert_eq!((speeds.walk, speeds.swim, speeds.climb), (30, 0, 0));
+    assert_eq!(speeds.to_string(), "30 ft");
+
+    character.feats.push(Feat::Mobile);
+    assert_eq!(character.speeds().walk, 40);
+    character.apply_condition(Condition::Exhaustion(2), ConditionDuration::UntilRemoved);
+    assert_eq!(character.speeds().walk, 25);
+    character.apply_condition(Condition::Grappled, ConditionDuration::UntilRemoved);
+    assert_eq!(character.speeds().walk, 0);
+}
+
+#[test]
+fn variant_encumbrance_slows_every_speed() {
+    let mut character = common::level_one("fighter");
+    character.abilities.set(Ability::Strength, AbilityScore::new(10));
+
+    // Str 10: over 50 lb is encumbered, over 100 lb heavily encumbered
+    character.equipment.push(rock(50.0));
+    assert_eq!(character.encumbrance_penalty(), 0);
+    assert_eq!(character.speeds().walk, 30);
+    character.equipment.push(rock(1.0));
+    assert_eq!(character.encumbrance_penalty(), 10);
+    assert_eq!(character.speeds().walk, 20);
+    character.equipment.push(rock(50.0));
+    assert_eq!(character.encumbrance_penalty(), 20);
+    assert_eq!(character.speeds().walk, 10);
+
+    character.feats.push(Feat::Mobile);
+    assert_eq!(character.speeds().walk, 20);
+}
@@ -0,0 +1,75 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::dice::{DiceTerm, Keep};
+use dnd_character::DiceExpression;
+
+fn parse(text: &str) -> DiceExpression {
+    text.parse().unwrap_or_else(|e| panic!("{:?} didn't parse: {}", text, e))
+}
+
+#[test]
+fn expressions_parse_and_print_back() {
+    assert_eq!(parse("2d6+1d4-1").to_string(), "2d6 + 1d4 - 1");
+    assert_eq!(parse(" 4D6 kh3 ").to_string(), "4d6kh3");
+    assert_eq!(parse("2d20kl1").to_string(), "2d20kl1");
+    assert_eq!(parse("d20 + 5").to_string(), "1d20 + 5");
+    assert_eq!(parse("-1d4+3").to_string(), "-1d4 + 3");
+    assert_eq!(parse("7").to_string(), "7");
+
+    // "k" keeps the highest and "%" is a d100
+    assert_eq!(parse("4d6k3"), parse("4d6kh3"));
+    assert_eq!(parse("d%").to_string(), "1d100");
+}
+
+#[test]
+fn counts_sizes_and_constants_have_limits() {
+    assert!("100d6".parse::<DiceExpression>().is_ok());
+    assert!("101d6".parse::<DiceExpression>().is_err());
+    assert!("1d255".parse::<DiceExpression>().is_ok());
+    assert!("1d256".parse::<DiceExpression>().is_err());
+    assert!("10000".parse::<DiceExpression>().is_ok());
+    assert!("10001".parse::<DiceExpression>().is_err());
+    assert!("99999999999d6".parse::<DiceExpression>().is_err());
+}
+
+#[test]
+fn malformed_expressions_are_rejected() {
+    for text in ["", "   ", "d", "3d0", "0d6", "4d6kh5", "4d6kl0", "4d6kx", "2d6+", "2d6++1", "+", "d6d6", "abc", "1.5"]
+    {
+        assert!(text.parse::<DiceExpression>().is_err(), "{:?} parsed", text);
+    }
+}
+
+#[test]
+fn rolls_stay_in_bounds_and_keep_the_right_dice() {
+    let expression = parse("4d6kh3+2");
+    let mut rng = StdRng::seed_from_u64(11);
+    for _ in 0..200 {
+        let roll = expression.roll(&mut rng);
+        assert!((5..=20).contains(&roll.total), "{}", roll.breakdown());
+
+        let dice = &roll.terms[0];
+        assert_eq!(dice.term, DiceTerm::Dice { count: 4, sides: 6, keep: Keep::Highest(3) });
+        assert_eq!((dice.kept.len(), dice.dropped.len()), (3, 1));
+        assert!(dice.kept.iter().all(|&kept| kept >= dice.dropped[0]));
+        assert!(dice.kept.iter().chain(&dice.dropped).all(|&die| (1..=6).contains(&die)));
+    }
+
+    let lowest = parse("2d20kl1").roll(&mut rng);
+    assert_eq!(lowest.terms[0].kept[0], *lowest.terms[0].kept.iter().chain(&lowest.terms[0].dropped).min().unwrap());
+
+    // negative terms are taken away
+    for _ in 0..50 {
+        let total = parse("1-1d4").roll(&mut rng).total;
+        assert!((-3..=0).contains(&total));
+    }
+}
+
+#[test]
+fn the_same_seed_gives_the_same_roll() {
+    let expression = parse("3d8+1d%-2");
+    let first = expression.roll(&mut StdRng::seed_from_u64(5));
+    assert_eq!(first, expression.roll(&mut StdRng::seed_from_u64(5)));
+    assert!(first.breakdown().ends_with(&format!("= {}", first.total)));
+}
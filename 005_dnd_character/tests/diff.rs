@@ -0,0 +1,77 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::json;
+
+use dnd_character::equipment::{Item, ItemKind};
+use dnd_character::{Ability, FieldChange, HitPointIncrease, Language, RandomOptions, Skill};
+
+mod common;
+
+fn dagger() -> Item {
+    Item { name: "Dagger".to_string(), quantity: 1, weight: 1.0, kind: ItemKind::Gear }
+}
+
+#[test]
+fn a_level_up_shows_every_field_it_touched() {
+    let before = common::character("rogue", 5, 4);
+    assert!(before.diff(&before).is_empty());
+
+    let mut after = before.clone();
+    after.level_up(HitPointIncrease::Average, &mut StdRng::seed_from_u64(1)).unwrap();
+    let skill = Skill::ALL.into_iter().find(|s| after.skills.contains(s) && !after.expertise.contains(s)).unwrap();
+    after.expertise.insert(skill);
+    after.abilities.set(Ability::Wisdom, after.abilities.score(Ability::Wisdom).saturating_add(1));
+    after.equipment.push(dagger());
+    after.equipment.push(dagger());
+    after.spells.push("Mage Hand".to_string());
+
+    let diff = before.diff(&after);
+    assert_eq!(
+        diff.get("level"),
+        Some(&FieldChange::Changed { field: "level".to_string(), before: json!(5), after: json!(6) })
+    );
+    assert_eq!(
+        diff.get("expertise"),
+        Some(&FieldChange::Entries {
+            field: "expertise".to_string(),
+            added: vec![format!("{:?}", skill)],
+            removed: vec![]
+        })
+    );
+    let wisdom = before.abilities.score(Ability::Wisdom).value();
+    assert_eq!(
+        diff.get("abilities.wisdom").unwrap().to_string(),
+        format!("abilities.wisdom: {} → {} (+1)", wisdom, wisdom + 1)
+    );
+    assert!(diff.get("hit_points.max").is_some());
+    assert!(diff.get("abilities.strength").is_none());
+    assert_eq!(diff.get("equipment").unwrap().to_string(), "equipment: +Dagger, +Dagger");
+    assert_eq!(diff.get("spells").unwrap().to_string(), "spells: +Mage Hand");
+
+    // and backwards everything is removed again
+    let back = after.diff(&before);
+    assert_eq!(back.changes.len(), diff.changes.len());
+    assert_eq!(back.get("equipment").unwrap().to_string(), "equipment: -Dagger, -Dagger");
+}
+
+#[test]
+fn diffs_serialize_with_a_kind_for_each_change() {
+    let before = common::seeded(&RandomOptions::default(), 2);
+    let mut after = before.clone();
+    after.hit_points.current = 1;
+    after.languages.insert(Language::Draconic);
+    after.languages.insert(Language::Abyssal);
+    after.languages.remove(&Language::Common);
+
+    let json = serde_json::to_value(before.diff(&after)).unwrap();
+    assert_eq!(json["before"], json!(before.name));
+    let changes = json["changes"].as_array().unwrap();
+    let hit_points = changes.iter().find(|c| c["field"] == "hit_points.current").unwrap();
+    assert_eq!(hit_points["kind"], "changed");
+    assert_eq!(hit_points["after"], 1);
+
+    let languages = changes.iter().find(|c| c["field"] == "languages").unwrap();
+    assert_eq!(languages["kind"], "entries");
+    assert_eq!(languages["removed"], json!(["Common"]));
+    assert!(languages["added"].as_array().unwrap().contains(&json!("Draconic")));
+}
@@ -0,0 +1,92 @@
+use dnd_character::export::Block;
+use dnd_character::{Character, Html, Markdown, Template};
+
+mod common;
+
+fn wizard() -> Character {
+    let mut character = common::character("wizard", 3, 11);
+    character.spells = vec!["Magic Missile".to_string()];
+    character.notes = "Owes the <Zhentarim> | 50 gp".to_string();
+    character
+}
+
+#[test]
+fn every_section_is_exported() {
+    let markdown = wizard().export(&Markdown);
+    for heading in
+        ["Abilities", "Combat", "Saving Throws", "Skills", "Attacks", "Spells", "Features", "Equipment", "Notes"]
+    {
+        assert!(markdown.contains(&format!("\n## {}\n", heading)), "missing {}", heading);
+    }
+    assert!(markdown.contains("- Magic Missile\n"));
+    assert!(markdown.contains("Owes the \\<Zhentarim\\> \\| 50 gp"));
+}
+
+// pages in the document, counted from the page objects
+fn pdf_pages(pdf: &[u8]) -> usize {
+    let text = String::from_utf8_lossy(pdf);
+    text.matches("/Type/Page").count() - text.matches("/Type/Pages").count()
+}
+
+#[test]
+fn casters_get_a_spell_page_in_the_pdf() {
+    let mut wizard = wizard();
+    wizard.notes.clear();
+    let pdf = wizard.export_pdf().unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+    assert_eq!(pdf_pages(&pdf), 2);
+
+    let fighter = common::character("fighter", 1, 5);
+    assert_eq!(pdf_pages(&fighter.export_pdf().unwrap()), 1);
+}
+
+#[test]
+fn html_is_self_contained_and_escaped() {
+    let html = wizard().export(&Html);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("@media print"));
+    assert!(!html.contains("<link") && !html.contains("<script"));
+    assert!(html.contains("Owes the &lt;Zhentarim&gt; | 50 gp"));
+}
+
+// a plain text template only needs the block methods
+struct Plain;
+
+impl Template for Plain {
+    fn document(&self, title: &str, body: &str) -> String {
+        format!("{}\n{}", title.to_uppercase(), body)
+    }
+
+    fn section(&self, title: &str, body: &str) -> String {
+        format!("[{}]\n{}", title, body)
+    }
+
+    fn fields(&self, fields: &[(String, String)]) -> String {
+        fields.iter().map(|(label, value)| format!("{}={}\n", label, value)).collect()
+    }
+
+    fn table(&self, _: &[String], rows: &[Vec<String>]) -> String {
+        rows.iter().map(|row| format!("{}\n", row.join(","))).collect()
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        format!("{}\n", items.join(";"))
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("{}\n", text)
+    }
+}
+
+#[test]
+fn templates_are_pluggable() {
+    let character = wizard();
+    let text = character.export(&Plain);
+    assert!(text.starts_with(&character.name.to_uppercase()));
+    assert!(text.contains("[Spells]\n"));
+    assert!(text.contains("Class=Wizard\n"));
+
+    let sheet = character.sheet();
+    let spells = sheet.sections.iter().find(|s| s.title == "Spells").unwrap();
+    assert!(spells.blocks.contains(&Block::List(vec!["Magic Missile".to_string()])));
+}
@@ -0,0 +1,75 @@
+use serde_json::Value;
+
+use dnd_character::advancement::XP_THRESHOLDS;
+use dnd_character::{Background, Character, Class, Race, RandomOptions, Subrace};
+
+mod common;
+
+use common::parse;
+
+// the character as JSON, with the hash sets sorted so two equal characters compare equal
+fn snapshot(character: &Character) -> Value {
+    let mut value = serde_json::to_value(character).unwrap();
+    for field in ["resistances", "vulnerabilities", "skills", "expertise", "saving_throws"] {
+        if let Some(Value::Array(items)) = value.get_mut(field) {
+            items.sort_by_key(|item| item.to_string());
+        }
+    }
+    value
+}
+
+#[test]
+fn the_same_seed_gives_the_same_character() {
+    let options = RandomOptions { level: Some(5), ..RandomOptions::default() };
+    for seed in 0..20 {
+        let first = snapshot(&common::seeded(&options, seed));
+        assert_eq!(first, snapshot(&common::seeded(&options, seed)), "seed {}", seed);
+    }
+}
+
+#[test]
+fn random_characters_keep_to_the_options() {
+    let (elf, wizard, sage): (Race, Class, Background) = (parse("elf"), parse("wizard"), parse("sage"));
+    let options = RandomOptions {
+        name: Some("Tanis".to_string()),
+        race: Some(elf),
+        class: Some(wizard),
+        background: Some(sage),
+        level: Some(7),
+        ..RandomOptions::default()
+    };
+    for seed in 0..20 {
+        let character = common::seeded(&options, seed);
+        assert_eq!(character.name, "Tanis");
+        assert_eq!(character.race, elf);
+        assert!(elf.subraces().contains(&character.subrace.unwrap()));
+        assert_eq!(character.class, wizard);
+        assert_eq!(character.background, sage);
+        assert_eq!(character.level, 7);
+        assert_eq!(character.experience, XP_THRESHOLDS[6]);
+    }
+}
+
+#[test]
+fn a_subrace_decides_the_race() {
+    let stout: Subrace = parse("stout");
+    let options = RandomOptions { race: Some(parse("elf")), subrace: Some(stout), ..RandomOptions::default() };
+    for seed in 0..20 {
+        let character = common::seeded(&options, seed);
+        assert_eq!(character.race, parse::<Race>("halfling"));
+        assert_eq!(character.subrace, Some(stout));
+    }
+
+    // races without subraces never get one
+    let options = RandomOptions { race: Some(parse("human")), ..RandomOptions::default() };
+    assert_eq!(common::seeded(&options, 1).subrace, None);
+}
+
+#[test]
+fn levels_are_kept_between_1_and_20() {
+    for level in [0, 1, 20, 30] {
+        let options = RandomOptions { level: Some(level), ..RandomOptions::default() };
+        let character = common::seeded(&options, u64::from(level));
+        assert_eq!(character.level, level.clamp(1, 20));
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::save::{load_character, save_character};
+use dnd_character::{Ability, AbilityScore, AdvancementMode, Character, Feat, HitPointIncrease};
+
+mod common;
+
+fn fighter(level: u8) -> Character {
+    let mut character = common::character("fighter", level, 7);
+    character.advancement = AdvancementMode::Milestone;
+    character.abilities.set(Ability::Dexterity, AbilityScore::new(14));
+    character
+}
+
+#[test]
+fn level_ups_are_recorded_and_can_be_reverted() {
+    let mut character = fighter(3);
+    character.record_created("new character");
+    let original_hit_points = character.hit_points.max;
+
+    let before = character.clone();
+    let summary = character.level_up(HitPointIncrease::Average, &mut StdRng::seed_from_u64(1)).unwrap();
+    assert!(summary.ability_score_improvement);
+    character.improve_ability(Ability::Dexterity, 2);
+    assert!(character.record_changes(&before, "ASI"));
+
+    let gained = character.hit_points.max - original_hit_points;
+    assert_eq!(character.history[1].to_string(), format!("L3 → L4: +2 Dex, +{} max HP (ASI)", gained));
+    assert!(!character.record_changes(&character.clone(), "nothing"));
+
+    let before = character.clone();
+    character.feats.push(Feat::Alert);
+    character.record_changes(&before, "DM ruling");
+    assert_eq!(character.history.len(), 3);
+
+    character.revert(1, "dispute settled").unwrap();
+    assert_eq!(character.level, 3);
+    assert_eq!(character.abilities.score(Ability::Dexterity).value(), 14);
+    assert!(character.feats.is_empty());
+    // nothing is lost: the reverted versions are still there to go back to
+    assert_eq!(character.history.len(), 4);
+    assert!(character.history[3].change.starts_with("Reverted to version 1: L4 → L3:"));
+    assert_eq!(character.version(3).unwrap().feats, [Feat::Alert]);
+    assert!(character.version(3).unwrap().history.is_empty());
+    assert!(character.revert(9, "").is_err());
+}
+
+#[test]
+fn hand_edits_show_up_as_unrecorded_changes() {
+    let path = std::env::temp_dir().join(format!("dnd_character_history_{}.json", std::process::id()));
+
+    // no history yet, so nothing is written for it
+    let mut character = fighter(2);
+    save_character(&character, &path).unwrap();
+    assert!(!fs::read_to_string(&path).unwrap().contains("history"));
+
+    character.record_created("new character");
+    save_character(&character, &path).unwrap();
+    let mut edited = load_character(&path).unwrap();
+    assert_eq!(edited.history.len(), 1);
+    assert_eq!(edited.unrecorded_changes(), None);
+
+    // damage isn't part of the build
+    edited.hit_points.current = 1;
+    assert_eq!(edited.unrecorded_changes(), None);
+
+    edited.abilities.set(Ability::Strength, edited.abilities.score(Ability::Strength).saturating_add(-1));
+    edited.spells.push("Shield".to_string());
+    assert_eq!(edited.unrecorded_changes().as_deref(), Some("-1 Str, +Shield spell"));
+    assert!(edited.record_edits("edited by hand"));
+    assert!(!edited.record_edits("edited by hand"));
+    assert_eq!(edited.history.len(), 2);
+
+    fs::remove_file(&path).unwrap();
+}
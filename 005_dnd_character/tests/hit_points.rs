@@ -0,0 +1,47 @@
+use dnd_character::{DamageType, HitPoints};
+
+mod common;
+
+#[test]
+fn healing_stops_at_the_maximum() {
+    let mut below = HitPoints { max: 20, current: 5, temp: 0 };
+    assert_eq!(below.heal(10), 10);
+    assert_eq!(below.heal(10), 5);
+    assert_eq!(below.current, 20);
+
+    let mut at = HitPoints::new(20);
+    assert_eq!(at.heal(4), 0);
+    assert_eq!(at.current, 20);
+
+    // current can be above max after a hand edit or a lower maximum, and stays put
+    let mut above = HitPoints { max: 20, current: 25, temp: 0 };
+    assert_eq!(above.heal(4), 0);
+    assert_eq!(above.current, 25);
+}
+
+#[test]
+fn temporary_hit_points_go_first_and_resistances_change_the_damage() {
+    let mut character = common::level_one("fighter");
+    character.hit_points = HitPoints::new(30);
+    character.hit_points.grant_temp(5);
+    character.hit_points.grant_temp(3);
+    assert_eq!(character.hit_points.temp, 5);
+
+    assert_eq!(character.take_damage(8, DamageType::Slashing), 8);
+    assert_eq!((character.hit_points.temp, character.hit_points.current), (0, 27));
+
+    character.resistances.insert(DamageType::Fire);
+    assert_eq!(character.take_damage(7, DamageType::Fire), 3);
+    character.vulnerabilities.insert(DamageType::Cold);
+    assert_eq!(character.take_damage(3, DamageType::Cold), 6);
+    assert_eq!(character.hit_points.current, 18);
+
+    // both at once cancel out
+    character.vulnerabilities.insert(DamageType::Fire);
+    assert_eq!(character.take_damage(4, DamageType::Fire), 4);
+    assert_eq!(character.hit_points.current, 14);
+
+    // current hit points stop at 0
+    assert_eq!(character.take_damage(50, DamageType::Force), 50);
+    assert_eq!(character.hit_points.current, 0);
+}
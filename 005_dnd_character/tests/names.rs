@@ -0,0 +1,33 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::{generate_name, random_name, NameStyle, Race};
+
+#[test]
+fn every_style_gives_capitalized_names_within_the_length_limits() {
+    for style in NameStyle::ALL {
+        for seed in 0..50 {
+            let name = generate_name(style, &mut StdRng::seed_from_u64(seed));
+            assert!((3..=10).contains(&name.chars().count()), "{:?} gave {:?}", style, name);
+            assert!(name.chars().next().unwrap().is_uppercase(), "{:?} gave {:?}", style, name);
+        }
+    }
+}
+
+#[test]
+fn the_same_seed_gives_the_same_name() {
+    for style in NameStyle::ALL {
+        let first = generate_name(style, &mut StdRng::seed_from_u64(7));
+        assert_eq!(first, generate_name(style, &mut StdRng::seed_from_u64(7)));
+    }
+}
+
+#[test]
+fn races_name_in_their_own_style() {
+    assert_eq!("half-elf".parse::<Race>().unwrap().name_style(), NameStyle::Elvish);
+    assert_eq!("half-orc".parse::<Race>().unwrap().name_style(), NameStyle::Orcish);
+    for race in Race::all() {
+        let name = random_name(race, &mut StdRng::seed_from_u64(3));
+        assert_eq!(name, generate_name(race.name_style(), &mut StdRng::seed_from_u64(3)));
+    }
+}
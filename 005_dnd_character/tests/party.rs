@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::PathBuf;
+
+use dnd_character::language::Language;
+use dnd_character::save::save_character;
+use dnd_character::{Party, PartySummary, Skill};
+
+mod common;
+
+#[test]
+fn the_summary_finds_the_best_member_for_each_skill() {
+    let mut rogue = common::member("Vex", "rogue", 1);
+    let mut fighter = common::member("Brom", "fighter", 2);
+    rogue.skills.insert(Skill::Stealth);
+    rogue.expertise.insert(Skill::Stealth);
+    fighter.skills.remove(&Skill::Stealth);
+    fighter.languages.insert(Language::Giant);
+    rogue.languages.remove(&Language::Giant);
+
+    let summary = PartySummary::new(&[rogue.clone(), fighter]);
+    assert_eq!(summary.members.len(), 2);
+
+    let stealth = summary.skills.iter().find(|c| c.skill == Skill::Stealth).unwrap();
+    assert_eq!(stealth.best, ["Vex"]);
+    assert_eq!(stealth.bonus, rogue.skill_modifier(Skill::Stealth));
+    assert!(stealth.proficient);
+
+    assert_eq!(summary.languages[&Language::Common], ["Vex", "Brom"]);
+    assert_eq!(summary.languages[&Language::Giant], ["Brom"]);
+}
+
+#[test]
+fn rosters_keep_paths_relative_to_themselves() {
+    let dir = std::env::temp_dir().join(format!("dnd_character_party_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let roster = dir.join("party.json");
+    save_character(&common::member("Vex", "rogue", 1), dir.join("vex.json")).unwrap();
+    save_character(&common::member("Brom", "fighter", 2), dir.join("brom.json")).unwrap();
+
+    let mut party = Party::load(&roster).unwrap();
+    assert!(party.members.is_empty());
+    assert!(party.add(&roster, &dir.join("vex.json")).unwrap());
+    assert!(party.add(&roster, &dir.join("brom.json")).unwrap());
+    assert!(!party.add(&roster, &dir.join("vex.json")).unwrap());
+    party.save(&roster).unwrap();
+
+    let party = Party::load(&roster).unwrap();
+    assert_eq!(party.members, [PathBuf::from("vex.json"), "brom.json".into()]);
+    assert_eq!(party.find(&roster, "brom").unwrap().1.name, "Brom");
+
+    let mut party = party;
+    assert_eq!(party.remove(&roster, "Vex").unwrap(), PathBuf::from("vex.json"));
+    assert_eq!(party.members.len(), 1);
+    assert!(party.remove(&roster, "Vex").is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,98 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::{ClassResource, Condition, ConditionDuration, DamageType, HitPoints, RestSummary};
+
+mod common;
+
+#[test]
+fn short_rests_spend_hit_dice_up_to_what_is_left() {
+    let mut character = common::level_one("fighter");
+    character.level = 3;
+    character.hit_points = HitPoints { max: 28, current: 1, temp: 0 };
+
+    let summary = character.short_rest(5, &mut StdRng::seed_from_u64(1));
+    assert_eq!(summary.hit_dice_rolls.len(), 3);
+    assert!(summary.hit_dice_rolls.iter().all(|&roll| (1..=10).contains(&roll)));
+    assert_eq!(character.hit_points.current, 1 + summary.hit_points_regained);
+    assert!(character.hit_points.current <= 28);
+    assert_eq!((character.hit_dice_spent, character.hit_dice_remaining()), (3, 0));
+
+    // nothing left to spend
+    let summary = character.short_rest(1, &mut StdRng::seed_from_u64(2));
+    assert!(summary.hit_dice_rolls.is_empty());
+}
+
+#[test]
+fn short_rests_bring_back_short_rest_resources_and_pact_slots() {
+    let mut fighter = common::level_one("fighter");
+    assert!(fighter.use_resource(ClassResource::SecondWind, 1));
+    assert!(!fighter.use_resource(ClassResource::SecondWind, 1));
+    fighter.short_rest(0, &mut StdRng::seed_from_u64(1));
+    assert_eq!(fighter.resource_remaining(ClassResource::SecondWind), 1);
+
+    let mut wizard = common::level_one("wizard");
+    assert!(wizard.use_resource(ClassResource::ArcaneRecovery, 1));
+    assert!(wizard.expend_spell_slot(1));
+    wizard.short_rest(0, &mut StdRng::seed_from_u64(1));
+    assert_eq!(wizard.resource_remaining(ClassResource::ArcaneRecovery), 0);
+    assert_eq!(wizard.spell_slots_used[0], 1);
+
+    let mut warlock = common::level_one("warlock");
+    assert!(warlock.expend_spell_slot(1));
+    warlock.short_rest(0, &mut StdRng::seed_from_u64(1));
+    assert_eq!(warlock.spell_slots_used, [0; 9]);
+}
+
+#[test]
+fn long_rests_restore_hit_points_slots_and_half_the_spent_hit_dice() {
+    let mut character = common::level_one("wizard");
+    character.level = 9;
+    character.hit_points = HitPoints { max: 40, current: 35, temp: 4 };
+    character.hit_dice_spent = 5;
+    assert!(character.expend_spell_slot(1));
+    assert!(character.expend_spell_slot(5));
+    assert!(character.use_resource(ClassResource::ArcaneRecovery, 1));
+
+    let summary = character.long_rest();
+    assert_eq!(summary, RestSummary { hit_dice_rolls: Vec::new(), hit_points_regained: 5, hit_dice_regained: 2 });
+    assert_eq!(character.hit_points, HitPoints { max: 40, current: 40, temp: 0 });
+    assert_eq!(character.hit_dice_spent, 3);
+    assert_eq!(character.spell_slots_used, [0; 9]);
+    assert_eq!(character.resource_remaining(ClassResource::ArcaneRecovery), 1);
+
+    // always at least one back, never more than were spent
+    assert_eq!(character.long_rest().hit_dice_regained, 1);
+    assert_eq!(character.long_rest().hit_dice_regained, 1);
+    assert_eq!(character.long_rest().hit_dice_regained, 1);
+    assert_eq!(character.long_rest().hit_dice_regained, 0);
+}
+
+#[test]
+fn long_rests_do_nothing_at_zero_hit_points() {
+    let mut character = common::level_one("wizard");
+    character.level = 3;
+    character.take_damage(character.hit_points.current, DamageType::Force);
+    character.hit_dice_spent = 2;
+
+    assert_eq!(character.long_rest(), RestSummary::default());
+    assert_eq!((character.hit_points.current, character.hit_dice_spent), (0, 2));
+}
+
+#[test]
+fn rests_let_time_pass_and_long_rests_ease_exhaustion() {
+    let mut character = common::level_one("fighter");
+    character.apply_condition(Condition::Poisoned, ConditionDuration::hours(1));
+    character.apply_condition(Condition::Frightened, ConditionDuration::hours(2));
+    character.apply_condition(Condition::Exhaustion(2), ConditionDuration::UntilRemoved);
+
+    character.short_rest(0, &mut StdRng::seed_from_u64(1));
+    assert!(!character.has_condition(Condition::Poisoned));
+    assert!(character.has_condition(Condition::Frightened));
+
+    character.long_rest();
+    assert!(!character.has_condition(Condition::Frightened));
+    assert_eq!(character.exhaustion_level(), 1);
+    character.long_rest();
+    assert_eq!(character.exhaustion_level(), 0);
+}
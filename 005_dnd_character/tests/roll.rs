@@ -0,0 +1,127 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::{Ability, Condition, ConditionDuration, RollMode, Skill};
+
+mod common;
+
+#[test]
+fn totals_add_up_and_natural_ones_and_twenties_are_flagged() {
+    let mut character = common::level_one("fighter");
+    character.skills.insert(Skill::Athletics);
+
+    let (mut twenty, mut one) = (false, false);
+    for seed in 0..200 {
+        let roll = character.roll_check(Skill::Athletics, RollMode::Normal, -1, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(roll.rolls, [roll.natural]);
+        assert_eq!((roll.die, roll.ability_modifier, roll.proficiency, roll.bonus), (roll.natural, 2, 2, -1));
+        assert_eq!(roll.total, roll.natural as i16 + 3);
+        assert_eq!(roll.is_natural_20(), roll.natural == 20);
+        assert_eq!(roll.is_natural_1(), roll.natural == 1);
+        twenty |= roll.is_natural_20() && roll.breakdown().ends_with("(natural 20!)");
+        one |= roll.is_natural_1() && roll.breakdown().ends_with("(natural 1!)");
+    }
+    assert!(twenty && one);
+
+    // the same seed rolls the same
+    let first = character.roll_save(Ability::Strength, RollMode::Normal, 0, &mut StdRng::seed_from_u64(9));
+    assert_eq!(first, character.roll_save(Ability::Strength, RollMode::Normal, 0, &mut StdRng::seed_from_u64(9)));
+    assert_eq!(first.proficiency, 2);
+    assert_eq!(character.roll_save(Ability::Wisdom, RollMode::Normal, 0, &mut StdRng::seed_from_u64(9)).proficiency, 0);
+}
+
+#[test]
+fn advantage_keeps_the_higher_die_and_disadvantage_the_lower() {
+    let character = common::level_one("fighter");
+    for seed in 0..50 {
+        let advantage =
+            character.roll_ability_check(Ability::Dexterity, RollMode::Advantage, 0, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(advantage.rolls.len(), 2);
+        assert_eq!(advantage.natural, advantage.rolls[0].max(advantage.rolls[1]));
+
+        let disadvantage = character.roll_ability_check(
+            Ability::Dexterity,
+            RollMode::Disadvantage,
+            0,
+            &mut StdRng::seed_from_u64(seed),
+        );
+        assert_eq!(disadvantage.rolls, advantage.rolls);
+        assert_eq!(disadvantage.natural, disadvantage.rolls[0].min(disadvantage.rolls[1]));
+    }
+
+    assert_eq!(RollMode::resolve(true, true), RollMode::Normal);
+    assert_eq!(RollMode::resolve(true, false), RollMode::Advantage);
+    assert_eq!(RollMode::resolve(false, true), RollMode::Disadvantage);
+    assert_eq!(RollMode::resolve(false, false), RollMode::Normal);
+}
+
+#[test]
+fn reliable_talent_only_lifts_proficient_checks_from_eleventh_level() {
+    let mut rogue = common::level_one("rogue");
+    rogue.skills.insert(Skill::Stealth);
+    rogue.skills.remove(&Skill::Arcana);
+
+    for level in [10, 11] {
+        rogue.level = level;
+        let mut lifted = false;
+        for seed in 0..100 {
+            let stealth = rogue.roll_check(Skill::Stealth, RollMode::Normal, 0, &mut StdRng::seed_from_u64(seed));
+            let arcana = rogue.roll_check(Skill::Arcana, RollMode::Normal, 0, &mut StdRng::seed_from_u64(seed));
+            let expected = if level >= 11 { stealth.natural.max(10) } else { stealth.natural };
+            assert_eq!(stealth.die, expected);
+            assert_eq!(arcana.die, arcana.natural);
+            lifted |= stealth.die != stealth.natural;
+        }
+        assert_eq!(lifted, level >= 11);
+    }
+}
+
+#[test]
+fn jack_of_all_trades_adds_half_proficiency_to_unproficient_checks() {
+    let mut bard = common::level_one("bard");
+    bard.skills = [Skill::Performance, Skill::Persuasion].into();
+    bard.expertise = [Skill::Persuasion].into();
+    let mut rng = StdRng::seed_from_u64(3);
+
+    // not until 2nd level
+    assert_eq!(bard.roll_check(Skill::Stealth, RollMode::Normal, 0, &mut rng).proficiency, 0);
+
+    bard.level = 5;
+    assert_eq!(bard.roll_check(Skill::Stealth, RollMode::Normal, 0, &mut rng).proficiency, 1);
+    assert_eq!(bard.roll_ability_check(Ability::Strength, RollMode::Normal, 0, &mut rng).proficiency, 1);
+    assert_eq!(bard.roll_check(Skill::Performance, RollMode::Normal, 0, &mut rng).proficiency, 3);
+    assert_eq!(bard.roll_check(Skill::Persuasion, RollMode::Normal, 0, &mut rng).proficiency, 6);
+    // saving throws aren't ability checks
+    assert_eq!(bard.roll_save(Ability::Strength, RollMode::Normal, 0, &mut rng).proficiency, 0);
+    assert_eq!(bard.skill_modifier(Skill::Stealth), 2 + 1);
+}
+
+#[test]
+fn attack_rolls_add_proficiency_and_take_conditions_into_account() {
+    let mut character = common::level_one("fighter");
+    let attack = character.roll_attack(Ability::Strength, true, RollMode::Normal, 1, &mut StdRng::seed_from_u64(4));
+    assert_eq!((attack.ability_modifier, attack.proficiency, attack.bonus), (2, 2, 1));
+    assert_eq!(attack.total, attack.natural as i16 + 5);
+    let attack = character.roll_attack(Ability::Dexterity, false, RollMode::Normal, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!(attack.proficiency, 0);
+
+    // poisoned attacks with disadvantage, unless invisible too
+    character.apply_condition(Condition::Poisoned, ConditionDuration::UntilRemoved);
+    let attack = character.roll_attack(Ability::Strength, true, RollMode::Normal, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!((attack.mode, attack.rolls.len()), (RollMode::Disadvantage, 2));
+    let attack =
+        character.roll_attack(Ability::Strength, true, RollMode::Disadvantage, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!(attack.mode, RollMode::Disadvantage);
+    let attack = character.roll_attack(Ability::Strength, true, RollMode::Advantage, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!(attack.mode, RollMode::Normal);
+    character.apply_condition(Condition::Invisible, ConditionDuration::UntilRemoved);
+    let attack = character.roll_attack(Ability::Strength, true, RollMode::Normal, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!(attack.mode, RollMode::Normal);
+
+    // paralyzed creatures fail Strength and Dexterity saves whatever they roll
+    character.apply_condition(Condition::Paralyzed, ConditionDuration::UntilRemoved);
+    let save = character.roll_save(Ability::Dexterity, RollMode::Normal, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!(save.automatic_failure, Some(Condition::Paralyzed));
+    let save = character.roll_save(Ability::Wisdom, RollMode::Normal, 0, &mut StdRng::seed_from_u64(4));
+    assert_eq!(save.automatic_failure, None);
+}
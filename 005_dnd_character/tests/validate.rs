@@ -0,0 +1,43 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dnd_character::ability::{Ability, AbilityScore};
+use dnd_character::{random_character, RandomOptions, Severity, Skill};
+
+mod common;
+
+#[test]
+fn random_characters_follow_the_rules() {
+    let mut rng = StdRng::seed_from_u64(42);
+    for level in [1, 5, 12, 20] {
+        for _ in 0..20 {
+            let options = RandomOptions { level: Some(level), ..RandomOptions::default() };
+            let character = random_character(&options, &mut rng);
+            let errors: Vec<_> = character.validate().into_iter().filter(|v| v.severity == Severity::Error).collect();
+            assert!(errors.is_empty(), "{} {}: {:?}", character.race, character.class, errors);
+        }
+    }
+}
+
+#[test]
+fn hand_edited_characters_are_caught() {
+    let mut character = common::character("rogue", 1, 7);
+    let missing = Skill::ALL.into_iter().find(|s| !character.skills.contains(s)).unwrap();
+    character.expertise.insert(missing);
+    character.abilities.strength = AbilityScore::new(25);
+    character.saving_throws.remove(&Ability::Dexterity);
+
+    let violations = character.validate();
+    let messages: Vec<_> = violations.iter().map(|v| (v.severity, v.message.as_str())).collect();
+    assert!(
+        messages.contains(&(Severity::Error, "Rogue saving throws should be Dexterity and Intelligence")),
+        "{:?}",
+        messages
+    );
+    assert!(messages.contains(&(Severity::Warning, "Strength 25 is above 20")), "{:?}", messages);
+    assert!(violations.iter().any(|v| v.severity == Severity::Error && v.message.starts_with("expertise in")));
+    assert!(violations.iter().any(|v| v.message.ends_with("expertise picks, a level 1 Rogue has 2")));
+
+    // errors come before warnings
+    assert!(violations.windows(2).all(|pair| pair[0].severity >= pair[1].severity));
+}
@@ -0,0 +1,151 @@
+use serde_json::{json, Value};
+
+use dnd_character::equipment::{Item, ItemKind, Weapon, WeaponCategory};
+use dnd_character::vtt::GENERIC_SCHEMA;
+use dnd_character::{AdvancementMode, Character, Condition, ConditionDuration, DamageType, VttFormat};
+
+mod common;
+
+// every field the formats support, compared one by one so a failure names the field
+fn assert_same(a: &Character, b: &Character) {
+    assert_eq!(a.name, b.name);
+    assert_eq!((a.race, a.subrace, a.class, a.background), (b.race, b.subrace, b.class, b.background));
+    assert_eq!((a.level, a.experience, a.hit_dice_spent), (b.level, b.experience, b.hit_dice_spent));
+    for ability in dnd_character::Ability::ALL {
+        assert_eq!(a.abilities.score(ability), b.abilities.score(ability));
+    }
+    assert_eq!(a.hit_points, b.hit_points);
+    assert_eq!(a.saving_throws, b.saving_throws);
+    assert_eq!(a.skills, b.skills);
+    assert_eq!(a.expertise, b.expertise);
+    assert_eq!(a.languages, b.languages);
+    assert_eq!(a.tool_proficiencies, b.tool_proficiencies);
+    assert_eq!(a.resistances, b.resistances);
+    assert_eq!(a.vulnerabilities, b.vulnerabilities);
+    assert_eq!(a.feats, b.feats);
+    assert_eq!(a.equipment, b.equipment);
+    assert_eq!(a.spells, b.spells);
+    assert_eq!(a.spell_slots_used, b.spell_slots_used);
+    assert_eq!(a.personality, b.personality);
+    assert_eq!(a.notes, b.notes);
+}
+
+fn round_trip(character: &Character, format: VttFormat) -> Character {
+    let exported = character.to_vtt(format);
+    assert!(exported.unsupported.is_empty(), "{:?}", exported.unsupported);
+
+    // through text, like a real file
+    let text = serde_json::to_string(&exported.value).unwrap();
+    let imported = Character::from_vtt(&serde_json::from_str(&text).unwrap(), format).unwrap();
+    assert!(imported.unsupported.is_empty(), "{:?}", imported.unsupported);
+    imported.value
+}
+
+#[test]
+fn foundry_actors_round_trip() {
+    for seed in 0..30 {
+        let mut character = common::filled_in(seed);
+        character.death_saves.failures = 1;
+        character.equipment.push(Item {
+            name: "Sunblade".to_string(),
+            quantity: 1,
+            weight: 3.0,
+            kind: ItemKind::Weapon(Weapon {
+                category: WeaponCategory::Martial,
+                damage: "1d8".to_string(),
+                damage_type: DamageType::Radiant,
+                finesse: true,
+                ranged: false,
+            }),
+        });
+        let back = round_trip(&character, VttFormat::Foundry);
+        assert_same(&character, &back);
+        assert_eq!(character.death_saves, back.death_saves);
+    }
+}
+
+#[test]
+fn generic_documents_round_trip() {
+    for seed in 0..30 {
+        let mut character = common::filled_in(seed);
+        character.advancement = AdvancementMode::Milestone;
+        let back = round_trip(&character, VttFormat::Generic);
+        assert_same(&character, &back);
+        assert_eq!(back.advancement, AdvancementMode::Milestone);
+    }
+}
+
+#[test]
+fn unsupported_fields_are_reported() {
+    let mut character = common::filled_in(3);
+    character.apply_condition(Condition::Poisoned, ConditionDuration::UntilRemoved);
+    character.advancement = AdvancementMode::Milestone;
+    character.equipment.push(Item {
+        name: "Boomstick".to_string(),
+        quantity: 1,
+        weight: 6.0,
+        kind: ItemKind::Weapon(Weapon {
+            category: WeaponCategory::Martial,
+            damage: "2d8".to_string(),
+            damage_type: DamageType::Thunder,
+            finesse: false,
+            ranged: true,
+        }),
+    });
+
+    let foundry = character.to_vtt(VttFormat::Foundry).unsupported;
+    assert_eq!(foundry, ["1 active condition(s)", "milestone advancement"]);
+    let generic = character.to_vtt(VttFormat::Generic).unsupported;
+    assert_eq!(generic, ["1 active condition(s)", "weapon or armor stats of Boomstick"]);
+
+    let mut actor = character.to_vtt(VttFormat::Foundry).value;
+    actor["items"].as_array_mut().unwrap().push(json!({ "name": "Great Weapon Master", "type": "feat", "system": {} }));
+    actor["items"].as_array_mut().unwrap().push(json!({ "name": "Wildfire", "type": "subclass", "system": {} }));
+    actor["system"]["currency"] = json!({ "gp": 15 });
+    let imported = Character::from_vtt(&actor, VttFormat::Foundry).unwrap();
+    assert_eq!(imported.unsupported, ["feat Great Weapon Master", "subclass item Wildfire", "currency"]);
+}
+
+#[test]
+fn fields_the_importers_dont_read_are_reported() {
+    let character = common::filled_in(5);
+
+    let mut actor = character.to_vtt(VttFormat::Foundry).value;
+    actor["system"]["details"]["biography"]["public"] = json!("Known in every tavern.");
+    actor["system"]["attributes"]["inspiration"] = json!(true);
+    actor["prototypeToken"] = json!({ "name": character.name });
+    let imported = Character::from_vtt(&serde_json::from_str(&actor.to_string()).unwrap(), VttFormat::Foundry).unwrap();
+    assert_eq!(
+        imported.unsupported,
+        ["unread field system.attributes.inspiration", "unread field system.details.biography.public"]
+    );
+    assert_same(&character, &imported.value);
+
+    let mut document = character.to_vtt(VttFormat::Generic).value;
+    document["alignment"] = json!("Chaotic Good");
+    document["hit_points"]["rolled"] = json!([8, 5]);
+    let imported = Character::from_vtt(&document, VttFormat::Generic).unwrap();
+    assert_eq!(imported.unsupported, ["unread field alignment", "unread field hit_points.rolled"]);
+    assert_same(&character, &imported.value);
+}
+
+#[test]
+fn generic_documents_follow_the_schema() {
+    let schema: Value = serde_json::from_str(GENERIC_SCHEMA).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    let document = common::filled_in(8).to_vtt(VttFormat::Generic).value;
+    let document = document.as_object().unwrap();
+
+    for key in document.keys() {
+        assert!(properties.contains_key(key), "{} isn't in the schema", key);
+    }
+    for key in schema["required"].as_array().unwrap() {
+        assert!(document.contains_key(key.as_str().unwrap()), "{} is required", key);
+    }
+    for (key, definition) in [("skills", "skill"), ("languages", "language"), ("saving_throws", "ability")] {
+        let allowed = schema["$defs"][definition]["enum"].as_array().unwrap();
+        for value in document[key].as_array().unwrap() {
+            assert!(allowed.contains(value), "{} isn't an allowed {}", value, definition);
+        }
+    }
+}